@@ -0,0 +1,2 @@
+-- Add skipped status for nodes on branches that were not taken
+ALTER TYPE execution_status ADD VALUE IF NOT EXISTS 'skipped';
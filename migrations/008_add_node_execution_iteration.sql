@@ -0,0 +1,5 @@
+-- Add iteration index for nodes executed inside a loop body
+ALTER TABLE node_executions
+ADD COLUMN IF NOT EXISTS iteration INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_node_executions_iteration ON node_executions(execution_id, iteration);
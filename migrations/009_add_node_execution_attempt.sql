@@ -0,0 +1,3 @@
+-- Add attempt number for nodes retried by the engine
+ALTER TABLE node_executions
+ADD COLUMN IF NOT EXISTS attempt INTEGER NOT NULL DEFAULT 1;
@@ -0,0 +1,10 @@
+-- Create circuit_breakers table holding circuit state shared across executions
+CREATE TABLE IF NOT EXISTS circuit_breakers (
+    circuit_id VARCHAR(255) PRIMARY KEY,
+    state VARCHAR(20) NOT NULL DEFAULT 'closed',
+    failure_count INTEGER NOT NULL DEFAULT 0,
+    success_count INTEGER NOT NULL DEFAULT 0,
+    probes_in_flight INTEGER NOT NULL DEFAULT 0,
+    opened_at TIMESTAMP WITH TIME ZONE,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Add waiting status for executions suspended at a wait_webhook node
+ALTER TYPE execution_status ADD VALUE IF NOT EXISTS 'waiting';
+
+-- Create execution_waits table holding the state of suspended executions
+CREATE TABLE IF NOT EXISTS execution_waits (
+    wait_id VARCHAR(255) PRIMARY KEY,
+    execution_id UUID NOT NULL REFERENCES workflow_executions(id) ON DELETE CASCADE,
+    node_id VARCHAR(255) NOT NULL,
+    state JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_execution_waits_execution_id ON execution_waits(execution_id);
+CREATE INDEX IF NOT EXISTS idx_execution_waits_expires_at ON execution_waits(expires_at);
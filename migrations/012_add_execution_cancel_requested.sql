@@ -0,0 +1,3 @@
+-- Record cancellation requests so the process running an execution can stop it
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS cancel_requested_at TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,3 @@
+-- Store workflow-level settings (execution mode, timeouts, concurrency, ...)
+ALTER TABLE workflows
+ADD COLUMN IF NOT EXISTS settings JSONB NOT NULL DEFAULT '{}'::jsonb;
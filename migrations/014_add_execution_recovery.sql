@@ -0,0 +1,7 @@
+-- Let interrupted executions be detected and continued from their node executions
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS heartbeat_at TIMESTAMP WITH TIME ZONE;
+
+ALTER TABLE node_executions
+ADD COLUMN IF NOT EXISTS output_port VARCHAR(255),
+ADD COLUMN IF NOT EXISTS variables JSONB;
@@ -0,0 +1,10 @@
+-- Add pending status for executions waiting in the queue for a worker
+ALTER TYPE execution_status ADD VALUE IF NOT EXISTS 'pending';
+
+-- Create execution_queue table consumed by workers
+CREATE TABLE IF NOT EXISTS execution_queue (
+    execution_id UUID PRIMARY KEY REFERENCES workflow_executions(id) ON DELETE CASCADE,
+    enqueued_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_execution_queue_enqueued_at ON execution_queue(enqueued_at);
@@ -0,0 +1,7 @@
+-- Track the last fire time of every schedule_trigger node
+CREATE TABLE IF NOT EXISTS workflow_schedules (
+    workflow_id UUID NOT NULL REFERENCES workflows(id) ON DELETE CASCADE,
+    node_id VARCHAR(255) NOT NULL,
+    last_fired_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (workflow_id, node_id)
+);
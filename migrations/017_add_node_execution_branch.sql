@@ -0,0 +1,3 @@
+-- Add the key of the split branch a node executed in
+ALTER TABLE node_executions
+ADD COLUMN IF NOT EXISTS branch TEXT;
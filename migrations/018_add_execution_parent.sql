@@ -0,0 +1,7 @@
+-- Link sub-workflow executions to the execution and node that started them
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS parent_execution_id UUID REFERENCES workflow_executions(id) ON DELETE SET NULL,
+ADD COLUMN IF NOT EXISTS parent_node_id VARCHAR(255),
+ADD COLUMN IF NOT EXISTS depth INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_parent ON workflow_executions(parent_execution_id);
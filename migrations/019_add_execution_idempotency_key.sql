@@ -0,0 +1,10 @@
+-- Deduplicate execution starts that carry the same idempotency key
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS idempotency_key TEXT,
+ADD COLUMN IF NOT EXISTS idempotency_expires_at TIMESTAMP WITH TIME ZONE;
+
+-- A key is released (set to NULL) once its retention window passed, so it
+-- is unique per workflow within the window
+CREATE UNIQUE INDEX IF NOT EXISTS idx_workflow_executions_idempotency_key
+    ON workflow_executions(workflow_id, idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
@@ -0,0 +1,6 @@
+-- Link re-runs to the execution they re-run and the node they restart from
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS rerun_of UUID REFERENCES workflow_executions(id) ON DELETE SET NULL,
+ADD COLUMN IF NOT EXISTS rerun_from_node VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_workflow_executions_rerun_of ON workflow_executions(rerun_of);
@@ -0,0 +1,6 @@
+-- Add pinned status for nodes that returned their pinned output instead of running
+ALTER TYPE execution_status ADD VALUE IF NOT EXISTS 'pinned';
+
+-- Executions from production triggers can run every node for real
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS ignore_pinned_data BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,5 @@
+-- Partial executions run a single node, or a node and its ancestors
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS only_node VARCHAR(255),
+ADD COLUMN IF NOT EXISTS until_node VARCHAR(255),
+ADD COLUMN IF NOT EXISTS seed_execution_id UUID REFERENCES workflow_executions(id) ON DELETE SET NULL;
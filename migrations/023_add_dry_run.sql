@@ -0,0 +1,3 @@
+-- Dry runs mock the nodes with external side effects
+ALTER TABLE workflow_executions
+ADD COLUMN IF NOT EXISTS dry_run BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+-- A resumed wait keeps the run state and the payload until a worker continues the execution
+ALTER TABLE execution_waits
+ADD COLUMN IF NOT EXISTS payload JSONB,
+ADD COLUMN IF NOT EXISTS resumed_at TIMESTAMP WITH TIME ZONE;
@@ -1,4 +1,6 @@
-use crate::models::{NodeCategory, NodeRegistry, WorkflowDefinition};
+use crate::models::{
+    EdgeDefinition, NodeCategory, NodeDefinition, NodeRegistry, WorkflowDefinition,
+};
 use anyhow::{Context, Result};
 use std::path::Path;
 
@@ -46,32 +48,69 @@ fn validate_workflow(workflow: &WorkflowDefinition, registry: Option<&NodeRegist
         anyhow::bail!("Workflow must have at least one node");
     }
 
-    // Check that all node IDs are unique
+    if workflow.max_concurrency == Some(0) {
+        anyhow::bail!("max_concurrency must be at least 1");
+    }
+
+    if workflow.max_concurrent_executions == Some(0) {
+        anyhow::bail!("max_concurrent_executions must be at least 1");
+    }
+
+    // A failing error workflow would otherwise queue itself forever
+    if workflow.error_workflow.as_deref() == Some(workflow.name.as_str()) {
+        anyhow::bail!("error_workflow cannot be the workflow itself");
+    }
+
+    // Check node IDs, edges and cycles, including nested node bodies
     let mut seen_ids = std::collections::HashSet::new();
-    for node in &workflow.nodes {
+    validate_graph(&workflow.nodes, &workflow.edges, &mut seen_ids)?;
+
+    // If registry is provided, validate that first nodes are trigger nodes
+    if let Some(registry) = registry {
+        validate_trigger_nodes(workflow, registry)?;
+    }
+
+    Ok(())
+}
+
+/// Validate a graph of nodes and recurse into nested bodies (e.g. `loop` nodes).
+///
+/// Node IDs must be unique across the whole workflow, and edges may only
+/// connect nodes of the same graph.
+fn validate_graph<'a>(
+    nodes: &'a [NodeDefinition],
+    edges: &[EdgeDefinition],
+    seen_ids: &mut std::collections::HashSet<&'a str>,
+) -> Result<()> {
+    // Check that all node IDs are unique
+    let graph_ids: std::collections::HashSet<&str> =
+        nodes.iter().map(|node| node.id.as_str()).collect();
+    for node in nodes {
         if !seen_ids.insert(&node.id) {
             anyhow::bail!("Duplicate node ID: {}", node.id);
         }
     }
 
     // Check that all edges reference valid nodes
-    for edge in &workflow.edges {
-        if !seen_ids.contains(&edge.from) {
+    for edge in edges {
+        if !graph_ids.contains(edge.from.as_str()) {
             anyhow::bail!("Edge references unknown source node: {}", edge.from);
         }
-        if !seen_ids.contains(&edge.to) {
+        if !graph_ids.contains(edge.to.as_str()) {
             anyhow::bail!("Edge references unknown target node: {}", edge.to);
         }
     }
 
     // Check for cycles (basic check - we'll do a more thorough check during execution)
-    if has_cycle(workflow) {
+    if has_cycle(nodes, edges) {
         anyhow::bail!("Workflow contains a cycle");
     }
 
-    // If registry is provided, validate that first nodes are trigger nodes
-    if let Some(registry) = registry {
-        validate_trigger_nodes(workflow, registry)?;
+    for node in nodes {
+        if !node.edges.is_empty() && node.nodes.is_empty() {
+            anyhow::bail!("Node '{}' has body edges but no body nodes", node.id);
+        }
+        validate_graph(&node.nodes, &node.edges, seen_ids)?;
     }
 
     Ok(())
@@ -120,15 +159,15 @@ fn validate_trigger_nodes(workflow: &WorkflowDefinition, registry: &NodeRegistry
 }
 
 /// Check if the workflow has a cycle using DFS
-fn has_cycle(workflow: &WorkflowDefinition) -> bool {
+fn has_cycle(nodes: &[NodeDefinition], edges: &[EdgeDefinition]) -> bool {
     use std::collections::{HashMap, HashSet};
 
     // Build adjacency list
     let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-    for node in &workflow.nodes {
+    for node in nodes {
         graph.insert(&node.id, Vec::new());
     }
-    for edge in &workflow.edges {
+    for edge in edges {
         graph.get_mut(edge.from.as_str()).unwrap().push(&edge.to);
     }
 
@@ -161,7 +200,7 @@ fn has_cycle(workflow: &WorkflowDefinition) -> bool {
         false
     }
 
-    for node in workflow.nodes.iter() {
+    for node in nodes {
         if !visited.contains(node.id.as_str())
             && dfs(&node.id, &graph, &mut visited, &mut rec_stack)
         {
@@ -223,6 +262,117 @@ edges:
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_max_concurrency_validation() {
+        let yaml = r#"
+name: Fan-out Workflow
+execution_mode: parallel
+max_concurrency: 0
+nodes:
+  - id: node1
+    node_type: start
+    name: Node 1
+    parameters: {}
+edges: []
+"#;
+
+        assert!(load_workflow_from_str(yaml).is_err());
+
+        let workflow =
+            load_workflow_from_str(&yaml.replace("max_concurrency: 0", "max_concurrency: 4"))
+                .unwrap();
+        assert_eq!(workflow.max_concurrency, Some(4));
+    }
+
+    #[test]
+    fn test_max_concurrent_executions_validation() {
+        let yaml = r#"
+name: Nightly Sync
+max_concurrent_executions: 0
+overlap_policy: skip
+nodes:
+  - id: node1
+    node_type: start
+    name: Node 1
+    parameters: {}
+edges: []
+"#;
+
+        assert!(load_workflow_from_str(yaml).is_err());
+
+        let workflow = load_workflow_from_str(&yaml.replace(
+            "max_concurrent_executions: 0",
+            "max_concurrent_executions: 2",
+        ))
+        .unwrap();
+        assert_eq!(
+            workflow.execution_limit(),
+            Some((2, crate::models::OverlapPolicy::Skip))
+        );
+    }
+
+    #[test]
+    fn test_error_workflow_validation() {
+        let yaml = r#"
+name: Billing Sync
+error_workflow: Billing Sync
+nodes:
+  - id: node1
+    node_type: start
+    name: Node 1
+    parameters: {}
+edges: []
+"#;
+
+        assert!(load_workflow_from_str(yaml).is_err());
+
+        let workflow = load_workflow_from_str(&yaml.replace(
+            "error_workflow: Billing Sync",
+            "error_workflow: Notify On-call",
+        ))
+        .unwrap();
+        assert_eq!(workflow.error_workflow.as_deref(), Some("Notify On-call"));
+    }
+
+    #[test]
+    fn test_nested_body_validation() {
+        let yaml = r#"
+name: Loop Workflow
+nodes:
+  - id: node1
+    node_type: start
+    name: Start
+  - id: each
+    node_type: loop
+    name: Each
+    parameters:
+      items: [1, 2]
+    nodes:
+      - id: body1
+        node_type: transform
+        name: Body 1
+      - id: body2
+        node_type: transform
+        name: Body 2
+    edges:
+      - from: body1
+        to: body2
+edges:
+  - from: node1
+    to: each
+"#;
+        assert!(load_workflow_from_str(yaml).is_ok());
+
+        // Body node IDs must be unique across the workflow
+        let duplicate = yaml.replace("id: body2", "id: node1");
+        let duplicate = duplicate.replace("to: body2", "to: node1");
+        assert!(load_workflow_from_str(&duplicate).is_err());
+
+        // Body edges may only connect body nodes
+        let outer_edge = yaml.replace("to: body2", "to: each");
+        assert!(load_workflow_from_str(&outer_edge).is_err());
+    }
+
     #[test]
     fn test_trigger_node_validation_with_trigger_start() {
         use crate::nodes::{HttpRequestNode, ManualTriggerNode};
@@ -0,0 +1,80 @@
+use crate::models::CircuitBreakerState;
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+/// Get the state of a circuit breaker, if it has been used
+pub async fn get_circuit_breaker(
+    pool: &PgPool,
+    circuit_id: &str,
+) -> Result<Option<CircuitBreakerState>> {
+    let circuit = sqlx::query_as::<_, CircuitBreakerState>(
+        r#"
+        SELECT * FROM circuit_breakers WHERE circuit_id = $1
+        "#,
+    )
+    .bind(circuit_id)
+    .fetch_optional(pool)
+    .await
+    .context("Failed to get circuit breaker")?;
+
+    Ok(circuit)
+}
+
+/// Atomically read, modify and store the state of a circuit breaker.
+///
+/// The row is created on first use and locked for the duration of `update`,
+/// so concurrent executions sharing a circuit see each other's changes.
+pub async fn update_circuit_breaker<T>(
+    pool: &PgPool,
+    circuit_id: &str,
+    update: impl FnOnce(&mut CircuitBreakerState) -> T,
+) -> Result<T> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO circuit_breakers (circuit_id)
+        VALUES ($1)
+        ON CONFLICT (circuit_id) DO NOTHING
+        "#,
+    )
+    .bind(circuit_id)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to create circuit breaker")?;
+
+    let mut circuit = sqlx::query_as::<_, CircuitBreakerState>(
+        r#"
+        SELECT * FROM circuit_breakers WHERE circuit_id = $1 FOR UPDATE
+        "#,
+    )
+    .bind(circuit_id)
+    .fetch_one(&mut *tx)
+    .await
+    .context("Failed to lock circuit breaker")?;
+
+    let result = update(&mut circuit);
+
+    sqlx::query(
+        r#"
+        UPDATE circuit_breakers
+        SET state = $2, failure_count = $3, success_count = $4, probes_in_flight = $5,
+            opened_at = $6, updated_at = $7
+        WHERE circuit_id = $1
+        "#,
+    )
+    .bind(circuit_id)
+    .bind(circuit.state.to_string())
+    .bind(circuit.failure_count)
+    .bind(circuit.success_count)
+    .bind(circuit.probes_in_flight)
+    .bind(circuit.opened_at)
+    .bind(circuit.updated_at)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to update circuit breaker")?;
+
+    tx.commit().await?;
+
+    Ok(result)
+}
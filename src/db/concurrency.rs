@@ -0,0 +1,47 @@
+use anyhow::{Context, Result};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// First half of the advisory lock key taken for a concurrency key; the
+/// second half is the hash of the concurrency key
+const CONCURRENCY_LOCK_NAMESPACE: i32 = 0x706d_7063;
+
+/// Wait for the lock of a concurrency key for the current transaction.
+///
+/// Holding it while counting running executions and starting a new one
+/// keeps processes from starting executions over the limit at the same time.
+pub async fn lock_concurrency_key(conn: &mut PgConnection, key: &str) -> Result<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1, hashtext($2))")
+        .bind(CONCURRENCY_LOCK_NAMESPACE)
+        .bind(key)
+        .execute(conn)
+        .await
+        .context("Failed to take concurrency lock")?;
+
+    Ok(())
+}
+
+/// IDs of the running executions of the workflows sharing a concurrency
+/// key, oldest first.
+///
+/// A workflow without a `concurrency_key` setting uses its own ID as key.
+/// Waiting executions do not count: they go back through the queue, and are
+/// admitted again, when they resume. Interrupted executions stay `running`
+/// until they are recovered, so they keep their slot meanwhile.
+pub async fn list_running_executions(conn: &mut PgConnection, key: &str) -> Result<Vec<Uuid>> {
+    let executions = sqlx::query_scalar(
+        r#"
+        SELECT workflow_executions.id FROM workflow_executions
+        JOIN workflows ON workflows.id = workflow_executions.workflow_id
+        WHERE workflow_executions.status = 'running'
+          AND COALESCE(workflows.settings->>'concurrency_key', workflows.id::text) = $1
+        ORDER BY workflow_executions.started_at
+        "#,
+    )
+    .bind(key)
+    .fetch_all(conn)
+    .await
+    .context("Failed to list running executions")?;
+
+    Ok(executions)
+}
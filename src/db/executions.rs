@@ -1,18 +1,20 @@
-use crate::models::{ExecutionStatus, NodeExecution, WorkflowExecution};
+use crate::models::{ExecutionStatus, ExecutionWait, NodeExecution, WorkflowExecution};
 use anyhow::{Context, Result};
 use chrono::Utc;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use std::collections::HashMap;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Create a new workflow execution
 pub async fn create_workflow_execution(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     execution: &WorkflowExecution,
 ) -> Result<WorkflowExecution> {
     let result = sqlx::query_as::<_, WorkflowExecution>(
         r#"
-        INSERT INTO workflow_executions (id, workflow_id, status, started_at, finished_at, input_data, output_data, error)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO workflow_executions (id, workflow_id, status, started_at, finished_at, input_data, output_data, error, parent_execution_id, parent_node_id, depth, idempotency_key, idempotency_expires_at, rerun_of, rerun_from_node, ignore_pinned_data, only_node, until_node, seed_execution_id, dry_run)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
         RETURNING *
         "#,
     )
@@ -24,7 +26,19 @@ pub async fn create_workflow_execution(
     .bind(&execution.input_data)
     .bind(&execution.output_data)
     .bind(&execution.error)
-    .fetch_one(pool)
+    .bind(execution.parent_execution_id)
+    .bind(&execution.parent_node_id)
+    .bind(execution.depth)
+    .bind(&execution.idempotency_key)
+    .bind(execution.idempotency_expires_at)
+    .bind(execution.rerun_of)
+    .bind(&execution.rerun_from_node)
+    .bind(execution.ignore_pinned_data)
+    .bind(&execution.only_node)
+    .bind(&execution.until_node)
+    .bind(execution.seed_execution_id)
+    .bind(execution.dry_run)
+    .fetch_one(executor)
     .await
     .context("Failed to create workflow execution")?;
 
@@ -82,9 +96,29 @@ pub async fn list_workflow_executions(
     Ok(executions)
 }
 
+/// List the sub-workflow executions started by the nodes of an execution
+pub async fn list_child_executions(
+    pool: &PgPool,
+    parent_execution_id: Uuid,
+) -> Result<Vec<WorkflowExecution>> {
+    let executions = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        SELECT * FROM workflow_executions
+        WHERE parent_execution_id = $1
+        ORDER BY started_at
+        "#,
+    )
+    .bind(parent_execution_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to list child executions")?;
+
+    Ok(executions)
+}
+
 /// Update workflow execution status
 pub async fn update_workflow_execution_status(
-    pool: &PgPool,
+    executor: impl PgExecutor<'_>,
     id: Uuid,
     status: ExecutionStatus,
     output_data: Option<serde_json::Value>,
@@ -100,16 +134,103 @@ pub async fn update_workflow_execution_status(
     )
     .bind(id)
     .bind(status.to_string())
-    .bind(Utc::now())
+    .bind(status.is_finished().then(Utc::now))
     .bind(output_data)
     .bind(error)
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
     .context("Failed to update workflow execution status")?;
 
     Ok(result)
 }
 
+/// Flag a running execution for cancellation
+pub async fn request_execution_cancel(
+    executor: impl PgExecutor<'_>,
+    id: Uuid,
+) -> Result<WorkflowExecution> {
+    let result = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        UPDATE workflow_executions
+        SET cancel_requested_at = COALESCE(cancel_requested_at, NOW())
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .fetch_one(executor)
+    .await
+    .context("Failed to request execution cancellation")?;
+
+    Ok(result)
+}
+
+/// Record that an execution is still being run and check whether its
+/// cancellation was requested
+pub async fn heartbeat_execution(pool: &PgPool, id: Uuid) -> Result<bool> {
+    let requested: bool = sqlx::query_scalar(
+        r#"
+        UPDATE workflow_executions
+        SET heartbeat_at = NOW()
+        WHERE id = $1
+        RETURNING cancel_requested_at IS NOT NULL
+        "#,
+    )
+    .bind(id)
+    .fetch_one(pool)
+    .await
+    .context("Failed to record execution heartbeat")?;
+
+    Ok(requested)
+}
+
+/// List running executions without a heartbeat for `stale_after`
+pub async fn list_stale_executions(
+    pool: &PgPool,
+    stale_after: Duration,
+) -> Result<Vec<WorkflowExecution>> {
+    let executions = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        SELECT * FROM workflow_executions
+        WHERE status = 'running'
+          AND COALESCE(heartbeat_at, started_at) < NOW() - make_interval(secs => $1)
+        ORDER BY started_at ASC
+        "#,
+    )
+    .bind(stale_after.as_secs_f64())
+    .fetch_all(pool)
+    .await
+    .context("Failed to list stale executions")?;
+
+    Ok(executions)
+}
+
+/// Claim a running execution without a heartbeat for `stale_after`, so that
+/// only one process recovers it
+pub async fn claim_stale_execution(
+    pool: &PgPool,
+    id: Uuid,
+    stale_after: Duration,
+) -> Result<Option<WorkflowExecution>> {
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        UPDATE workflow_executions
+        SET heartbeat_at = NOW()
+        WHERE id = $1
+          AND status = 'running'
+          AND COALESCE(heartbeat_at, started_at) < NOW() - make_interval(secs => $2)
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(stale_after.as_secs_f64())
+    .fetch_optional(pool)
+    .await
+    .context("Failed to claim stale execution")?;
+
+    Ok(execution)
+}
+
 /// Create a new node execution
 pub async fn create_node_execution(
     pool: &PgPool,
@@ -117,8 +238,8 @@ pub async fn create_node_execution(
 ) -> Result<NodeExecution> {
     let result = sqlx::query_as::<_, NodeExecution>(
         r#"
-        INSERT INTO node_executions (id, execution_id, node_id, status, started_at, finished_at, input_data, output_data, error)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        INSERT INTO node_executions (id, execution_id, node_id, status, started_at, finished_at, input_data, output_data, error, iteration, attempt, branch, output_port, variables)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
         RETURNING *
         "#,
     )
@@ -131,6 +252,11 @@ pub async fn create_node_execution(
     .bind(&execution.input_data)
     .bind(&execution.output_data)
     .bind(&execution.error)
+    .bind(execution.iteration)
+    .bind(execution.attempt)
+    .bind(&execution.branch)
+    .bind(&execution.output_port)
+    .bind(&execution.variables)
     .fetch_one(pool)
     .await
     .context("Failed to create node execution")?;
@@ -156,7 +282,7 @@ pub async fn update_node_execution_status(
     )
     .bind(id)
     .bind(status.to_string())
-    .bind(Utc::now())
+    .bind(status.is_finished().then(Utc::now))
     .bind(output_data)
     .bind(error)
     .fetch_one(pool)
@@ -166,6 +292,36 @@ pub async fn update_node_execution_status(
     Ok(result)
 }
 
+/// Complete a node execution with its output, output port and the variables it set
+pub async fn complete_node_execution(
+    pool: &PgPool,
+    id: Uuid,
+    status: ExecutionStatus,
+    output_data: serde_json::Value,
+    output_port: Option<&str>,
+    variables: &HashMap<String, serde_json::Value>,
+) -> Result<NodeExecution> {
+    let result = sqlx::query_as::<_, NodeExecution>(
+        r#"
+        UPDATE node_executions
+        SET status = $2, finished_at = $3, output_data = $4, output_port = $5, variables = $6, error = NULL
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(status.to_string())
+    .bind(status.is_finished().then(Utc::now))
+    .bind(output_data)
+    .bind(output_port)
+    .bind(serde_json::to_value(variables)?)
+    .fetch_one(pool)
+    .await
+    .context("Failed to complete node execution")?;
+
+    Ok(result)
+}
+
 /// List node executions for a workflow execution
 pub async fn list_node_executions(pool: &PgPool, execution_id: Uuid) -> Result<Vec<NodeExecution>> {
     let executions = sqlx::query_as::<_, NodeExecution>(
@@ -182,3 +338,194 @@ pub async fn list_node_executions(pool: &PgPool, execution_id: Uuid) -> Result<V
 
     Ok(executions)
 }
+
+/// Update the node execution a suspended execution is waiting on
+pub async fn update_waiting_node_execution(
+    executor: impl PgExecutor<'_>,
+    execution_id: Uuid,
+    node_id: &str,
+    status: ExecutionStatus,
+    output_data: Option<serde_json::Value>,
+    error: Option<String>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE node_executions
+        SET status = $3, finished_at = $4, output_data = COALESCE($5, output_data), error = $6
+        WHERE execution_id = $1 AND node_id = $2 AND status = 'waiting'
+        "#,
+    )
+    .bind(execution_id)
+    .bind(node_id)
+    .bind(status.to_string())
+    .bind(status.is_finished().then(Utc::now))
+    .bind(output_data)
+    .bind(error)
+    .execute(executor)
+    .await
+    .context("Failed to update waiting node execution")?;
+
+    Ok(())
+}
+
+/// Store the state of an execution suspended at a wait node
+pub async fn create_execution_wait(pool: &PgPool, wait: &ExecutionWait) -> Result<ExecutionWait> {
+    let result = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        INSERT INTO execution_waits (wait_id, execution_id, node_id, state, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(&wait.wait_id)
+    .bind(wait.execution_id)
+    .bind(&wait.node_id)
+    .bind(&wait.state)
+    .bind(wait.created_at)
+    .bind(wait.expires_at)
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("Failed to create execution wait {}", wait.wait_id))?;
+
+    Ok(result)
+}
+
+/// Lock a wait that was not resumed yet for the current transaction, so
+/// that only one caller can resume or expire it
+pub async fn lock_execution_wait(
+    conn: &mut PgConnection,
+    wait_id: &str,
+) -> Result<Option<ExecutionWait>> {
+    let wait = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        SELECT * FROM execution_waits
+        WHERE wait_id = $1 AND resumed_at IS NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(wait_id)
+    .fetch_optional(conn)
+    .await
+    .context("Failed to lock execution wait")?;
+
+    Ok(wait)
+}
+
+/// Record the payload a wait was resumed with
+pub async fn resume_execution_wait(
+    executor: impl PgExecutor<'_>,
+    wait_id: &str,
+    payload: &serde_json::Value,
+) -> Result<ExecutionWait> {
+    let wait = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        UPDATE execution_waits
+        SET payload = $2, resumed_at = NOW()
+        WHERE wait_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(wait_id)
+    .bind(payload)
+    .fetch_one(executor)
+    .await
+    .context("Failed to resume execution wait")?;
+
+    Ok(wait)
+}
+
+/// Remove and return a wait that was not resumed, so that it can only be
+/// expired once
+pub async fn take_execution_wait(
+    executor: impl PgExecutor<'_>,
+    wait_id: &str,
+) -> Result<Option<ExecutionWait>> {
+    let wait = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        DELETE FROM execution_waits WHERE wait_id = $1 AND resumed_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(wait_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to take execution wait")?;
+
+    Ok(wait)
+}
+
+/// Remove and return the resumed wait of a queued execution, which holds
+/// the state the execution continues from
+pub async fn take_resumed_execution_wait(
+    executor: impl PgExecutor<'_>,
+    execution_id: Uuid,
+) -> Result<Option<ExecutionWait>> {
+    let wait = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        DELETE FROM execution_waits WHERE execution_id = $1 AND resumed_at IS NOT NULL
+        RETURNING *
+        "#,
+    )
+    .bind(execution_id)
+    .fetch_optional(executor)
+    .await
+    .context("Failed to take resumed execution wait")?;
+
+    Ok(wait)
+}
+
+/// Whether a queued execution was resumed from a wait
+pub async fn has_resumed_execution_wait(
+    executor: impl PgExecutor<'_>,
+    execution_id: Uuid,
+) -> Result<bool> {
+    let resumed = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM execution_waits
+            WHERE execution_id = $1 AND resumed_at IS NOT NULL
+        )
+        "#,
+    )
+    .bind(execution_id)
+    .fetch_one(executor)
+    .await
+    .context("Failed to look up resumed execution wait")?;
+
+    Ok(resumed)
+}
+
+/// List the waits that passed their deadline without being resumed
+pub async fn list_expired_execution_waits(pool: &PgPool) -> Result<Vec<ExecutionWait>> {
+    let waits = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        SELECT * FROM execution_waits
+        WHERE expires_at <= NOW() AND resumed_at IS NULL
+        ORDER BY expires_at
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("Failed to list expired execution waits")?;
+
+    Ok(waits)
+}
+
+/// Remove and return the waits of an execution
+pub async fn take_execution_waits_for(
+    pool: &PgPool,
+    execution_id: Uuid,
+) -> Result<Vec<ExecutionWait>> {
+    let waits = sqlx::query_as::<_, ExecutionWait>(
+        r#"
+        DELETE FROM execution_waits WHERE execution_id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(execution_id)
+    .fetch_all(pool)
+    .await
+    .context("Failed to take execution waits")?;
+
+    Ok(waits)
+}
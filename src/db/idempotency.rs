@@ -0,0 +1,66 @@
+use crate::models::WorkflowExecution;
+use anyhow::{Context, Result};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// First half of the advisory lock key taken for an idempotency key; the
+/// second half is the hash of the workflow ID and the idempotency key
+const IDEMPOTENCY_LOCK_NAMESPACE: i32 = 0x706d_7069;
+
+/// Wait for the lock of a workflow's idempotency key for the current
+/// transaction.
+///
+/// Holding it while looking up the key and creating the execution keeps
+/// concurrent requests with the same key from both creating one.
+pub async fn lock_idempotency_key(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    key: &str,
+) -> Result<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1, hashtext($2 || ':' || $3))")
+        .bind(IDEMPOTENCY_LOCK_NAMESPACE)
+        .bind(workflow_id.to_string())
+        .bind(key)
+        .execute(conn)
+        .await
+        .context("Failed to take idempotency lock")?;
+
+    Ok(())
+}
+
+/// Get the execution of a workflow started with an idempotency key.
+///
+/// Once the retention window of the key passed, the key is released from
+/// its execution and `None` is returned, so a new execution can take it.
+pub async fn find_execution_by_idempotency_key(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    key: &str,
+) -> Result<Option<WorkflowExecution>> {
+    sqlx::query(
+        r#"
+        UPDATE workflow_executions
+        SET idempotency_key = NULL
+        WHERE workflow_id = $1 AND idempotency_key = $2 AND idempotency_expires_at <= NOW()
+        "#,
+    )
+    .bind(workflow_id)
+    .bind(key)
+    .execute(&mut *conn)
+    .await
+    .context("Failed to release expired idempotency key")?;
+
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        SELECT * FROM workflow_executions
+        WHERE workflow_id = $1 AND idempotency_key = $2
+        "#,
+    )
+    .bind(workflow_id)
+    .bind(key)
+    .fetch_optional(conn)
+    .await
+    .context("Failed to get execution by idempotency key")?;
+
+    Ok(execution)
+}
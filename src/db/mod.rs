@@ -1,7 +1,17 @@
+pub mod circuit_breakers;
+pub mod concurrency;
 pub mod executions;
+pub mod idempotency;
 pub mod pool;
+pub mod queue;
+pub mod schedules;
 pub mod workflows;
 
+pub use circuit_breakers::*;
+pub use concurrency::*;
 pub use executions::*;
+pub use idempotency::*;
 pub use pool::*;
+pub use queue::*;
+pub use schedules::*;
 pub use workflows::*;
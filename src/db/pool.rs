@@ -18,6 +18,24 @@ pub async fn run_migrations(pool: &PgPool) -> Result<()> {
         include_str!("../../migrations/001_create_workflows.sql"),
         include_str!("../../migrations/002_create_executions.sql"),
         include_str!("../../migrations/003_add_execution_tracking.sql"),
+        include_str!("../../migrations/007_add_skipped_status.sql"),
+        include_str!("../../migrations/008_add_node_execution_iteration.sql"),
+        include_str!("../../migrations/009_add_node_execution_attempt.sql"),
+        include_str!("../../migrations/010_create_circuit_breakers.sql"),
+        include_str!("../../migrations/011_add_execution_waits.sql"),
+        include_str!("../../migrations/012_add_execution_cancel_requested.sql"),
+        include_str!("../../migrations/013_add_workflow_settings.sql"),
+        include_str!("../../migrations/014_add_execution_recovery.sql"),
+        include_str!("../../migrations/015_create_execution_queue.sql"),
+        include_str!("../../migrations/016_create_workflow_schedules.sql"),
+        include_str!("../../migrations/017_add_node_execution_branch.sql"),
+        include_str!("../../migrations/018_add_execution_parent.sql"),
+        include_str!("../../migrations/019_add_execution_idempotency_key.sql"),
+        include_str!("../../migrations/020_add_execution_rerun.sql"),
+        include_str!("../../migrations/021_add_pinned_data.sql"),
+        include_str!("../../migrations/022_add_partial_executions.sql"),
+        include_str!("../../migrations/023_add_dry_run.sql"),
+        include_str!("../../migrations/024_add_execution_wait_resume.sql"),
     ];
 
     for (idx, migration) in migrations.iter().enumerate() {
@@ -0,0 +1,157 @@
+use crate::db::create_workflow_execution;
+use crate::models::{ExecutionStatus, WorkflowExecution};
+use anyhow::{Context, Result};
+use sqlx::{Acquire, PgConnection, PgExecutor, Postgres};
+use uuid::Uuid;
+
+/// Create a pending execution and add it to the queue.
+///
+/// Accepts a pool or a connection of an enclosing transaction.
+pub async fn enqueue_execution<'a>(
+    conn: impl Acquire<'a, Database = Postgres>,
+    execution: &WorkflowExecution,
+) -> Result<WorkflowExecution> {
+    let mut tx = conn.begin().await.context("Failed to begin transaction")?;
+
+    let execution = create_workflow_execution(&mut *tx, execution).await?;
+    sqlx::query(
+        r#"
+        INSERT INTO execution_queue (execution_id) VALUES ($1)
+        "#,
+    )
+    .bind(execution.id)
+    .execute(&mut *tx)
+    .await
+    .context("Failed to enqueue execution")?;
+
+    tx.commit().await.context("Failed to commit transaction")?;
+
+    Ok(execution)
+}
+
+/// Lock the oldest queued execution that is not in `deferred` for the
+/// current transaction.
+///
+/// Rows locked by other workers are skipped, so every execution is taken by
+/// exactly one worker. The execution stays queued until it is started or
+/// finished in the same transaction.
+pub async fn next_queued_execution(
+    conn: &mut PgConnection,
+    deferred: &[Uuid],
+) -> Result<Option<WorkflowExecution>> {
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        SELECT workflow_executions.* FROM execution_queue
+        JOIN workflow_executions ON workflow_executions.id = execution_queue.execution_id
+        WHERE NOT (execution_queue.execution_id = ANY($1))
+        ORDER BY execution_queue.enqueued_at
+        FOR UPDATE OF execution_queue SKIP LOCKED
+        LIMIT 1
+        "#,
+    )
+    .bind(deferred)
+    .fetch_optional(conn)
+    .await
+    .context("Failed to get queued execution")?;
+
+    Ok(execution)
+}
+
+/// Take a locked execution off the queue and mark it as running
+pub async fn start_queued_execution(
+    conn: &mut PgConnection,
+    execution_id: Uuid,
+) -> Result<WorkflowExecution> {
+    remove_queued_execution(&mut *conn, execution_id).await?;
+
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        UPDATE workflow_executions
+        SET status = 'running', started_at = NOW(), heartbeat_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING *
+        "#,
+    )
+    .bind(execution_id)
+    .fetch_one(conn)
+    .await
+    .context("Failed to start queued execution")?;
+
+    Ok(execution)
+}
+
+/// Move a waiting execution back to the queue, so that a worker continues it
+pub async fn requeue_waiting_execution(
+    conn: &mut PgConnection,
+    execution_id: Uuid,
+) -> Result<WorkflowExecution> {
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        UPDATE workflow_executions
+        SET status = 'pending'
+        WHERE id = $1 AND status = 'waiting'
+        RETURNING *
+        "#,
+    )
+    .bind(execution_id)
+    .fetch_one(&mut *conn)
+    .await
+    .context("Failed to requeue waiting execution")?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO execution_queue (execution_id) VALUES ($1)
+        "#,
+    )
+    .bind(execution_id)
+    .execute(conn)
+    .await
+    .context("Failed to enqueue execution")?;
+
+    Ok(execution)
+}
+
+/// Take a locked execution off the queue and finish it without running it
+pub async fn finish_queued_execution(
+    conn: &mut PgConnection,
+    execution_id: Uuid,
+    status: ExecutionStatus,
+    error: String,
+) -> Result<WorkflowExecution> {
+    remove_queued_execution(&mut *conn, execution_id).await?;
+
+    let execution = sqlx::query_as::<_, WorkflowExecution>(
+        r#"
+        UPDATE workflow_executions
+        SET status = $2, finished_at = NOW(), error = $3
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(execution_id)
+    .bind(status.to_string())
+    .bind(error)
+    .fetch_one(conn)
+    .await
+    .context("Failed to finish queued execution")?;
+
+    Ok(execution)
+}
+
+/// Remove an execution from the queue; returns whether it was still queued
+pub async fn remove_queued_execution(
+    executor: impl PgExecutor<'_>,
+    execution_id: Uuid,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM execution_queue WHERE execution_id = $1
+        "#,
+    )
+    .bind(execution_id)
+    .execute(executor)
+    .await
+    .context("Failed to remove queued execution")?;
+
+    Ok(result.rows_affected() > 0)
+}
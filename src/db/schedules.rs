@@ -0,0 +1,65 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// Advisory lock key held by the scheduler replica firing the current tick
+const SCHEDULER_LOCK_KEY: i64 = 0x706d_705f_7363_6864;
+
+/// Try to take the scheduler lock for the current transaction.
+///
+/// Returns `false` if another replica holds it; the lock is released when
+/// the transaction ends.
+pub async fn try_lock_scheduler(conn: &mut PgConnection) -> Result<bool> {
+    let locked: bool = sqlx::query_scalar("SELECT pg_try_advisory_xact_lock($1)")
+        .bind(SCHEDULER_LOCK_KEY)
+        .fetch_one(conn)
+        .await
+        .context("Failed to take scheduler lock")?;
+
+    Ok(locked)
+}
+
+/// Get the last fire time of a schedule, if it has been seen before
+pub async fn get_schedule_last_fired(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    node_id: &str,
+) -> Result<Option<DateTime<Utc>>> {
+    let last_fired_at = sqlx::query_scalar(
+        r#"
+        SELECT last_fired_at FROM workflow_schedules WHERE workflow_id = $1 AND node_id = $2
+        "#,
+    )
+    .bind(workflow_id)
+    .bind(node_id)
+    .fetch_optional(conn)
+    .await
+    .context("Failed to get schedule")?;
+
+    Ok(last_fired_at)
+}
+
+/// Store the last fire time of a schedule
+pub async fn set_schedule_last_fired(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    node_id: &str,
+    last_fired_at: DateTime<Utc>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO workflow_schedules (workflow_id, node_id, last_fired_at)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (workflow_id, node_id) DO UPDATE SET last_fired_at = EXCLUDED.last_fired_at
+        "#,
+    )
+    .bind(workflow_id)
+    .bind(node_id)
+    .bind(last_fired_at)
+    .execute(conn)
+    .await
+    .context("Failed to update schedule")?;
+
+    Ok(())
+}
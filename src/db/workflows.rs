@@ -1,14 +1,14 @@
 use crate::models::{Workflow, WorkflowDefinition};
 use anyhow::{Context, Result};
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 
 /// Create a new workflow in the database
 pub async fn create_workflow(pool: &PgPool, workflow: &Workflow) -> Result<Workflow> {
     let result = sqlx::query_as::<_, Workflow>(
         r#"
-        INSERT INTO workflows (id, name, description, active, nodes, edges, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO workflows (id, name, description, active, nodes, edges, settings, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
         RETURNING *
         "#,
     )
@@ -18,6 +18,7 @@ pub async fn create_workflow(pool: &PgPool, workflow: &Workflow) -> Result<Workf
     .bind(workflow.active)
     .bind(&workflow.nodes)
     .bind(&workflow.edges)
+    .bind(&workflow.settings)
     .bind(workflow.created_at)
     .bind(workflow.updated_at)
     .fetch_one(pool)
@@ -28,14 +29,14 @@ pub async fn create_workflow(pool: &PgPool, workflow: &Workflow) -> Result<Workf
 }
 
 /// Get a workflow by ID
-pub async fn get_workflow(pool: &PgPool, id: Uuid) -> Result<Workflow> {
+pub async fn get_workflow(executor: impl PgExecutor<'_>, id: Uuid) -> Result<Workflow> {
     let workflow = sqlx::query_as::<_, Workflow>(
         r#"
         SELECT * FROM workflows WHERE id = $1
         "#,
     )
     .bind(id)
-    .fetch_one(pool)
+    .fetch_one(executor)
     .await
     .context("Failed to get workflow")?;
 
@@ -78,7 +79,8 @@ pub async fn update_workflow(pool: &PgPool, workflow: &Workflow) -> Result<Workf
     let result = sqlx::query_as::<_, Workflow>(
         r#"
         UPDATE workflows
-        SET name = $2, description = $3, active = $4, nodes = $5, edges = $6, updated_at = NOW()
+        SET name = $2, description = $3, active = $4, nodes = $5, edges = $6, settings = $7,
+            updated_at = NOW()
         WHERE id = $1
         RETURNING *
         "#,
@@ -89,6 +91,7 @@ pub async fn update_workflow(pool: &PgPool, workflow: &Workflow) -> Result<Workf
     .bind(workflow.active)
     .bind(&workflow.nodes)
     .bind(&workflow.edges)
+    .bind(&workflow.settings)
     .fetch_one(pool)
     .await
     .context("Failed to update workflow")?;
@@ -0,0 +1,109 @@
+use crate::db;
+use crate::models::CircuitBreakerState;
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+
+/// Prefix of the Redis keys holding circuit breaker state
+const REDIS_KEY_PREFIX: &str = "pmp-workflow:circuit:";
+
+/// Store for circuit breaker state shared across workflow executions
+#[derive(Clone)]
+pub enum CircuitStore {
+    /// Keep state in the `circuit_breakers` table
+    Postgres(PgPool),
+    /// Keep state in Redis, one JSON value per circuit
+    Redis(redis::Client),
+}
+
+impl CircuitStore {
+    /// Create a store backed by the Redis server at `url`
+    pub fn redis(url: &str) -> Result<Self> {
+        let client = redis::Client::open(url).context("Invalid Redis URL")?;
+        Ok(Self::Redis(client))
+    }
+
+    /// Get the state of a circuit; circuits that were never used are closed
+    pub async fn get(&self, circuit_id: &str) -> Result<CircuitBreakerState> {
+        let circuit = match self {
+            CircuitStore::Postgres(pool) => db::get_circuit_breaker(pool, circuit_id).await?,
+            CircuitStore::Redis(client) => {
+                let mut conn = client
+                    .get_async_connection()
+                    .await
+                    .context("Failed to connect to Redis")?;
+                let stored: Option<String> = redis::cmd("GET")
+                    .arg(redis_key(circuit_id))
+                    .query_async(&mut conn)
+                    .await?;
+                stored
+                    .map(|stored| serde_json::from_str(&stored))
+                    .transpose()
+                    .with_context(|| format!("Invalid state for circuit {}", circuit_id))?
+            }
+        };
+
+        Ok(circuit.unwrap_or_else(|| CircuitBreakerState::new(circuit_id.to_string())))
+    }
+
+    /// Atomically read, modify and store the state of a circuit.
+    ///
+    /// `update` may run more than once when another execution changes the
+    /// circuit concurrently.
+    pub async fn update<T>(
+        &self,
+        circuit_id: &str,
+        mut update: impl FnMut(&mut CircuitBreakerState) -> T,
+    ) -> Result<T> {
+        match self {
+            CircuitStore::Postgres(pool) => {
+                db::update_circuit_breaker(pool, circuit_id, |circuit| update(circuit)).await
+            }
+            CircuitStore::Redis(client) => update_redis(client, circuit_id, update).await,
+        }
+    }
+}
+
+/// Optimistic read-modify-write of a circuit in Redis using WATCH/MULTI
+async fn update_redis<T>(
+    client: &redis::Client,
+    circuit_id: &str,
+    mut update: impl FnMut(&mut CircuitBreakerState) -> T,
+) -> Result<T> {
+    let key = redis_key(circuit_id);
+    let mut conn = client
+        .get_async_connection()
+        .await
+        .context("Failed to connect to Redis")?;
+
+    loop {
+        redis::cmd("WATCH")
+            .arg(&key)
+            .query_async::<_, ()>(&mut conn)
+            .await?;
+
+        let stored: Option<String> = redis::cmd("GET").arg(&key).query_async(&mut conn).await?;
+        let mut circuit = match stored {
+            Some(stored) => serde_json::from_str(&stored)
+                .with_context(|| format!("Invalid state for circuit {}", circuit_id))?,
+            None => CircuitBreakerState::new(circuit_id.to_string()),
+        };
+
+        let result = update(&mut circuit);
+
+        // EXEC returns nil when the key changed since WATCH
+        let committed: Option<()> = redis::pipe()
+            .atomic()
+            .set(&key, serde_json::to_string(&circuit)?)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        if committed.is_some() {
+            return Ok(result);
+        }
+    }
+}
+
+fn redis_key(circuit_id: &str) -> String {
+    format!("{}{}", REDIS_KEY_PREFIX, circuit_id)
+}
@@ -0,0 +1,382 @@
+use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
+use async_trait::async_trait;
+
+/// Stand-in for a node with external side effects during a dry run.
+///
+/// The recorder logs the parameters the node would have been called with and
+/// outputs a placeholder shaped like the node's output schema, so that the
+/// nodes after it still have something to work with.
+pub struct DryRunRecorder {
+    node: Box<dyn Node>,
+}
+
+impl DryRunRecorder {
+    pub fn new(node: Box<dyn Node>) -> Self {
+        Self { node }
+    }
+}
+
+impl NodeType for DryRunRecorder {
+    fn type_name(&self) -> &str {
+        self.node.type_name()
+    }
+
+    fn category(&self) -> NodeCategory {
+        self.node.category()
+    }
+
+    fn subcategory(&self) -> NodeSubcategory {
+        self.node.subcategory()
+    }
+
+    fn parameter_schema(&self) -> serde_json::Value {
+        self.node.parameter_schema()
+    }
+
+    fn output_schema(&self) -> serde_json::Value {
+        self.node.output_schema()
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        false
+    }
+
+    fn required_credential_type(&self) -> Option<&str> {
+        self.node.required_credential_type()
+    }
+}
+
+#[async_trait]
+impl Node for DryRunRecorder {
+    async fn execute(
+        &self,
+        context: &NodeContext,
+        parameters: &serde_json::Value,
+    ) -> anyhow::Result<NodeOutput> {
+        tracing::info!(
+            execution_id = %context.execution_id,
+            node_id = %context.node_id,
+            node_type = %self.node.type_name(),
+            parameters = %parameters,
+            "Dry run: node not executed"
+        );
+
+        Ok(NodeOutput::success(placeholder(&self.node.output_schema())))
+    }
+
+    fn validate_parameters(&self, parameters: &serde_json::Value) -> anyhow::Result<()> {
+        self.node.validate_parameters(parameters)
+    }
+}
+
+/// Build a value of the shape described by a JSON schema.
+///
+/// Uses the `const`, `default`, first of `examples` or first of `enum` when
+/// the schema has one, and otherwise an empty value of the schema's type.
+/// Objects get every property of the schema, arrays a single item.
+pub fn placeholder(schema: &serde_json::Value) -> serde_json::Value {
+    if let Some(value) = schema.get("const").or_else(|| schema.get("default")) {
+        return value.clone();
+    }
+    for key in ["examples", "enum"] {
+        if let Some(value) = schema.get(key).and_then(|values| values.get(0)) {
+            return value.clone();
+        }
+    }
+
+    // Nullable types are written as ["string", "null"]
+    let schema_type = match schema.get("type") {
+        Some(serde_json::Value::Array(types)) => types
+            .iter()
+            .filter_map(|schema_type| schema_type.as_str())
+            .find(|schema_type| *schema_type != "null"),
+        Some(schema_type) => schema_type.as_str(),
+        None => None,
+    };
+
+    match schema_type {
+        Some("object") => {
+            let properties = schema
+                .get("properties")
+                .and_then(|properties| properties.as_object())
+                .map(|properties| {
+                    properties
+                        .iter()
+                        .map(|(name, property)| (name.clone(), placeholder(property)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            serde_json::Value::Object(properties)
+        }
+        Some("array") => match schema.get("items") {
+            Some(items) => serde_json::json!([placeholder(items)]),
+            None => serde_json::json!([]),
+        },
+        Some("string") => serde_json::json!(""),
+        Some("integer") | Some("number") => serde_json::json!(0),
+        Some("boolean") => serde_json::json!(false),
+        _ => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::NodeRegistry;
+    use crate::nodes::{HttpRequestNode, SlackNode, register_builtin_nodes};
+    use sqlx::PgPool;
+
+    #[test]
+    fn test_placeholder() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "string"},
+                "count": {"type": "integer"},
+                "status": {"type": "string", "enum": ["sent", "queued"]},
+                "retry": {"type": "boolean", "default": true},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "thread": {"type": ["string", "null"]},
+                "meta": {"type": "object"},
+                "raw": {}
+            }
+        });
+
+        assert_eq!(
+            placeholder(&schema),
+            serde_json::json!({
+                "id": "",
+                "count": 0,
+                "status": "sent",
+                "retry": true,
+                "tags": [""],
+                "thread": "",
+                "meta": {},
+                "raw": null
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_recorder() {
+        let node = DryRunRecorder::new(Box::new(HttpRequestNode));
+        assert_eq!(node.type_name(), "http_request");
+        assert!(!node.has_side_effects(&serde_json::json!({})));
+        assert!(HttpRequestNode.has_side_effects(&serde_json::json!({})));
+
+        let context = NodeContext::new("exec-1".to_string(), "fetch".to_string());
+        let output = node
+            .execute(&context, &serde_json::json!({"url": "https://example.com"}))
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        assert_eq!(
+            output.data,
+            serde_json::json!({"status": 200, "headers": {}, "body": null})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_slack() {
+        let node = DryRunRecorder::new(Box::new(SlackNode::new()));
+        let context = NodeContext::new("exec-1".to_string(), "notify".to_string());
+        let output = node
+            .execute(
+                &context,
+                &serde_json::json!({
+                    "credentials_name": "slack",
+                    "operation": "send_message",
+                    "channel": "#alerts",
+                    "text": "Deploy finished"
+                }),
+            )
+            .await
+            .unwrap();
+
+        assert!(output.success);
+        assert_eq!(output.data["success"], true);
+        assert_eq!(output.data["channel"], "");
+        assert_eq!(output.data["text"], "");
+    }
+
+    /// Operations named like this change something outside the workflow
+    const WRITE_PREFIXES: [&str; 29] = [
+        "create_",
+        "update_",
+        "delete_",
+        "put_",
+        "write_",
+        "append_",
+        "send_",
+        "set_",
+        "clear_",
+        "rotate_",
+        "restore_",
+        "tag_",
+        "import_",
+        "move_",
+        "copy_",
+        "revoke_",
+        "renew_",
+        "enable_",
+        "upload_",
+        "insert_",
+        "post_",
+        "save_",
+        "emit_",
+        "log_",
+        "record_",
+        "increment_",
+        "decrement_",
+        "start_",
+        "end_",
+    ];
+
+    /// Nodes whose writes stay inside the worker running the workflow
+    const IN_WORKER: [&str; 3] = ["logging", "workflow_debugger", "mock_server"];
+
+    fn builtin_registry() -> NodeRegistry {
+        let pool = PgPool::connect_lazy("postgres://localhost/test").unwrap();
+        let mut registry = NodeRegistry::new();
+        register_builtin_nodes(&mut registry, &pool);
+        registry
+    }
+
+    /// Parameters for every operation of a node, with and without a file to
+    /// write the result to
+    fn parameter_probes(node: &dyn Node) -> Vec<serde_json::Value> {
+        let schema = node.parameter_schema();
+        let operations = schema["properties"]["operation"]["enum"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let mut probes = vec![serde_json::json!({})];
+        probes.extend(
+            operations
+                .into_iter()
+                .map(|operation| serde_json::json!({"operation": operation})),
+        );
+        if schema["properties"].get("output_path").is_some() {
+            let with_output = probes
+                .iter()
+                .map(|probe| {
+                    let mut probe = probe.clone();
+                    probe["output_path"] = serde_json::json!("out");
+                    probe
+                })
+                .collect::<Vec<_>>();
+            probes.extend(with_output);
+        }
+        probes
+    }
+
+    #[tokio::test]
+    async fn test_write_operations_are_mocked() {
+        let registry = builtin_registry();
+
+        for node_type in registry.get_types() {
+            let node = registry.create(&node_type).unwrap();
+            if node.category() == NodeCategory::Trigger || IN_WORKER.contains(&node_type.as_str()) {
+                continue;
+            }
+
+            for probe in parameter_probes(node.as_ref()) {
+                let operation = probe["operation"].as_str().unwrap_or_default();
+                let writes = probe["output_path"].is_string()
+                    || WRITE_PREFIXES
+                        .iter()
+                        .any(|prefix| operation.starts_with(prefix));
+                if writes {
+                    assert!(
+                        node.has_side_effects(&probe),
+                        "{} is not mocked for {}",
+                        node_type,
+                        probe
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_general_nodes_with_side_effects() {
+        let registry = builtin_registry();
+
+        for node_type in [
+            "stripe",
+            "pagerduty",
+            "jira",
+            "github",
+            "gitlab",
+            "aws_lambda",
+            "aws_messaging",
+            "http_webhook",
+            "metrics",
+        ] {
+            let node = registry.create(node_type).unwrap();
+            assert_eq!(node.subcategory(), NodeSubcategory::General);
+            assert!(
+                node.has_side_effects(&serde_json::json!({})),
+                "{} is not mocked",
+                node_type
+            );
+        }
+
+        // Reads run for real, writes are mocked
+        for (node_type, read, write) in [
+            ("google_calendar", "list_events", "create_event"),
+            ("google_sheets", "read_values", "append_values"),
+            ("datadog", "query_metrics", "send_metric"),
+            ("aws_cloudwatch", "get_metric_statistics", "put_log_events"),
+            ("file_operations", "read_file", "write_file"),
+            ("vault", "read_secret", "write_secret"),
+            (
+                "aws_secrets_manager",
+                "get_secret_value",
+                "put_secret_value",
+            ),
+            ("azure_key_vault", "get_secret", "set_secret"),
+        ] {
+            let node = registry.create(node_type).unwrap();
+            assert!(!node.has_side_effects(&serde_json::json!({"operation": read})));
+            assert!(node.has_side_effects(&serde_json::json!({"operation": write})));
+        }
+
+        let converter = registry.create("json_xml_converter").unwrap();
+        assert!(!converter.has_side_effects(&serde_json::json!({"operation": "json_to_xml"})));
+        assert!(converter.has_side_effects(
+            &serde_json::json!({"operation": "json_to_xml", "output_path": "out.xml"})
+        ));
+        assert!(
+            !registry
+                .create("filter")
+                .unwrap()
+                .has_side_effects(&serde_json::json!({}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mocked_nodes_describe_their_output() {
+        let registry = builtin_registry();
+
+        // A mocked node without an output schema would hand an empty object to
+        // the nodes after it
+        for node_type in registry.get_types() {
+            let node = registry.create(&node_type).unwrap();
+            let mocked = parameter_probes(node.as_ref())
+                .iter()
+                .any(|probe| node.has_side_effects(probe));
+            if mocked {
+                let schema = node.output_schema();
+                assert!(
+                    schema["properties"]
+                        .as_object()
+                        .is_some_and(|properties| !properties.is_empty()),
+                    "{} has side effects but no output schema",
+                    node_type
+                );
+            }
+        }
+    }
+}
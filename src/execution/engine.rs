@@ -1,31 +1,76 @@
 use crate::db;
+use crate::execution::circuit::CircuitStore;
+use crate::execution::dry_run::DryRunRecorder;
+use crate::execution::events::{EventBus, ExecutionEvent, ExecutionObserver};
+use crate::execution::executor::{Overlap, admit};
+use crate::execution::expression::{self, ExpressionScope};
+use crate::execution::routing::{self, MergeJoin};
 use crate::models::{
-    ExecutionMode, ExecutionStatus, NodeContext, NodeExecution, NodeRegistry, WorkflowDefinition,
-    WorkflowExecution,
+    Admission, CircuitBreakerConfig, EdgeDefinition, ErrorClass, ExecutionMode, ExecutionStatus,
+    ExecutionWait, NodeContext, NodeDefinition, NodeExecution, NodeOutput, NodeRegistry,
+    PartialRun, WorkflowDefinition, WorkflowExecution,
 };
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
-use tokio::time::timeout;
+use tokio::sync::{RwLock, Semaphore, broadcast};
+use tokio::task::JoinSet;
+use tokio::time::{Instant, sleep_until, timeout};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 /// Workflow execution engine
+#[derive(Clone)]
 pub struct WorkflowEngine {
     pool: PgPool,
     registry: Arc<NodeRegistry>,
+    circuits: CircuitStore,
+    /// Limit on nodes running at the same time, shared across executions
+    node_slots: Option<Arc<Semaphore>>,
+    /// Observers and subscribers of execution progress
+    events: EventBus,
 }
 
 impl WorkflowEngine {
     pub fn new(pool: PgPool, registry: NodeRegistry) -> Self {
         Self {
+            circuits: CircuitStore::Postgres(pool.clone()),
             pool,
             registry: Arc::new(registry),
+            node_slots: None,
+            events: EventBus::new(),
         }
     }
 
+    /// Call `observer` for every event of every execution the engine runs
+    pub fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.events = self.events.with_observer(observer);
+        self
+    }
+
+    /// Receive the events of every execution the engine runs from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Limit the number of nodes running at the same time across every
+    /// execution of every engine sharing `slots`
+    pub fn with_node_slots(mut self, slots: Arc<Semaphore>) -> Self {
+        self.node_slots = Some(slots);
+        self
+    }
+
+    /// Keep circuit breaker state in the given store instead of Postgres
+    pub fn with_circuit_store(mut self, circuits: CircuitStore) -> Self {
+        self.circuits = circuits;
+        self
+    }
+
     /// Execute a workflow
     pub async fn execute_workflow(
         &self,
@@ -34,17 +79,373 @@ impl WorkflowEngine {
         input_data: Option<serde_json::Value>,
     ) -> Result<WorkflowExecution> {
         // Create workflow execution record
-        let mut execution = WorkflowExecution::new(workflow_id, input_data.clone());
+        let mut execution = WorkflowExecution::new(workflow_id, input_data);
         execution = db::create_workflow_execution(&self.pool, &execution).await?;
 
+        self.run_execution(workflow, execution).await
+    }
+
+    /// Run an execution that was already recorded as running, e.g. one a
+    /// worker took from the queue.
+    ///
+    /// A re-run (see [`WorkflowExecution::rerun_of`]) starts from the state
+    /// of the execution it re-runs.
+    pub async fn run_execution(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: WorkflowExecution,
+    ) -> Result<WorkflowExecution> {
         tracing::info!(
             "Starting workflow execution {} for workflow {}",
             execution.id,
-            workflow_id
+            execution.workflow_id
         );
+        self.events.emit(ExecutionEvent::ExecutionStarted {
+            execution_id: execution.id,
+            workflow_id: execution.workflow_id,
+        });
 
         // Execute the workflow
-        match self.run_workflow(workflow, &execution, input_data).await {
+        let run = match (execution.rerun_of, execution.partial_run()) {
+            (Some(original_id), _) => self.rerun_state(workflow, &execution, original_id).await,
+            (None, Some(partial)) => self.partial_state(workflow, &execution, &partial).await,
+            (None, None) => Ok(GraphRun::start(workflow, execution.input_data.clone())),
+        };
+        let result = match run {
+            Ok(run) => {
+                self.run_cancellable(&execution, self.run_workflow(workflow, &execution, run))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        self.finish_execution(workflow, execution, result).await
+    }
+
+    /// Build the state a re-run starts from.
+    ///
+    /// Nodes of the original execution that keep their results are copied
+    /// to the re-run, so it shows every node and can be re-run in turn.
+    async fn rerun_state(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        original_id: Uuid,
+    ) -> Result<GraphRun> {
+        let node_executions = db::list_node_executions(&self.pool, original_id).await?;
+        let (run, kept) = GraphRun::rerun(
+            workflow,
+            execution.input_data.clone(),
+            &node_executions,
+            execution.rerun_from_node.as_deref(),
+        )?;
+
+        tracing::info!(
+            "Re-running execution {} as {}, keeping the results of {} nodes",
+            original_id,
+            execution.id,
+            kept.len()
+        );
+
+        for node_execution in kept {
+            let mut copy = node_execution.clone();
+            copy.id = Uuid::new_v4();
+            copy.execution_id = execution.id;
+            db::create_node_execution(&self.pool, &copy).await?;
+        }
+
+        Ok(run)
+    }
+
+    /// Build the state a partial execution starts from.
+    ///
+    /// Nodes outside the part to run are handled up front. The ones it
+    /// depends on are seeded with their output in the seed execution, or
+    /// their pinned output, and recorded in the partial execution.
+    async fn partial_state(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        partial: &PartialRun,
+    ) -> Result<GraphRun> {
+        let seed_executions = match execution.seed_execution_id {
+            Some(seed_id) => db::list_node_executions(&self.pool, seed_id).await?,
+            None => Vec::new(),
+        };
+        let (run, seeds) = GraphRun::partial(
+            workflow,
+            execution.input_data.clone(),
+            &seed_executions,
+            partial,
+            execution.ignore_pinned_data,
+        )?;
+
+        tracing::info!(
+            "Running part of workflow {} up to node {} in execution {}, seeded with {} nodes",
+            execution.workflow_id,
+            partial.node_id(),
+            execution.id,
+            seeds.len()
+        );
+
+        for mut seed in seeds {
+            seed.id = Uuid::new_v4();
+            seed.execution_id = execution.id;
+            db::create_node_execution(&self.pool, &seed).await?;
+        }
+
+        Ok(run)
+    }
+
+    /// Continue an execution whose process stopped while running it.
+    ///
+    /// The execution must have been claimed with [`db::claim_stale_execution`].
+    /// Outputs, ports and variables of the nodes that completed are rebuilt
+    /// from `node_executions`, and the run continues with the first node that
+    /// did not complete. Nodes that were interrupted run again, unless they
+    /// are not idempotent; the execution then fails instead.
+    pub async fn recover_execution(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: WorkflowExecution,
+    ) -> Result<WorkflowExecution> {
+        tracing::info!("Recovering interrupted workflow execution {}", execution.id);
+
+        // A wait stored right before the interruption belongs to a node that runs again
+        db::take_execution_waits_for(&self.pool, execution.id).await?;
+
+        let node_executions = db::list_node_executions(&self.pool, execution.id).await?;
+        let (mut run, interrupted) =
+            GraphRun::recover(workflow, execution.input_data.clone(), &node_executions);
+        if let Some(partial) = execution.partial_run() {
+            run.restrict(workflow, &partial);
+        }
+
+        for node_execution in &interrupted {
+            db::update_node_execution_status(
+                &self.pool,
+                node_execution.id,
+                ExecutionStatus::Failed,
+                None,
+                Some("Node execution was interrupted".to_string()),
+            )
+            .await?;
+        }
+
+        let not_idempotent = interrupted.iter().find(|node_execution| {
+            NodeDefinition::find(&workflow.nodes, &node_execution.node_id)
+                .is_some_and(|node_def| !node_def.idempotent)
+        });
+
+        let result = if execution.cancel_requested_at.is_some() {
+            Err(ExecutionCancelled.into())
+        } else if let Some(node_execution) = not_idempotent {
+            Err(anyhow::anyhow!(
+                "Node {} was interrupted and is not idempotent, so it was not run again",
+                node_execution.node_id
+            ))
+        } else {
+            self.run_cancellable(&execution, self.run_workflow(workflow, &execution, run))
+                .await
+        };
+
+        self.finish_execution(workflow, execution, result).await
+    }
+
+    /// Cancel an execution.
+    ///
+    /// Queued and waiting executions are cancelled right away. Running
+    /// executions are flagged, and the process running them aborts their
+    /// in-flight nodes within [`CANCEL_POLL_INTERVAL`].
+    pub async fn cancel_execution(&self, execution_id: Uuid) -> Result<WorkflowExecution> {
+        let execution = db::get_workflow_execution(&self.pool, execution_id).await?;
+
+        let execution = match execution.status {
+            ExecutionStatus::Pending
+                if db::remove_queued_execution(&self.pool, execution_id).await? =>
+            {
+                tracing::info!("Cancelling queued execution {}", execution_id);
+
+                // Executions queued to resume keep their resumed wait until a worker takes them
+                db::take_execution_waits_for(&self.pool, execution_id).await?;
+                db::update_workflow_execution_status(
+                    &self.pool,
+                    execution_id,
+                    ExecutionStatus::Cancelled,
+                    None,
+                    Some(ExecutionCancelled.to_string()),
+                )
+                .await
+            }
+            // A worker took the execution in the meantime
+            ExecutionStatus::Running | ExecutionStatus::Pending => {
+                tracing::info!("Requesting cancellation of execution {}", execution_id);
+                db::request_execution_cancel(&self.pool, execution_id).await
+            }
+            ExecutionStatus::Waiting => {
+                tracing::info!("Cancelling waiting execution {}", execution_id);
+                for wait in db::take_execution_waits_for(&self.pool, execution_id).await? {
+                    db::update_waiting_node_execution(
+                        &self.pool,
+                        execution_id,
+                        &wait.node_id,
+                        ExecutionStatus::Cancelled,
+                        None,
+                        Some(ExecutionCancelled.to_string()),
+                    )
+                    .await?;
+                }
+
+                db::update_workflow_execution_status(
+                    &self.pool,
+                    execution_id,
+                    ExecutionStatus::Cancelled,
+                    None,
+                    Some(ExecutionCancelled.to_string()),
+                )
+                .await
+            }
+            status => anyhow::bail!("Execution {} already finished ({})", execution_id, status),
+        }?;
+
+        // Running executions finish once their process notices the request
+        if execution.status.is_finished() {
+            self.emit_finished(&execution);
+        }
+        Ok(execution)
+    }
+
+    /// Run an execution until it completes or its cancellation is requested.
+    ///
+    /// A background task polls the cancellation flag of the execution and
+    /// cancels the token; the run is then dropped, aborting in-flight nodes.
+    async fn run_cancellable(
+        &self,
+        execution: &WorkflowExecution,
+        run: impl Future<Output = Result<serde_json::Value>>,
+    ) -> Result<serde_json::Value> {
+        let token = CancellationToken::new();
+        tokio::spawn(watch_cancel_requests(
+            self.pool.clone(),
+            execution.id,
+            token.clone(),
+        ));
+
+        // Stops the watcher once the run finishes, or is dropped by a caller
+        let _stop_watcher = token.clone().drop_guard();
+
+        tokio::select! {
+            result = run => result,
+            _ = token.cancelled() => Err(ExecutionCancelled.into()),
+        }
+    }
+
+    /// Record the nodes of the workflow that never ran as cancelled
+    async fn cancel_pending_nodes(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution_id: Uuid,
+    ) -> Result<()> {
+        let recorded: HashSet<String> = db::list_node_executions(&self.pool, execution_id)
+            .await?
+            .into_iter()
+            .map(|node_execution| node_execution.node_id)
+            .collect();
+
+        for node_def in &workflow.nodes {
+            if !recorded.contains(&node_def.id) {
+                db::create_node_execution(
+                    &self.pool,
+                    &NodeExecution::cancelled(execution_id, node_def.id.clone()),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Continue an execution suspended at a `wait_webhook` node.
+    ///
+    /// The execution must have been taken from the queue after its wait was
+    /// resumed. The wait node outputs the payload of the wait and the
+    /// remaining nodes run from the stored state of the execution.
+    pub async fn resume_workflow(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: WorkflowExecution,
+        wait: ExecutionWait,
+    ) -> Result<WorkflowExecution> {
+        tracing::info!(
+            "Resuming workflow execution {} at node {}",
+            execution.id,
+            wait.node_id
+        );
+
+        let payload = wait.payload.unwrap_or_default();
+        let result = match serde_json::from_value::<GraphRun>(wait.state) {
+            Ok(mut run) => {
+                run.node_outputs.insert(wait.node_id.clone(), payload);
+                let graph =
+                    self.run_graph(workflow, &workflow.nodes, &workflow.edges, &execution, run);
+                self.run_cancellable(&execution, graph).await
+            }
+            Err(e) => Err(anyhow::anyhow!(
+                "Invalid state for wait {}: {}",
+                wait.wait_id,
+                e
+            )),
+        };
+
+        self.finish_execution(workflow, execution, result).await
+    }
+
+    /// Fail an execution whose wait expired before it was resumed
+    pub async fn expire_wait(&self, wait: &ExecutionWait) -> Result<WorkflowExecution> {
+        let mut tx = self.pool.begin().await?;
+
+        // Resumed or expired by another process in the meantime
+        if db::take_execution_wait(&mut *tx, &wait.wait_id)
+            .await?
+            .is_none()
+        {
+            return db::get_workflow_execution(&self.pool, wait.execution_id).await;
+        }
+
+        let error = format!("Wait {} expired at {}", wait.wait_id, wait.expires_at);
+        tracing::warn!("Workflow execution {}: {}", wait.execution_id, error);
+
+        db::update_waiting_node_execution(
+            &mut *tx,
+            wait.execution_id,
+            &wait.node_id,
+            ExecutionStatus::Failed,
+            None,
+            Some(error.clone()),
+        )
+        .await?;
+
+        let execution = db::update_workflow_execution_status(
+            &mut *tx,
+            wait.execution_id,
+            ExecutionStatus::Failed,
+            None,
+            Some(error),
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.emit_finished(&execution);
+        Ok(execution)
+    }
+
+    /// Record the outcome of running an execution
+    async fn finish_execution(
+        &self,
+        workflow: &WorkflowDefinition,
+        mut execution: WorkflowExecution,
+        result: Result<serde_json::Value>,
+    ) -> Result<WorkflowExecution> {
+        match result {
             Ok(output) => {
                 // Update execution as successful
                 execution = db::update_workflow_execution_status(
@@ -58,6 +459,32 @@ impl WorkflowEngine {
 
                 tracing::info!("Workflow execution {} completed successfully", execution.id);
             }
+            Err(e) if e.downcast_ref::<Suspended>().is_some() => {
+                // The state was stored when the execution suspended
+                execution = db::update_workflow_execution_status(
+                    &self.pool,
+                    execution.id,
+                    ExecutionStatus::Waiting,
+                    None,
+                    None,
+                )
+                .await?;
+
+                tracing::info!("Workflow execution {} is waiting: {}", execution.id, e);
+            }
+            Err(e) if e.downcast_ref::<ExecutionCancelled>().is_some() => {
+                self.cancel_pending_nodes(workflow, execution.id).await?;
+                execution = db::update_workflow_execution_status(
+                    &self.pool,
+                    execution.id,
+                    ExecutionStatus::Cancelled,
+                    None,
+                    Some(e.to_string()),
+                )
+                .await?;
+
+                tracing::warn!("Workflow execution {} was cancelled", execution.id);
+            }
             Err(e) => {
                 // Update execution as failed
                 execution = db::update_workflow_execution_status(
@@ -70,333 +497,575 @@ impl WorkflowEngine {
                 .await?;
 
                 tracing::error!("Workflow execution {} failed: {}", execution.id, e);
+                self.queue_error_workflow(workflow, &execution).await;
             }
         }
 
+        self.emit_finished(&execution);
         Ok(execution)
     }
 
-    /// Run the workflow nodes
-    async fn run_workflow(
+    /// Queue the error workflow of a failed execution's workflow.
+    ///
+    /// Problems here are only logged, so they never replace the error of the
+    /// execution itself.
+    async fn queue_error_workflow(
         &self,
         workflow: &WorkflowDefinition,
         execution: &WorkflowExecution,
-        input_data: Option<serde_json::Value>,
-    ) -> Result<serde_json::Value> {
-        match workflow.execution_mode {
-            ExecutionMode::Sequential => {
-                self.run_workflow_sequential(workflow, execution, input_data)
-                    .await
-            }
-            ExecutionMode::Parallel => {
-                self.run_workflow_parallel(workflow, execution, input_data)
-                    .await
+    ) {
+        let Some(name) = &workflow.error_workflow else {
+            return;
+        };
+        let input = serde_json::json!({
+            "execution_id": execution.id,
+            "workflow_id": execution.workflow_id,
+            "workflow_name": workflow.name,
+            "error": execution.error,
+        });
+
+        let queued = async {
+            let error_workflow = db::get_workflow_by_name(&self.pool, name).await?;
+            let mut handler = WorkflowExecution::new(error_workflow.id, Some(input));
+            if execution.dry_run {
+                handler = handler.as_dry_run();
             }
+            db::enqueue_execution(&self.pool, &handler).await
+        }
+        .await;
+
+        match queued {
+            Ok(handler) => tracing::info!(
+                "Queued error workflow '{}' as execution {} for failed execution {}",
+                name,
+                handler.id,
+                execution.id
+            ),
+            Err(e) => tracing::warn!(
+                "Failed to queue error workflow '{}' for execution {}: {}",
+                name,
+                execution.id,
+                e
+            ),
         }
     }
 
-    /// Run the workflow nodes sequentially
-    async fn run_workflow_sequential(
+    /// Emit the event for an execution that stopped running
+    fn emit_finished(&self, execution: &WorkflowExecution) {
+        self.events.emit(ExecutionEvent::ExecutionFinished {
+            execution_id: execution.id,
+            workflow_id: execution.workflow_id,
+            status: execution.status.clone(),
+            error: execution.error.clone(),
+        });
+    }
+
+    /// Record a node that is skipped because none of its incoming branches was taken
+    async fn skip_node(
+        &self,
+        execution: &WorkflowExecution,
+        node_id: &str,
+        iteration: Option<&Iteration>,
+    ) -> Result<()> {
+        tracing::info!("Skipping node {}: no incoming branch was taken", node_id);
+        db::create_node_execution(
+            &self.pool,
+            &in_iteration(
+                NodeExecution::skipped(execution.id, node_id.to_string()),
+                iteration,
+            ),
+        )
+        .await?;
+
+        self.events.emit(ExecutionEvent::NodeSkipped {
+            execution_id: execution.id,
+            node_id: node_id.to_string(),
+        });
+        Ok(())
+    }
+
+    /// Run the workflow nodes that `run` has not handled yet
+    async fn run_workflow(
         &self,
         workflow: &WorkflowDefinition,
         execution: &WorkflowExecution,
-        input_data: Option<serde_json::Value>,
+        run: GraphRun,
     ) -> Result<serde_json::Value> {
-        // Build execution order using topological sort
-        let execution_order = self.topological_sort(workflow)?;
-
-        // Track node outputs
-        let mut node_outputs: HashMap<String, serde_json::Value> = HashMap::new();
-        let mut workflow_variables: HashMap<String, serde_json::Value> = HashMap::new();
-
-        // Initialize with input data if provided
-        if let Some(input) = input_data {
-            workflow_variables.insert("input".to_string(), input.clone());
-
-            // If there's a start node, initialize it with input
-            for node_def in &workflow.nodes {
-                if node_def.node_type == "start" {
-                    node_outputs.insert(node_def.id.clone(), input.clone());
-                    break;
-                }
+        match workflow.execution_mode {
+            ExecutionMode::Sequential => {
+                self.run_graph(workflow, &workflow.nodes, &workflow.edges, execution, run)
+                    .await
             }
+            ExecutionMode::Parallel => self.run_workflow_parallel(workflow, execution, run).await,
         }
+    }
 
-        // Execute nodes in order
-        for node_id in &execution_order {
-            let node_def = workflow
-                .nodes
-                .iter()
-                .find(|n| n.id == *node_id)
-                .ok_or_else(|| anyhow::anyhow!("Node not found: {}", node_id))?;
+    /// Run a graph of nodes sequentially and return the output of the last executed node.
+    ///
+    /// The graph is either the workflow itself or the nested body of a node.
+    fn run_graph<'a>(
+        &'a self,
+        workflow: &'a WorkflowDefinition,
+        nodes: &'a [NodeDefinition],
+        edges: &'a [EdgeDefinition],
+        execution: &'a WorkflowExecution,
+        mut run: GraphRun,
+    ) -> BoxFuture<'a, Result<serde_json::Value>> {
+        Box::pin(async move {
+            // Build execution order using topological sort
+            let execution_order = self.topological_sort(nodes, edges)?;
+
+            // Execute nodes in order
+            for node_id in &execution_order {
+                // Nodes handled before the execution was suspended
+                if !run.handled.insert(node_id.clone()) {
+                    continue;
+                }
 
-            // Create node execution record
-            let mut node_execution = NodeExecution::new(execution.id, node_id.clone());
-            node_execution = db::create_node_execution(&self.pool, &node_execution).await?;
+                let node_def = nodes
+                    .iter()
+                    .find(|n| n.id == *node_id)
+                    .ok_or_else(|| anyhow::anyhow!("Node not found: {}", node_id))?;
+
+                // Skip nodes that sit on a branch that was not taken
+                let Some(taken_edges) =
+                    routing::taken_incoming_edges(node_id, edges, &run.node_ports)
+                else {
+                    self.skip_node(execution, node_id, run.iteration.as_ref())
+                        .await?;
+                    continue;
+                };
 
-            tracing::info!("Executing node: {} ({})", node_def.name, node_def.node_type);
+                // A merge node only combines the branches its strategy waits for
+                let taken_edges = match MergeJoin::of(node_def) {
+                    Some(join) => {
+                        routing::merged_edges(taken_edges, join.strategy, &execution_order)
+                    }
+                    None => taken_edges,
+                };
+
+                // Executions can only suspend at the top level of the workflow
+                let waits =
+                    node_def.node_type == WAIT_NODE_TYPE && !short_circuited(execution, node_def);
+                if waits && run.root_input.is_some() {
+                    anyhow::bail!(
+                        "Node {} cannot wait inside the body of another node",
+                        node_id
+                    );
+                }
 
-            // Prepare node context
-            let mut context = NodeContext::new(execution.id.to_string(), node_id.clone());
-            context.variables = workflow_variables.clone();
+                // Prepare node context
+                let mut context = NodeContext::new(execution.id.to_string(), node_id.clone());
+                context.variables = run.workflow_variables.clone();
+                context.depth = execution.depth;
+                context.ignore_pinned_data = execution.ignore_pinned_data;
+                context.dry_run = execution.dry_run;
 
-            // Collect inputs from predecessor nodes
-            let mut input_data_json = serde_json::Map::new();
-            for edge in &workflow.edges {
-                if edge.to == *node_id
-                    && let Some(input) = node_outputs.get(&edge.from)
+                // Root nodes of a nested body receive the body input
+                if taken_edges.is_empty()
+                    && let Some((key, value)) = &run.root_input
                 {
-                    let input_key = if edge.to_input.is_empty() {
-                        edge.from.clone()
-                    } else {
-                        edge.to_input.clone()
-                    };
-                    context.add_input(input_key.clone(), input.clone());
-                    input_data_json.insert(input_key, input.clone());
+                    context.add_input(key.clone(), value.clone());
                 }
-            }
-
-            // Store input data
-            let input_data_value = if input_data_json.is_empty() {
-                None
-            } else {
-                Some(serde_json::Value::Object(input_data_json))
-            };
 
-            if let Some(ref input_data) = input_data_value {
-                node_execution.input_data = Some(input_data.clone());
-            }
+                // Collect inputs from predecessor nodes along taken edges
+                let mut input_data_json = serde_json::Map::new();
+                for edge in taken_edges {
+                    if let Some(input) = run.node_outputs.get(&edge.from) {
+                        let input_key = routing::input_key(edge);
+                        context.add_input(input_key.clone(), input.clone());
+                        input_data_json.insert(input_key, input.clone());
+                    }
+                }
 
-            // Determine timeout duration
-            let timeout_duration = node_def
-                .timeout_seconds
-                .or(workflow.timeout_seconds)
-                .map(Duration::from_secs);
+                // Store input data
+                let input_data_value = if input_data_json.is_empty() {
+                    None
+                } else {
+                    Some(serde_json::Value::Object(input_data_json))
+                };
 
-            // Execute the node with timeout
-            let node = self.registry.create(&node_def.node_type)?;
-            let execute_future = node.execute(&context, &node_def.parameters);
+                // Determine timeout duration
+                let timeout_duration = node_def
+                    .timeout_seconds
+                    .or(workflow.timeout_seconds)
+                    .map(Duration::from_secs);
 
-            let execution_result = if let Some(duration) = timeout_duration {
-                match timeout(duration, execute_future).await {
-                    Ok(result) => result,
-                    Err(_) => Err(anyhow::anyhow!(
-                        "Node execution timed out after {} seconds",
-                        duration.as_secs()
-                    )),
+                // Resolve parameter expressions and execute the node
+                let scope = expression_scope(
+                    &context,
+                    &run.node_outputs,
+                    &run.workflow_variables,
+                    execution,
+                );
+                let parameters = expression::resolve_parameters(&node_def.parameters, &scope);
+                let result = self
+                    .execute_attempts(
+                        workflow,
+                        execution,
+                        node_def,
+                        &context,
+                        parameters,
+                        &run.node_outputs,
+                        input_data_value.clone(),
+                        run.iteration.as_ref(),
+                        timeout_duration,
+                    )
+                    .await;
+                let output = route_failure(node_id, edges, input_data_value, result)?;
+
+                // Suspend the execution until the wait node is resumed
+                if waits && output.success {
+                    run.node_ports.insert(node_id.clone(), output.port);
+                    run.last_executed = Some(node_id.clone());
+                    run.workflow_variables.extend(output.variables);
+                    return Err(self.suspend(execution, node_id, &output.data, &run).await?);
                 }
-            } else {
-                execute_future.await
-            };
 
-            match execution_result {
-                Ok(output) => {
-                    if output.success {
-                        tracing::info!("Node {} completed successfully", node_id);
-
-                        // Store output for downstream nodes
-                        node_outputs.insert(node_id.clone(), output.data.clone());
-
-                        // Update node execution as successful
-                        db::update_node_execution_status(
-                            &self.pool,
-                            node_execution.id,
-                            ExecutionStatus::Success,
-                            Some(output.data),
-                            None,
-                        )
-                        .await?;
-                    } else {
-                        let error_msg = output.error.unwrap_or_else(|| "Unknown error".to_string());
-                        tracing::error!("Node {} failed: {}", node_id, error_msg);
-
-                        // Update node execution as failed
-                        db::update_node_execution_status(
-                            &self.pool,
-                            node_execution.id,
-                            ExecutionStatus::Failed,
-                            None,
-                            Some(error_msg.clone()),
-                        )
-                        .await?;
+                // Store output and taken port for downstream nodes
+                run.node_outputs.insert(node_id.clone(), output.data);
+                run.node_ports.insert(node_id.clone(), output.port);
+                run.last_executed = Some(node_id.clone());
 
-                        anyhow::bail!("Node {} failed: {}", node_id, error_msg);
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("Node {} error: {}", node_id, e);
+                // Apply variables set by the node for later nodes
+                run.workflow_variables.extend(output.variables);
+            }
 
-                    // Update node execution as failed
-                    db::update_node_execution_status(
-                        &self.pool,
-                        node_execution.id,
-                        ExecutionStatus::Failed,
-                        None,
-                        Some(e.to_string()),
-                    )
-                    .await?;
+            // Return the output of the last executed node
+            let last_node_id = run.last_executed.context("No nodes executed")?;
+            let final_output = run
+                .node_outputs
+                .get(&last_node_id)
+                .cloned()
+                .unwrap_or(serde_json::json!({}));
 
-                    return Err(e);
-                }
-            }
-        }
+            Ok(final_output)
+        })
+    }
 
-        // Return the output of the last node
-        let last_node_id = execution_order.last().context("No nodes executed")?;
-        let final_output = node_outputs
-            .get(last_node_id)
-            .cloned()
-            .unwrap_or(serde_json::json!({}));
+    /// Store the state of a run suspended at a wait node and return the
+    /// [`Suspended`] error that stops it
+    async fn suspend(
+        &self,
+        execution: &WorkflowExecution,
+        node_id: &str,
+        output: &serde_json::Value,
+        run: &GraphRun,
+    ) -> Result<anyhow::Error> {
+        let wait_id = output["wait_id"]
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let timeout_seconds = output["timeout_seconds"].as_i64().unwrap_or(3600);
+        let now = chrono::Utc::now();
+
+        let wait = ExecutionWait {
+            wait_id: wait_id.clone(),
+            execution_id: execution.id,
+            node_id: node_id.to_string(),
+            state: serde_json::to_value(run)?,
+            created_at: now,
+            expires_at: now + chrono::Duration::seconds(timeout_seconds),
+            payload: None,
+            resumed_at: None,
+        };
+        db::create_execution_wait(&self.pool, &wait).await?;
+
+        tracing::info!(
+            "Workflow execution {} suspended at node {} (wait {})",
+            execution.id,
+            node_id,
+            wait_id
+        );
 
-        Ok(final_output)
+        Ok(Suspended { wait_id }.into())
     }
 
-    /// Run the workflow nodes in parallel where possible
+    /// Run the workflow nodes in parallel, starting each node as soon as all
+    /// of its predecessors have finished.
+    ///
+    /// A `merge` node starts as soon as enough of its branches delivered for
+    /// its strategy, and fails if the rest do not arrive within its `timeout`.
+    /// At most `max_concurrency` nodes of the workflow run at the same time.
     async fn run_workflow_parallel(
         &self,
         workflow: &WorkflowDefinition,
         execution: &WorkflowExecution,
-        input_data: Option<serde_json::Value>,
+        run: GraphRun,
     ) -> Result<serde_json::Value> {
         tracing::info!("Running workflow in parallel mode");
 
-        // Group nodes by dependency level
-        let levels = self.compute_node_levels(workflow)?;
+        // Also rejects cycles before anything runs
+        let execution_order = self.topological_sort(&workflow.nodes, &workflow.edges)?;
+        let shared_workflow = Arc::new(workflow.clone());
+        let slots = Arc::new(Semaphore::new(
+            workflow
+                .max_concurrency
+                .map_or(Semaphore::MAX_PERMITS, |limit| limit.max(1)),
+        ));
 
         // Track node outputs (shared across parallel tasks)
-        let node_outputs = Arc::new(RwLock::new(HashMap::<String, serde_json::Value>::new()));
-        let workflow_variables = Arc::new(RwLock::new(HashMap::<String, serde_json::Value>::new()));
-
-        // Initialize with input data if provided
-        if let Some(input) = input_data {
-            workflow_variables
-                .write()
-                .await
-                .insert("input".to_string(), input.clone());
+        let node_outputs = Arc::new(RwLock::new(run.node_outputs));
+        let node_ports = Arc::new(RwLock::new(run.node_ports));
+        let mut workflow_variables = run.workflow_variables;
 
-            // If there's a start node, initialize it with input
-            for node_def in &workflow.nodes {
-                if node_def.node_type == "start" {
-                    node_outputs
-                        .write()
-                        .await
-                        .insert(node_def.id.clone(), input.clone());
-                    break;
-                }
+        // Number of predecessors each node is still waiting for
+        let mut pending: HashMap<&str, usize> = workflow
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), 0))
+            .collect();
+        for edge in &workflow.edges {
+            if let Some(count) = pending.get_mut(edge.to.as_str()) {
+                *count += 1;
             }
         }
+        let mut ready: VecDeque<&NodeDefinition> = workflow
+            .nodes
+            .iter()
+            .filter(|node| pending[node.id.as_str()] == 0)
+            .collect();
 
-        // Execute nodes level by level
-        let mut last_node_id = String::new();
-        for (level_num, level_nodes) in levels.iter().enumerate() {
-            tracing::info!(
-                "Executing level {} with {} nodes",
-                level_num,
-                level_nodes.len()
-            );
+        // Dropping the set aborts tasks that are still running
+        let mut tasks = JoinSet::new();
+        let mut task_nodes = HashMap::new();
+        let mut executed = HashSet::new();
+        // Merge nodes are queued again when their remaining branches finish
+        let mut started = HashSet::new();
+        let mut finish_order = Vec::new();
+        let mut merge_deadlines = HashMap::new();
+
+        loop {
+            // Start every node whose predecessors have all finished
+            while let Some(node_def) = ready.pop_front() {
+                let node_id = &node_def.id;
+                if !started.insert(node_id.clone()) {
+                    continue;
+                }
+                merge_deadlines.remove(node_id.as_str());
 
-            // Execute all nodes in this level in parallel
-            let mut tasks = Vec::new();
+                // Nodes that completed before the execution was interrupted
+                if run.handled.contains(node_id) {
+                    if node_ports.read().await.contains_key(node_id) {
+                        executed.insert(node_id.clone());
+                        finish_order.push(node_id.clone());
+                    }
+                    release_successors(
+                        node_id,
+                        workflow,
+                        &*node_ports.read().await,
+                        &started,
+                        &mut pending,
+                        &mut merge_deadlines,
+                        &mut ready,
+                    );
+                    continue;
+                }
 
-            for node_id in level_nodes {
-                let node_def = workflow
-                    .nodes
-                    .iter()
-                    .find(|n| &n.id == node_id)
-                    .ok_or_else(|| anyhow::anyhow!("Node not found: {}", node_id))?
-                    .clone();
-
-                let pool = self.pool.clone();
-                let registry = Arc::clone(&self.registry);
-                let execution_id = execution.id;
-                let workflow_edges = workflow.edges.clone();
+                // Skip nodes that sit on a branch that was not taken
+                let taken_edges: Option<Vec<EdgeDefinition>> = {
+                    let ports = node_ports.read().await;
+                    routing::taken_incoming_edges(node_id, &workflow.edges, &ports).map(|edges| {
+                        let edges = match MergeJoin::of(node_def) {
+                            Some(join) => {
+                                routing::merged_edges(edges, join.strategy, &finish_order)
+                            }
+                            None => edges,
+                        };
+                        edges.into_iter().cloned().collect()
+                    })
+                };
+                let Some(taken_edges) = taken_edges else {
+                    self.skip_node(execution, node_id, None).await?;
+                    release_successors(
+                        node_id,
+                        workflow,
+                        &*node_ports.read().await,
+                        &started,
+                        &mut pending,
+                        &mut merge_deadlines,
+                        &mut ready,
+                    );
+                    continue;
+                };
+
+                let engine = self.clone();
+                let workflow_clone = Arc::clone(&shared_workflow);
+                let execution_clone = execution.clone();
+                let node_def = node_def.clone();
                 let node_outputs_clone = Arc::clone(&node_outputs);
-                let workflow_variables_clone = Arc::clone(&workflow_variables);
+                let node_ports_clone = Arc::clone(&node_ports);
+                let slots = Arc::clone(&slots);
+                // A node sees the variables set by the nodes that finished before it started
+                let variables_snapshot = workflow_variables.clone();
                 let timeout_duration = node_def
                     .timeout_seconds
                     .or(workflow.timeout_seconds)
                     .map(Duration::from_secs);
 
                 // Spawn a task for each node
-                let task = tokio::spawn(async move {
-                    Self::execute_node(
-                        pool,
-                        registry,
-                        execution_id,
-                        node_def,
-                        workflow_edges,
-                        node_outputs_clone,
-                        workflow_variables_clone,
-                        timeout_duration,
-                    )
-                    .await
+                let task = tasks.spawn(async move {
+                    let _slot = slots.acquire_owned().await?;
+                    engine
+                        .execute_node(
+                            workflow_clone,
+                            execution_clone,
+                            node_def,
+                            taken_edges,
+                            node_outputs_clone,
+                            node_ports_clone,
+                            variables_snapshot,
+                            timeout_duration,
+                        )
+                        .await
                 });
 
-                tasks.push((node_id.clone(), task));
+                task_nodes.insert(task.id(), node_id.clone());
             }
 
-            // Wait for all tasks in this level to complete
-            for (node_id, task) in tasks {
-                match task.await {
-                    Ok(result) => {
-                        result?; // Propagate any execution errors
-                        last_node_id = node_id;
-                    }
-                    Err(e) => {
-                        anyhow::bail!("Task for node {} panicked: {}", node_id, e);
-                    }
+            // Wait for the next node to finish or the earliest merge deadline
+            let deadline = merge_deadlines.values().min().copied();
+            let joined = tokio::select! {
+                joined = tasks.join_next_with_id() => Some(joined),
+                _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => None,
+            };
+            let Some(joined) = joined else {
+                let now = Instant::now();
+                let expired: Vec<&str> = merge_deadlines
+                    .iter()
+                    .filter(|(_, deadline)| **deadline <= now)
+                    .map(|(node_id, _)| *node_id)
+                    .collect();
+
+                for node_id in expired {
+                    merge_deadlines.remove(node_id);
+                    started.insert(node_id.to_string());
+
+                    let message = format!(
+                        "Merge node {} timed out waiting for its incoming branches",
+                        node_id
+                    );
+                    tracing::warn!("{}", message);
+                    db::create_node_execution(
+                        &self.pool,
+                        &NodeExecution::failed(execution.id, node_id.to_string(), message.clone()),
+                    )
+                    .await?;
+                    self.events.emit(ExecutionEvent::NodeFailed {
+                        execution_id: execution.id,
+                        node_id: node_id.to_string(),
+                        attempt: 1,
+                        error: message.clone(),
+                    });
+
+                    let failure = NodeFailure {
+                        node_id: node_id.to_string(),
+                        message,
+                    };
+                    let output =
+                        route_failure(node_id, &workflow.edges, None, Err(failure.into()))?;
+                    node_outputs
+                        .write()
+                        .await
+                        .insert(node_id.to_string(), output.data);
+                    node_ports
+                        .write()
+                        .await
+                        .insert(node_id.to_string(), output.port);
+
+                    release_successors(
+                        node_id,
+                        workflow,
+                        &*node_ports.read().await,
+                        &started,
+                        &mut pending,
+                        &mut merge_deadlines,
+                        &mut ready,
+                    );
+                    executed.insert(node_id.to_string());
+                    finish_order.push(node_id.to_string());
                 }
-            }
+                continue;
+            };
+            let Some(joined) = joined else {
+                break;
+            };
+            let node_id = match joined {
+                Ok((task_id, result)) => {
+                    let node_id = task_nodes.remove(&task_id).unwrap_or_default();
+                    // Propagate any execution errors
+                    workflow_variables.extend(result?);
+                    node_id
+                }
+                Err(e) => {
+                    anyhow::bail!("Task for node {} panicked: {}", task_nodes[&e.id()], e);
+                }
+            };
+
+            release_successors(
+                &node_id,
+                workflow,
+                &*node_ports.read().await,
+                &started,
+                &mut pending,
+                &mut merge_deadlines,
+                &mut ready,
+            );
+            executed.insert(node_id.clone());
+            finish_order.push(node_id);
         }
 
-        // Return the output of the last node
-        let final_output = node_outputs
-            .read()
-            .await
-            .get(&last_node_id)
-            .cloned()
-            .unwrap_or(serde_json::json!({}));
+        // Return the output of the last executed node in execution order
+        let final_output = match execution_order
+            .iter()
+            .rev()
+            .find(|id| executed.contains(*id))
+        {
+            Some(node_id) => node_outputs.read().await.get(node_id).cloned(),
+            None => None,
+        };
 
-        Ok(final_output)
+        Ok(final_output.unwrap_or(serde_json::json!({})))
     }
 
     /// Execute a single node (helper for parallel execution)
+    ///
+    /// Returns the workflow variables set by the node.
     #[allow(clippy::too_many_arguments)]
     async fn execute_node(
-        pool: PgPool,
-        registry: Arc<NodeRegistry>,
-        execution_id: Uuid,
-        node_def: crate::models::NodeDefinition,
-        workflow_edges: Vec<crate::models::EdgeDefinition>,
+        self,
+        workflow: Arc<WorkflowDefinition>,
+        execution: WorkflowExecution,
+        node_def: NodeDefinition,
+        taken_edges: Vec<EdgeDefinition>,
         node_outputs: Arc<RwLock<HashMap<String, serde_json::Value>>>,
-        workflow_variables: Arc<RwLock<HashMap<String, serde_json::Value>>>,
+        node_ports: Arc<RwLock<HashMap<String, Option<String>>>>,
+        workflow_variables: HashMap<String, serde_json::Value>,
         timeout_duration: Option<Duration>,
-    ) -> Result<()> {
+    ) -> Result<HashMap<String, serde_json::Value>> {
         let node_id = node_def.id.clone();
 
-        // Create node execution record
-        let mut node_execution = NodeExecution::new(execution_id, node_id.clone());
-        node_execution = db::create_node_execution(&pool, &node_execution).await?;
-
-        tracing::info!("Executing node: {} ({})", node_def.name, node_def.node_type);
+        if node_def.node_type == WAIT_NODE_TYPE && !short_circuited(&execution, &node_def) {
+            anyhow::bail!(
+                "Node {} cannot wait in parallel execution mode; use sequential mode",
+                node_id
+            );
+        }
 
         // Prepare node context
-        let mut context = NodeContext::new(execution_id.to_string(), node_id.clone());
-        context.variables = workflow_variables.read().await.clone();
+        let mut context = NodeContext::new(execution.id.to_string(), node_id.clone());
+        context.variables = workflow_variables;
+        context.depth = execution.depth;
+        context.ignore_pinned_data = execution.ignore_pinned_data;
+        context.dry_run = execution.dry_run;
 
-        // Collect inputs from predecessor nodes
+        // Collect inputs from predecessor nodes along taken edges
         let mut input_data_json = serde_json::Map::new();
         {
             let outputs = node_outputs.read().await;
-            for edge in &workflow_edges {
-                if edge.to == node_id
-                    && let Some(input) = outputs.get(&edge.from)
-                {
-                    let input_key = if edge.to_input.is_empty() {
-                        edge.from.clone()
-                    } else {
-                        edge.to_input.clone()
-                    };
+            for edge in &taken_edges {
+                if let Some(input) = outputs.get(&edge.from) {
+                    let input_key = routing::input_key(edge);
                     context.add_input(input_key.clone(), input.clone());
                     input_data_json.insert(input_key, input.clone());
                 }
@@ -410,168 +1079,981 @@ impl WorkflowEngine {
             Some(serde_json::Value::Object(input_data_json))
         };
 
-        if let Some(ref input_data) = input_data_value {
-            node_execution.input_data = Some(input_data.clone());
+        // Resolve parameter expressions against the outputs available so far.
+        // Nodes with a nested body also get a snapshot of the outputs for the body.
+        let (parameters, outputs_snapshot) = {
+            let outputs = node_outputs.read().await;
+            let scope = expression_scope(&context, &outputs, &context.variables, &execution);
+            let snapshot = if node_def.nodes.is_empty() {
+                HashMap::new()
+            } else {
+                outputs.clone()
+            };
+            (
+                expression::resolve_parameters(&node_def.parameters, &scope),
+                snapshot,
+            )
+        };
+
+        // Execute the node
+        let result = self
+            .execute_attempts(
+                &workflow,
+                &execution,
+                &node_def,
+                &context,
+                parameters,
+                &outputs_snapshot,
+                input_data_value.clone(),
+                None,
+                timeout_duration,
+            )
+            .await;
+        let output = route_failure(&node_id, &workflow.edges, input_data_value, result)?;
+
+        // Store output and taken port for downstream nodes
+        node_outputs
+            .write()
+            .await
+            .insert(node_id.clone(), output.data);
+        node_ports.write().await.insert(node_id, output.port);
+
+        Ok(output.variables)
+    }
+
+    /// Execute a node, retrying failed attempts according to its retry policy.
+    ///
+    /// Every attempt is recorded as its own row in `node_executions`. Returns
+    /// the output of the first successful attempt, or a [`NodeFailure`] with
+    /// the error of the last one. A disabled node does not run and passes its
+    /// input through; a node with pinned output returns that output instead.
+    #[allow(clippy::too_many_arguments)]
+    async fn execute_attempts(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        context: &NodeContext,
+        parameters: Result<serde_json::Value>,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        input_data: Option<serde_json::Value>,
+        iteration: Option<&Iteration>,
+        timeout_duration: Option<Duration>,
+    ) -> Result<NodeOutput> {
+        if node_def.disabled {
+            let output = main_input(context);
+            let mut node_execution = in_iteration(
+                NodeExecution::new(execution.id, node_def.id.clone()),
+                iteration,
+            );
+            node_execution.status = ExecutionStatus::Success;
+            node_execution.finished_at = Some(node_execution.started_at);
+            node_execution.input_data = input_data;
+            node_execution.output_data = Some(output.clone());
+            db::create_node_execution(&self.pool, &node_execution).await?;
+
+            tracing::info!(
+                "Node {} is disabled; passing its input through",
+                node_def.id
+            );
+            self.events.emit(ExecutionEvent::NodeFinished {
+                execution_id: execution.id,
+                node_id: node_def.id.clone(),
+                attempt: node_execution.attempt,
+                output: output.clone(),
+                port: None,
+            });
+            return Ok(NodeOutput::success(output));
         }
 
-        // Execute the node with timeout
-        let node = registry.create(&node_def.node_type)?;
-        let execute_future = node.execute(&context, &node_def.parameters);
+        if let Some(output) = pinned_output(execution, node_def) {
+            let mut node_execution = in_iteration(
+                NodeExecution::pinned(execution.id, node_def.id.clone(), output.clone()),
+                iteration,
+            );
+            node_execution.input_data = input_data;
+            db::create_node_execution(&self.pool, &node_execution).await?;
+
+            tracing::info!("Node {} returned its pinned output", node_def.id);
+            self.events.emit(ExecutionEvent::NodeFinished {
+                execution_id: execution.id,
+                node_id: node_def.id.clone(),
+                attempt: node_execution.attempt,
+                output: output.clone(),
+                port: None,
+            });
+            return Ok(NodeOutput::success(output.clone()));
+        }
 
-        let execution_result = if let Some(duration) = timeout_duration {
-            match timeout(duration, execute_future).await {
-                Ok(result) => result,
-                Err(_) => Err(anyhow::anyhow!(
-                    "Node execution timed out after {} seconds",
-                    duration.as_secs()
-                )),
-            }
-        } else {
-            execute_future.await
-        };
+        let max_attempts = node_def
+            .retry
+            .as_ref()
+            .map_or(1, |policy| policy.max_attempts.max(1));
+        let mut attempt = 1;
 
-        match execution_result {
-            Ok(output) => {
-                if output.success {
-                    tracing::info!("Node {} completed successfully", node_id);
+        loop {
+            // Create node execution record
+            let mut node_execution = in_iteration(
+                NodeExecution::new(execution.id, node_def.id.clone()),
+                iteration,
+            )
+            .with_attempt(attempt as i32);
+            node_execution.input_data = input_data.clone();
+            node_execution = db::create_node_execution(&self.pool, &node_execution).await?;
+            let in_flight = InFlightNode::new(self.pool.clone(), node_execution.id);
+            self.events.emit(ExecutionEvent::NodeStarted {
+                execution_id: execution.id,
+                node_id: node_def.id.clone(),
+                attempt: node_execution.attempt,
+                iteration: node_execution.iteration,
+                branch: node_execution.branch.clone(),
+            });
 
-                    // Store output for downstream nodes
-                    node_outputs
-                        .write()
-                        .await
-                        .insert(node_id.clone(), output.data.clone());
+            tracing::info!(
+                "Executing node: {} ({}), attempt {}/{}",
+                node_def.name,
+                node_def.node_type,
+                attempt,
+                max_attempts
+            );
+
+            let result = match &parameters {
+                Ok(parameters) => {
+                    self.run_node(
+                        workflow,
+                        execution,
+                        node_def,
+                        context,
+                        parameters,
+                        node_outputs,
+                        iteration,
+                        timeout_duration,
+                    )
+                    .await
+                }
+                Err(e) => Err(anyhow::anyhow!("Failed to resolve parameters: {:#}", e)),
+            };
+            in_flight.finish();
+
+            // Classify the failure; parameter errors are never retried
+            let (error_msg, error, class) = match result {
+                Ok(output) if output.success => {
+                    tracing::info!("Node {} completed successfully", node_def.id);
+
+                    // Wait nodes stay waiting until the execution is resumed
+                    let status = if node_def.node_type == WAIT_NODE_TYPE {
+                        ExecutionStatus::Waiting
+                    } else {
+                        ExecutionStatus::Success
+                    };
 
                     // Update node execution as successful
-                    db::update_node_execution_status(
-                        &pool,
+                    db::complete_node_execution(
+                        &self.pool,
                         node_execution.id,
-                        ExecutionStatus::Success,
-                        Some(output.data),
-                        None,
+                        status,
+                        output.data.clone(),
+                        output.port.as_deref(),
+                        &output.variables,
                     )
                     .await?;
-                } else {
+
+                    self.events.emit(ExecutionEvent::NodeFinished {
+                        execution_id: execution.id,
+                        node_id: node_def.id.clone(),
+                        attempt: node_execution.attempt,
+                        output: output.data.clone(),
+                        port: output.port.clone(),
+                    });
+                    if !output.variables.is_empty() {
+                        self.events.emit(ExecutionEvent::VariablesChanged {
+                            execution_id: execution.id,
+                            node_id: node_def.id.clone(),
+                            variables: output.variables.clone(),
+                        });
+                    }
+
+                    return Ok(output);
+                }
+                Ok(output) => {
                     let error_msg = output.error.unwrap_or_else(|| "Unknown error".to_string());
-                    tracing::error!("Node {} failed: {}", node_id, error_msg);
+                    let error = anyhow::anyhow!("Node {} failed: {}", node_def.id, error_msg);
+                    (error_msg, error, Some(ErrorClass::Failure))
+                }
+                Err(e) => {
+                    let class = if e.downcast_ref::<NodeTimeout>().is_some()
+                        || e.downcast_ref::<DeadlineExceeded>().is_some()
+                    {
+                        Some(ErrorClass::Timeout)
+                    } else if parameters.is_ok() {
+                        Some(ErrorClass::Error)
+                    } else {
+                        None
+                    };
+                    (e.to_string(), e, class)
+                }
+            };
 
-                    // Update node execution as failed
-                    db::update_node_execution_status(
-                        &pool,
-                        node_execution.id,
-                        ExecutionStatus::Failed,
-                        None,
-                        Some(error_msg.clone()),
-                    )
-                    .await?;
+            tracing::error!("Node {} failed: {}", node_def.id, error_msg);
+
+            // Update node execution as failed
+            db::update_node_execution_status(
+                &self.pool,
+                node_execution.id,
+                ExecutionStatus::Failed,
+                None,
+                Some(error_msg.clone()),
+            )
+            .await?;
+            self.events.emit(ExecutionEvent::NodeFailed {
+                execution_id: execution.id,
+                node_id: node_def.id.clone(),
+                attempt: node_execution.attempt,
+                error: error_msg,
+            });
+
+            let retry = node_def.retry.as_ref().filter(|policy| {
+                attempt < max_attempts && class.is_some_and(|class| policy.retries(class))
+            });
+            let Some(policy) = retry else {
+                return Err(NodeFailure {
+                    node_id: node_def.id.clone(),
+                    message: format!("{:#}", error),
+                }
+                .into());
+            };
+
+            let delay = policy.delay_for_attempt(attempt);
+            tracing::warn!(
+                "Retrying node {} in {} ms (attempt {}/{})",
+                node_def.id,
+                delay.as_millis(),
+                attempt + 1,
+                max_attempts
+            );
+            self.events.emit(ExecutionEvent::NodeRetried {
+                execution_id: execution.id,
+                node_id: node_def.id.clone(),
+                attempt: node_execution.attempt + 1,
+                delay_ms: delay.as_millis() as u64,
+            });
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
 
-                    anyhow::bail!("Node {} failed: {}", node_id, error_msg);
+    /// Execute a node and, if it has a nested body, run the body for its output
+    #[allow(clippy::too_many_arguments)]
+    async fn run_node(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        context: &NodeContext,
+        parameters: &serde_json::Value,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        iteration: Option<&Iteration>,
+        timeout_duration: Option<Duration>,
+    ) -> Result<NodeOutput> {
+        let output = {
+            // Only the node itself holds a slot; nested bodies take their own
+            let _slot = match &self.node_slots {
+                Some(slots) => Some(Arc::clone(slots).acquire_owned().await?),
+                None => None,
+            };
+            Self::invoke_node(
+                &self.registry,
+                node_def,
+                context,
+                parameters,
+                timeout_duration,
+            )
+            .await?
+        };
+
+        if !output.success {
+            return Ok(output);
+        }
+
+        match node_def.node_type.as_str() {
+            EXECUTE_WORKFLOW_NODE_TYPE if output.data["wait"] == true => {
+                let run = self.run_sub_workflow(execution, node_def, output);
+                match timeout_duration {
+                    Some(duration) => timeout(duration, run).await.unwrap_or_else(|_| {
+                        Err(NodeTimeout {
+                            seconds: duration.as_secs(),
+                        }
+                        .into())
+                    }),
+                    None => run.await,
                 }
             }
-            Err(e) => {
-                tracing::error!("Node {} error: {}", node_id, e);
-
-                // Update node execution as failed
-                db::update_node_execution_status(
-                    &pool,
-                    node_execution.id,
-                    ExecutionStatus::Failed,
-                    None,
-                    Some(e.to_string()),
+            "circuit_breaker" => {
+                self.run_circuit_breaker(
+                    workflow,
+                    execution,
+                    node_def,
+                    output,
+                    context,
+                    node_outputs,
+                    iteration,
                 )
-                .await?;
+                .await
+            }
+            _ if node_def.nodes.is_empty() => Ok(output),
+            "timeout" => {
+                self.run_timeout_body(
+                    workflow,
+                    execution,
+                    node_def,
+                    output,
+                    context,
+                    node_outputs,
+                    iteration,
+                )
+                .await
+            }
+            "split" => {
+                self.run_split_body(
+                    workflow,
+                    execution,
+                    node_def,
+                    output,
+                    node_outputs,
+                    &context.variables,
+                )
+                .await
+            }
+            "try_catch" => {
+                self.run_try_body(
+                    workflow,
+                    execution,
+                    node_def,
+                    output,
+                    context,
+                    node_outputs,
+                    iteration,
+                )
+                .await
+            }
+            _ => {
+                self.run_loop_body(
+                    workflow,
+                    execution,
+                    node_def,
+                    output,
+                    node_outputs,
+                    &context.variables,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Run the sub-workflow requested by an `execute_workflow` node that waits
+    /// for it, as a child of `execution` and in this engine.
+    ///
+    /// The child is admitted like any other start of its workflow: over the
+    /// workflow's execution limit, it is queued and the node waits for a
+    /// worker to run it, or it is skipped. The child is cancelled if the node
+    /// is dropped before it finishes, e.g. when the parent is cancelled or a
+    /// deadline expires. A child that suspends at a wait node is cancelled
+    /// and the node fails, since the parent cannot wait for it meanwhile.
+    fn run_sub_workflow<'a>(
+        &'a self,
+        execution: &'a WorkflowExecution,
+        node_def: &'a NodeDefinition,
+        request: NodeOutput,
+    ) -> BoxFuture<'a, Result<NodeOutput>> {
+        Box::pin(async move {
+            let workflow_id: Uuid = serde_json::from_value(request.data["workflow_id"].clone())
+                .context("Sub-workflow request without a workflow_id")?;
+            let workflow = db::get_workflow(&self.pool, workflow_id).await?;
+            let definition = workflow.to_definition()?;
+            let input_data = Some(request.data["input"].clone()).filter(|input| !input.is_null());
+
+            let child_of = |child: WorkflowExecution| {
+                let mut child =
+                    child.with_parent(execution.id, node_def.id.clone(), execution.depth);
+                child.ignore_pinned_data = execution.ignore_pinned_data;
+                child.dry_run = execution.dry_run;
+                child
+            };
+
+            tracing::info!(
+                "Executing sub-workflow '{}' (ID: {})",
+                workflow.name,
+                workflow.id
+            );
 
-                return Err(e);
+            let mut tx = self.pool.begin().await?;
+            let child = match admit(&mut tx, workflow.id, &definition, false).await? {
+                Overlap::Start => {
+                    let child = child_of(WorkflowExecution::new(workflow.id, input_data));
+                    let child = db::create_workflow_execution(&mut *tx, &child).await?;
+                    tx.commit().await?;
+
+                    let guard = ChildExecution::new(self.clone(), &definition, &child, true);
+                    let child = self.run_execution(&definition, child).await;
+                    guard.finish();
+                    child?
+                }
+                Overlap::Wait => {
+                    let child = child_of(WorkflowExecution::pending(workflow.id, input_data));
+                    let child = db::enqueue_execution(&mut *tx, &child).await?;
+                    tx.commit().await?;
+
+                    tracing::info!(
+                        "Queued sub-workflow execution {}: workflow {} is at its execution limit",
+                        child.id,
+                        workflow.id
+                    );
+                    let guard = ChildExecution::new(self.clone(), &definition, &child, false);
+                    let child = self.wait_for_child(child.id).await;
+                    guard.finish();
+                    child?
+                }
+                Overlap::Skip(reason) => {
+                    let child = child_of(WorkflowExecution::skipped(
+                        workflow.id,
+                        input_data,
+                        reason.clone(),
+                    ));
+                    db::create_workflow_execution(&mut *tx, &child).await?;
+                    tx.commit().await?;
+
+                    return Ok(NodeOutput::error(format!(
+                        "Sub-workflow '{}' was not run: {}",
+                        workflow.name, reason
+                    )));
+                }
+            };
+
+            match child.status {
+                ExecutionStatus::Success => Ok(NodeOutput::success(serde_json::json!({
+                    "execution_id": child.id,
+                    "workflow_id": workflow.id,
+                    "workflow_name": workflow.name,
+                    "status": "success",
+                    "output": child.output_data
+                }))),
+                ExecutionStatus::Waiting => {
+                    self.cancel_execution(child.id).await?;
+                    Ok(NodeOutput::error(format!(
+                        "Sub-workflow '{}' suspended at a wait node, so it was cancelled; \
+                         start it with wait: false to let it wait",
+                        workflow.name
+                    )))
+                }
+                status => Ok(NodeOutput::error(format!(
+                    "Sub-workflow '{}' {}: {}",
+                    workflow.name,
+                    status,
+                    child
+                        .error
+                        .unwrap_or_else(|| format!("execution {} recorded no error", child.id))
+                ))),
+            }
+        })
+    }
+
+    /// Wait until a queued sub-workflow execution finishes or suspends
+    async fn wait_for_child(&self, execution_id: Uuid) -> Result<WorkflowExecution> {
+        loop {
+            tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+            let child = db::get_workflow_execution(&self.pool, execution_id).await?;
+            if child.status.is_finished() || child.status == ExecutionStatus::Waiting {
+                return Ok(child);
             }
         }
+    }
 
-        Ok(())
+    /// Run the nested body of a node once, passing it the node's input
+    async fn run_body(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        context: &NodeContext,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        iteration: Option<&Iteration>,
+    ) -> Result<serde_json::Value> {
+        let run = GraphRun {
+            iteration: iteration.cloned(),
+            root_input: Some((
+                node_def.id.clone(),
+                context.get_main_input().cloned().unwrap_or_default(),
+            )),
+            node_outputs: node_outputs.clone(),
+            workflow_variables: context.variables.clone(),
+            ..Default::default()
+        };
+
+        self.run_graph(workflow, &node_def.nodes, &node_def.edges, execution, run)
+            .await
     }
 
-    /// Compute node levels for parallel execution
-    /// Returns a vector of levels, where each level is a vector of node IDs that can be executed in parallel
-    fn compute_node_levels(&self, workflow: &WorkflowDefinition) -> Result<Vec<Vec<String>>> {
-        let mut in_degree: HashMap<String, usize> = HashMap::new();
-        let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
+    /// Run the nested body of a `loop` node once per item and collect the results.
+    ///
+    /// Each iteration runs the body sequentially with the item and index bound
+    /// as workflow variables and the item passed as input to the body's root
+    /// nodes. Up to `concurrency` iterations run at the same time.
+    async fn run_loop_body(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        mut output: NodeOutput,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        workflow_variables: &HashMap<String, serde_json::Value>,
+    ) -> Result<NodeOutput> {
+        let items = output
+            .data
+            .get("items")
+            .and_then(|items| items.as_array())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Node {} has a body but its output has no items array",
+                    node_def.id
+                )
+            })?;
+        let item_var = output.data["item_variable"]
+            .as_str()
+            .unwrap_or("item")
+            .to_string();
+        let index_var = output.data["index_variable"]
+            .as_str()
+            .unwrap_or("index")
+            .to_string();
+        let concurrency = output.data["concurrency"].as_u64().unwrap_or(1).max(1) as usize;
 
-        // Initialize
-        for node in &workflow.nodes {
-            in_degree.insert(node.id.clone(), 0);
-            adj_list.insert(node.id.clone(), Vec::new());
+        tracing::info!(
+            "Running body of node {} for {} items (concurrency {})",
+            node_def.id,
+            items.len(),
+            concurrency
+        );
+
+        let shared_workflow = Arc::new(workflow.clone());
+        let body = Arc::new(node_def.clone());
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = JoinSet::new();
+        let total = items.len();
+
+        for (index, item) in items.into_iter().enumerate() {
+            let mut run = GraphRun {
+                iteration: Some(Iteration {
+                    index: index as i32,
+                    branch: None,
+                }),
+                root_input: Some((node_def.id.clone(), item.clone())),
+                node_outputs: node_outputs.clone(),
+                workflow_variables: workflow_variables.clone(),
+                ..Default::default()
+            };
+            run.workflow_variables.insert(item_var.clone(), item);
+            run.workflow_variables
+                .insert(index_var.clone(), serde_json::json!(index));
+
+            let permit = Arc::clone(&semaphore).acquire_owned().await?;
+            let engine = self.clone();
+            let workflow = Arc::clone(&shared_workflow);
+            let body = Arc::clone(&body);
+            let execution = execution.clone();
+
+            tasks.spawn(async move {
+                let _permit = permit;
+                let result = engine
+                    .run_graph(&workflow, &body.nodes, &body.edges, &execution, run)
+                    .await;
+                (index, result)
+            });
         }
 
-        // Build adjacency list and in-degree map
-        for edge in &workflow.edges {
-            adj_list.get_mut(&edge.from).unwrap().push(edge.to.clone());
-            *in_degree.get_mut(&edge.to).unwrap() += 1;
+        // Collect results in item order; the first failure aborts the remaining iterations
+        let mut results = vec![serde_json::Value::Null; total];
+        while let Some(joined) = tasks.join_next().await {
+            let (index, result) = joined.map_err(|e| {
+                anyhow::anyhow!("Loop iteration of node {} panicked: {}", node_def.id, e)
+            })?;
+            results[index] = result
+                .with_context(|| format!("Loop {} failed at iteration {}", node_def.id, index))?;
         }
 
-        // Group nodes by level
-        let mut levels: Vec<Vec<String>> = Vec::new();
-        let mut current_level: Vec<String> = in_degree
-            .iter()
-            .filter(|&(_, &degree)| degree == 0)
-            .map(|(id, _)| id.clone())
-            .collect();
+        if let Some(data) = output.data.as_object_mut() {
+            data.insert("iterations".to_string(), serde_json::json!(total));
+            data.insert("results".to_string(), serde_json::Value::Array(results));
+        }
 
-        let mut processed = 0;
+        Ok(output)
+    }
 
-        while !current_level.is_empty() {
-            levels.push(current_level.clone());
-            processed += current_level.len();
+    /// Run the nested body of a `split` node once per branch and collect the results.
+    ///
+    /// Each branch runs the body sequentially with its item passed as input to
+    /// the body's root nodes and bound to the `item` variable, and its key bound
+    /// to the `branch` variable. Up to `concurrency` branches run at the same
+    /// time. With `wait_for_all` a failed branch fails the node once every
+    /// branch has finished; otherwise the first failure aborts the other branches.
+    async fn run_split_body(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        mut output: NodeOutput,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        workflow_variables: &HashMap<String, serde_json::Value>,
+    ) -> Result<NodeOutput> {
+        let branches = output
+            .data
+            .get("branches")
+            .and_then(|branches| branches.as_array())
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Node {} has a body but its output has no branches array",
+                    node_def.id
+                )
+            })?;
+        let wait_for_all = output.data["wait_for_all"].as_bool().unwrap_or(true);
+        let total = branches.len();
+        let concurrency = output.data["concurrency"]
+            .as_u64()
+            .map_or(total, |concurrency| concurrency as usize)
+            .max(1);
 
-            let mut next_level = Vec::new();
+        tracing::info!(
+            "Running body of node {} for {} branches (concurrency {})",
+            node_def.id,
+            total,
+            concurrency
+        );
 
-            for node_id in &current_level {
-                // Reduce in-degree for neighbors
-                if let Some(neighbors) = adj_list.get(node_id) {
-                    for neighbor in neighbors {
-                        let degree = in_degree.get_mut(neighbor).unwrap();
-                        *degree -= 1;
-                        if *degree == 0 {
-                            next_level.push(neighbor.clone());
-                        }
+        let shared_workflow = Arc::new(workflow.clone());
+        let body = Arc::new(node_def.clone());
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = JoinSet::new();
+
+        for (index, branch) in branches.into_iter().enumerate() {
+            let key = match &branch["key"] {
+                serde_json::Value::String(key) => key.clone(),
+                serde_json::Value::Null => index.to_string(),
+                key => key.to_string(),
+            };
+            let item = branch["item"].clone();
+
+            let mut run = GraphRun {
+                iteration: Some(Iteration {
+                    index: index as i32,
+                    branch: Some(key.clone()),
+                }),
+                root_input: Some((node_def.id.clone(), item.clone())),
+                node_outputs: node_outputs.clone(),
+                workflow_variables: workflow_variables.clone(),
+                ..Default::default()
+            };
+            run.workflow_variables.insert("item".to_string(), item);
+            run.workflow_variables
+                .insert("branch".to_string(), serde_json::json!(key));
+
+            let semaphore = Arc::clone(&semaphore);
+            let engine = self.clone();
+            let workflow = Arc::clone(&shared_workflow);
+            let body = Arc::clone(&body);
+            let execution = execution.clone();
+
+            tasks.spawn(async move {
+                let result = match semaphore.acquire_owned().await {
+                    Ok(_permit) => {
+                        engine
+                            .run_graph(&workflow, &body.nodes, &body.edges, &execution, run)
+                            .await
                     }
+                    Err(e) => Err(e.into()),
+                };
+                (index, key, result)
+            });
+        }
+
+        // Collect results in branch order
+        let mut results = vec![serde_json::Value::Null; total];
+        let mut failures = Vec::new();
+        while let Some(joined) = tasks.join_next().await {
+            let (index, key, result) = joined.map_err(|e| {
+                anyhow::anyhow!("Split branch of node {} panicked: {}", node_def.id, e)
+            })?;
+            match result {
+                Ok(result) => results[index] = result,
+                Err(e) if wait_for_all => failures.push(format!("{}: {:#}", key, e)),
+                Err(e) => {
+                    return Err(
+                        e.context(format!("Split {} failed in branch {}", node_def.id, key))
+                    );
                 }
             }
+        }
 
-            current_level = next_level;
+        if !failures.is_empty() {
+            anyhow::bail!(
+                "Split {} failed in {} of {} branches: {}",
+                node_def.id,
+                failures.len(),
+                total,
+                failures.join("; ")
+            );
         }
 
-        // Check if all nodes were processed (cycle detection)
-        if processed != workflow.nodes.len() {
-            anyhow::bail!("Workflow contains a cycle");
+        if let Some(data) = output.data.as_object_mut() {
+            data.insert("results".to_string(), serde_json::Value::Array(results));
         }
 
-        Ok(levels)
+        Ok(output)
+    }
+
+    /// Run the nested body of a `try_catch` node and handle its failure.
+    ///
+    /// The body runs once with the node's input passed to its root nodes. If it
+    /// fails, `error_strategy` decides what the node emits: `catch` emits the
+    /// error on the `error` port, `ignore` and `log` emit `default_value` as if
+    /// the body had succeeded. With `continue_on_error: false` the failure is
+    /// returned as the node's own error.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_try_body(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        output: NodeOutput,
+        context: &NodeContext,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        iteration: Option<&Iteration>,
+    ) -> Result<NodeOutput> {
+        let error = match self
+            .run_body(
+                workflow,
+                execution,
+                node_def,
+                context,
+                node_outputs,
+                iteration,
+            )
+            .await
+        {
+            Ok(result) => return Ok(NodeOutput::success(result)),
+            Err(error) => error,
+        };
+
+        if !output.data["continue_on_error"].as_bool().unwrap_or(true) {
+            return Err(error);
+        }
+
+        let default_value = output.data["default_value"].clone();
+        match output.data["error_strategy"].as_str().unwrap_or("catch") {
+            "ignore" => {
+                tracing::debug!("Ignoring error in body of {}: {:#}", node_def.id, error);
+                Ok(NodeOutput::success(default_value))
+            }
+            "log" => {
+                tracing::error!("Error in body of {}: {:#}", node_def.id, error);
+                Ok(NodeOutput::success(default_value))
+            }
+            _ => Ok(error_output(
+                &error,
+                &node_def.id,
+                context.get_main_input().cloned(),
+            )),
+        }
+    }
+
+    /// Run the nested body of a `timeout` node under a shared deadline.
+    ///
+    /// When the deadline expires the body's in-flight nodes are cancelled and
+    /// `on_timeout` decides the outcome: `error` fails the node, `default`
+    /// emits `default_value` and `skip` emits the `timeout` port so that the
+    /// downstream branch is skipped.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_timeout_body(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        output: NodeOutput,
+        context: &NodeContext,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        iteration: Option<&Iteration>,
+    ) -> Result<NodeOutput> {
+        let millis = output.data["timeout_ms"].as_u64().unwrap_or_default();
+        let body = self.run_body(
+            workflow,
+            execution,
+            node_def,
+            context,
+            node_outputs,
+            iteration,
+        );
+
+        // Dropping the body on expiry cancels its in-flight nodes
+        if let Ok(result) = timeout(Duration::from_millis(millis), body).await {
+            return Ok(NodeOutput::success(result?));
+        }
+
+        tracing::warn!(
+            "Deadline of node {} expired after {} ms",
+            node_def.id,
+            millis
+        );
+        match output.data["on_timeout"].as_str().unwrap_or("error") {
+            "default" => Ok(NodeOutput::success(output.data["default_value"].clone())),
+            "skip" => Ok(NodeOutput::success(serde_json::json!({
+                "timed_out": true,
+                "timeout_ms": millis,
+            }))
+            .with_port(routing::TIMEOUT_PORT)),
+            _ => Err(DeadlineExceeded { millis }.into()),
+        }
+    }
+
+    /// Guard the nested body of a `circuit_breaker` node with its shared circuit.
+    ///
+    /// While the circuit is open the body is skipped and the node emits its
+    /// `fallback` value on the `open` port. Otherwise the body runs and its
+    /// outcome is recorded on the circuit. Without a body the node only reports
+    /// the current state of the circuit.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_circuit_breaker(
+        &self,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        node_def: &NodeDefinition,
+        mut output: NodeOutput,
+        context: &NodeContext,
+        node_outputs: &HashMap<String, serde_json::Value>,
+        iteration: Option<&Iteration>,
+    ) -> Result<NodeOutput> {
+        let circuit_id = output.data["circuit_id"]
+            .as_str()
+            .unwrap_or(&node_def.id)
+            .to_string();
+
+        if node_def.nodes.is_empty() {
+            let circuit = self.circuits.get(&circuit_id).await?;
+            if let Some(data) = output.data.as_object_mut() {
+                data.insert(
+                    "current_state".to_string(),
+                    serde_json::json!(circuit.state.to_string()),
+                );
+            }
+            return Ok(output);
+        }
+
+        let config = CircuitBreakerConfig {
+            failure_threshold: output.data["failure_threshold"].as_u64().unwrap_or(5) as u32,
+            success_threshold: output.data["success_threshold"].as_u64().unwrap_or(2) as u32,
+            open_timeout: chrono::Duration::seconds(
+                output.data["timeout_seconds"].as_i64().unwrap_or(60),
+            ),
+        };
+
+        let admission = self
+            .circuits
+            .update(&circuit_id, |circuit| {
+                circuit.admit(&config, chrono::Utc::now())
+            })
+            .await?;
+
+        if admission == Admission::Rejected {
+            tracing::warn!(
+                "Circuit {} is open, node {} returns its fallback",
+                circuit_id,
+                node_def.id
+            );
+            return Ok(NodeOutput::success(output.data["fallback"].clone()).with_port(OPEN_PORT));
+        }
+
+        let result = self
+            .run_body(
+                workflow,
+                execution,
+                node_def,
+                context,
+                node_outputs,
+                iteration,
+            )
+            .await;
+
+        self.circuits
+            .update(&circuit_id, |circuit| {
+                if result.is_ok() {
+                    circuit.record_success(&config, admission, chrono::Utc::now());
+                } else {
+                    circuit.record_failure(&config, admission, chrono::Utc::now());
+                }
+            })
+            .await?;
+
+        Ok(NodeOutput::success(result?))
+    }
+
+    /// Execute a node with already resolved parameters, applying the timeout if any
+    async fn invoke_node(
+        registry: &NodeRegistry,
+        node_def: &NodeDefinition,
+        context: &NodeContext,
+        parameters: &serde_json::Value,
+        timeout_duration: Option<Duration>,
+    ) -> Result<NodeOutput> {
+        let mut node = registry.create(&node_def.node_type)?;
+        if context.dry_run && node.has_side_effects(parameters) {
+            node = Box::new(DryRunRecorder::new(node));
+        }
+        let execute_future = node.execute(context, parameters);
+
+        if let Some(duration) = timeout_duration {
+            match timeout(duration, execute_future).await {
+                Ok(result) => result,
+                Err(_) => Err(NodeTimeout {
+                    seconds: duration.as_secs(),
+                }
+                .into()),
+            }
+        } else {
+            execute_future.await
+        }
     }
 
     /// Perform topological sort to determine execution order
-    fn topological_sort(&self, workflow: &WorkflowDefinition) -> Result<Vec<String>> {
+    fn topological_sort(
+        &self,
+        nodes: &[NodeDefinition],
+        edges: &[EdgeDefinition],
+    ) -> Result<Vec<String>> {
         let mut in_degree: HashMap<String, usize> = HashMap::new();
         let mut adj_list: HashMap<String, Vec<String>> = HashMap::new();
 
         // Initialize
-        for node in &workflow.nodes {
+        for node in nodes {
             in_degree.insert(node.id.clone(), 0);
             adj_list.insert(node.id.clone(), Vec::new());
         }
 
         // Build adjacency list and in-degree map
-        for edge in &workflow.edges {
+        for edge in edges {
             adj_list.get_mut(&edge.from).unwrap().push(edge.to.clone());
             *in_degree.get_mut(&edge.to).unwrap() += 1;
         }
 
-        // Find nodes with no incoming edges
-        let mut queue: Vec<String> = in_degree
+        // Find nodes with no incoming edges, in definition order so that the
+        // order (and the node whose output is final) is deterministic
+        let mut queue: VecDeque<String> = nodes
             .iter()
-            .filter(|&(_, &degree)| degree == 0)
-            .map(|(id, _)| id.clone())
+            .filter(|node| in_degree[&node.id] == 0)
+            .map(|node| node.id.clone())
             .collect();
 
         let mut result = Vec::new();
 
-        while let Some(node_id) = queue.pop() {
+        while let Some(node_id) = queue.pop_front() {
             result.push(node_id.clone());
 
             // Reduce in-degree for neighbors
@@ -580,17 +2062,850 @@ impl WorkflowEngine {
                     let degree = in_degree.get_mut(neighbor).unwrap();
                     *degree -= 1;
                     if *degree == 0 {
-                        queue.push(neighbor.clone());
+                        queue.push_back(neighbor.clone());
                     }
                 }
             }
         }
 
         // Check if all nodes were processed (cycle detection)
-        if result.len() != workflow.nodes.len() {
+        if result.len() != nodes.len() {
             anyhow::bail!("Workflow contains a cycle");
         }
 
         Ok(result)
     }
 }
+
+/// Error returned when a node exceeds its timeout
+#[derive(Debug, thiserror::Error)]
+#[error("Node execution timed out after {seconds} seconds")]
+pub struct NodeTimeout {
+    pub seconds: u64,
+}
+
+/// How often a running execution reports a heartbeat and checks whether its
+/// cancellation was requested
+pub const CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Running executions without a heartbeat for this long are considered
+/// interrupted and can be recovered
+pub const STALE_EXECUTION_AFTER: Duration = Duration::from_secs(30);
+
+/// Error that stops a run whose cancellation was requested
+#[derive(Debug, thiserror::Error)]
+#[error("Execution was cancelled")]
+pub struct ExecutionCancelled;
+
+/// Report that the execution is alive and cancel `token` once cancellation of
+/// the execution is requested in the database. Returns when `token` is
+/// cancelled.
+async fn watch_cancel_requests(pool: PgPool, execution_id: Uuid, token: CancellationToken) {
+    let mut interval = tokio::time::interval(CANCEL_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = token.cancelled() => return,
+        }
+        match db::heartbeat_execution(&pool, execution_id).await {
+            Ok(true) => {
+                tracing::info!("Cancellation of execution {} requested", execution_id);
+                token.cancel();
+                return;
+            }
+            Ok(false) => {}
+            Err(e) => tracing::warn!(
+                "Failed to check cancellation of execution {}: {}",
+                execution_id,
+                e
+            ),
+        }
+    }
+}
+
+/// Node type at which executions suspend until they are resumed
+pub const WAIT_NODE_TYPE: &str = "wait_webhook";
+
+/// Error that stops a run suspended at a wait node; not a failure
+#[derive(Debug, thiserror::Error)]
+#[error("suspended until wait {wait_id} is resumed")]
+pub struct Suspended {
+    pub wait_id: String,
+}
+
+/// Error returned when the deadline of a `timeout` node expires
+#[derive(Debug, thiserror::Error)]
+#[error("Deadline of {millis} ms expired")]
+pub struct DeadlineExceeded {
+    pub millis: u64,
+}
+
+/// Node execution record that is marked as cancelled if the node is dropped
+/// before it finishes, e.g. when an enclosing deadline expires
+struct InFlightNode {
+    pool: PgPool,
+    id: Uuid,
+    finished: bool,
+}
+
+impl InFlightNode {
+    fn new(pool: PgPool, id: Uuid) -> Self {
+        Self {
+            pool,
+            id,
+            finished: false,
+        }
+    }
+
+    fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for InFlightNode {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        let pool = self.pool.clone();
+        let id = self.id;
+        tokio::spawn(async move {
+            if let Err(e) = db::update_node_execution_status(
+                &pool,
+                id,
+                ExecutionStatus::Cancelled,
+                None,
+                Some("Node execution was cancelled".to_string()),
+            )
+            .await
+            {
+                tracing::error!("Failed to record cancelled node execution {}: {}", id, e);
+            }
+        });
+    }
+}
+
+/// Node type that starts sub-workflows
+pub const EXECUTE_WORKFLOW_NODE_TYPE: &str = "execute_workflow";
+
+/// Sub-workflow execution that is cancelled if the node waiting for it is
+/// dropped before it finishes, e.g. when the parent execution is cancelled
+struct ChildExecution {
+    engine: WorkflowEngine,
+    workflow: WorkflowDefinition,
+    execution: WorkflowExecution,
+    /// Whether the child runs in the task of the parent rather than on a worker
+    inline: bool,
+    finished: bool,
+}
+
+impl ChildExecution {
+    fn new(
+        engine: WorkflowEngine,
+        workflow: &WorkflowDefinition,
+        execution: &WorkflowExecution,
+        inline: bool,
+    ) -> Self {
+        Self {
+            engine,
+            workflow: workflow.clone(),
+            execution: execution.clone(),
+            inline,
+            finished: false,
+        }
+    }
+
+    fn finish(mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for ChildExecution {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        let engine = self.engine.clone();
+        let workflow = self.workflow.clone();
+        let execution = self.execution.clone();
+        let inline = self.inline;
+        tokio::spawn(async move {
+            let id = execution.id;
+            tracing::warn!(
+                "Cancelling sub-workflow execution {}: its parent stopped",
+                id
+            );
+
+            // Nothing else runs an inline child once its run is dropped
+            let result = if inline {
+                engine
+                    .finish_execution(&workflow, execution, Err(ExecutionCancelled.into()))
+                    .await
+            } else {
+                engine.cancel_execution(id).await
+            };
+            if let Err(e) = result {
+                tracing::error!("Failed to cancel sub-workflow execution {}: {}", id, e);
+            }
+        });
+    }
+}
+
+/// Port emitted by a `circuit_breaker` node that skipped its body because the circuit is open
+pub const OPEN_PORT: &str = "open";
+
+/// Error returned when a node fails after its last attempt
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct NodeFailure {
+    pub node_id: String,
+    pub message: String,
+}
+
+/// Mark a node as finished for its successors and queue those whose
+/// predecessors have all finished.
+///
+/// A `merge` successor that has not started is also queued once the branches
+/// that delivered satisfy its strategy. Otherwise its timeout starts running
+/// when its first branch delivers.
+fn release_successors<'a>(
+    node_id: &str,
+    workflow: &'a WorkflowDefinition,
+    node_ports: &HashMap<String, Option<String>>,
+    started: &HashSet<String>,
+    pending: &mut HashMap<&str, usize>,
+    merge_deadlines: &mut HashMap<&'a str, Instant>,
+    ready: &mut VecDeque<&'a NodeDefinition>,
+) {
+    for edge in workflow.edges.iter().filter(|edge| edge.from == node_id) {
+        let Some(count) = pending.get_mut(edge.to.as_str()) else {
+            continue;
+        };
+        *count -= 1;
+        let running = *count;
+        let Some(successor) = workflow.nodes.iter().find(|node| node.id == edge.to) else {
+            continue;
+        };
+        if running == 0 {
+            ready.push_back(successor);
+            continue;
+        }
+
+        let Some(join) = MergeJoin::of(successor) else {
+            continue;
+        };
+        if started.contains(&successor.id) {
+            continue;
+        }
+        let delivered = routing::taken_incoming_edges(&successor.id, &workflow.edges, node_ports)
+            .map_or(0, |edges| edges.len());
+        if join.is_satisfied(delivered, running) {
+            ready.push_back(successor);
+        } else if delivered > 0
+            && let Some(timeout) = join.timeout
+        {
+            merge_deadlines
+                .entry(successor.id.as_str())
+                .or_insert_with(|| Instant::now() + timeout);
+        }
+    }
+}
+
+/// Route the failure of a node along its error edges, if it has any.
+///
+/// The failure becomes the node's output on the error port so that only the
+/// error edges are followed.
+fn route_failure(
+    node_id: &str,
+    edges: &[EdgeDefinition],
+    input: Option<serde_json::Value>,
+    result: Result<NodeOutput>,
+) -> Result<NodeOutput> {
+    match result {
+        Err(error) if routing::has_error_edges(node_id, edges) => {
+            tracing::warn!("Node {} failed, following its error edges", node_id);
+            Ok(error_output(&error, node_id, input))
+        }
+        result => result,
+    }
+}
+
+/// Build the output emitted on the error port for a failure.
+///
+/// Carries the error message, the id of the node that failed and its input.
+fn error_output(
+    error: &anyhow::Error,
+    node_id: &str,
+    input: Option<serde_json::Value>,
+) -> NodeOutput {
+    let failed_node = error
+        .downcast_ref::<NodeFailure>()
+        .map_or(node_id, |failure| failure.node_id.as_str());
+
+    NodeOutput::success(serde_json::json!({
+        "error": error.to_string(),
+        "node_id": failed_node,
+        "input": input.unwrap_or_default(),
+    }))
+    .with_port(routing::ERROR_PORT)
+}
+
+/// Future returned by recursive engine methods
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Loop iteration or split branch that runs a nested body
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Iteration {
+    /// Index of the loop item or split branch
+    index: i32,
+    /// Key of the split branch
+    branch: Option<String>,
+}
+
+/// Tag a node execution record with the iteration of the body it ran in
+fn in_iteration(node_execution: NodeExecution, iteration: Option<&Iteration>) -> NodeExecution {
+    match iteration {
+        Some(iteration) => node_execution
+            .with_iteration(Some(iteration.index))
+            .with_branch(iteration.branch.clone()),
+        None => node_execution,
+    }
+}
+
+/// State of a sequential run over a graph of nodes.
+///
+/// Stored with the execution when it suspends at a wait node.
+#[derive(Default, Serialize, Deserialize)]
+struct GraphRun {
+    /// Iteration recorded on the node executions of a loop or split body
+    iteration: Option<Iteration>,
+    /// Input passed to the root nodes of a nested body, keyed by input name
+    root_input: Option<(String, serde_json::Value)>,
+    /// Outputs of nodes that already ran
+    node_outputs: HashMap<String, serde_json::Value>,
+    /// Workflow variables visible to the next node
+    workflow_variables: HashMap<String, serde_json::Value>,
+    /// Output port each completed node took
+    #[serde(default)]
+    node_ports: HashMap<String, Option<String>>,
+    /// Nodes already executed or skipped
+    #[serde(default)]
+    handled: HashSet<String>,
+    /// Last node that ran successfully
+    #[serde(default)]
+    last_executed: Option<String>,
+}
+
+impl GraphRun {
+    /// State of a new run of the workflow with the given input
+    fn start(workflow: &WorkflowDefinition, input_data: Option<serde_json::Value>) -> Self {
+        let mut run = GraphRun::default();
+
+        // Initialize with input data if provided
+        if let Some(input) = input_data {
+            run.workflow_variables
+                .insert("input".to_string(), input.clone());
+
+            // If there's a start node, initialize it with input
+            for node_def in &workflow.nodes {
+                if node_def.node_type == "start" {
+                    run.node_outputs.insert(node_def.id.clone(), input.clone());
+                    break;
+                }
+            }
+        }
+
+        run
+    }
+
+    /// Rebuild the state of an interrupted run from its node executions.
+    ///
+    /// Top-level nodes whose latest record succeeded or was skipped are
+    /// handled; variables are applied in completion order. Also returns the
+    /// records left unfinished by the interruption.
+    fn recover<'a>(
+        workflow: &WorkflowDefinition,
+        input_data: Option<serde_json::Value>,
+        node_executions: &'a [NodeExecution],
+    ) -> (Self, Vec<&'a NodeExecution>) {
+        let mut run = Self::start(workflow, input_data);
+        run.apply(&completed_records(workflow, node_executions));
+
+        let interrupted = node_executions
+            .iter()
+            .filter(|node_execution| !node_execution.status.is_finished())
+            .collect();
+
+        (run, interrupted)
+    }
+
+    /// Build the state of a re-run of an execution from its node executions.
+    ///
+    /// Top-level nodes that completed and are not `from_node` or downstream
+    /// of it keep their results; everything else runs again. Without
+    /// `from_node` every node runs again. Also returns the records whose
+    /// results are kept.
+    fn rerun<'a>(
+        workflow: &WorkflowDefinition,
+        input_data: Option<serde_json::Value>,
+        node_executions: &'a [NodeExecution],
+        from_node: Option<&str>,
+    ) -> Result<(Self, Vec<&'a NodeExecution>)> {
+        let mut run = Self::start(workflow, input_data);
+        let Some(from_node) = from_node else {
+            return Ok((run, Vec::new()));
+        };
+        if !workflow.nodes.iter().any(|node| node.id == from_node) {
+            anyhow::bail!("Node {} is not a top-level node of the workflow", from_node);
+        }
+
+        let rerun = routing::downstream_nodes(from_node, &workflow.edges);
+        let kept: Vec<&NodeExecution> = completed_records(workflow, node_executions)
+            .into_iter()
+            .filter(|node_execution| !rerun.contains(node_execution.node_id.as_str()))
+            .collect();
+        run.apply(&kept);
+
+        Ok((run, kept))
+    }
+
+    /// Build the state of a partial execution.
+    ///
+    /// Top-level nodes outside the part to run are handled. The ones the
+    /// part depends on get the output they had in `seed_executions`, or
+    /// their pinned output; a start node gets the input. Also returns the
+    /// records of the seeded nodes, to be copied to the partial execution.
+    fn partial(
+        workflow: &WorkflowDefinition,
+        input_data: Option<serde_json::Value>,
+        seed_executions: &[NodeExecution],
+        partial: &PartialRun,
+        ignore_pinned_data: bool,
+    ) -> Result<(Self, Vec<NodeExecution>)> {
+        let target = partial.node_id();
+        if !workflow.nodes.iter().any(|node| node.id == target) {
+            anyhow::bail!("Node {} is not a top-level node of the workflow", target);
+        }
+
+        let mut run = Self::start(workflow, input_data);
+        let upstream = routing::upstream_nodes(target, &workflow.edges);
+        let part = Self::part(workflow, partial);
+
+        // Outputs of the seed execution for the nodes the part depends on
+        let seeded: Vec<&NodeExecution> = completed_records(workflow, seed_executions)
+            .into_iter()
+            .filter(|node_execution| {
+                let node_id = node_execution.node_id.as_str();
+                upstream.contains(node_id) && !part.contains(node_id)
+            })
+            .collect();
+        run.apply(&seeded);
+        let mut seeds: Vec<NodeExecution> = seeded.into_iter().cloned().collect();
+
+        for node_def in &workflow.nodes {
+            let node_id = node_def.id.as_str();
+            if !upstream.contains(node_id)
+                || part.contains(node_id)
+                || run.handled.contains(node_id)
+            {
+                continue;
+            }
+
+            let output = match &node_def.pinned_output {
+                Some(output) if !ignore_pinned_data => output.clone(),
+                _ if node_def.node_type == "start" => run
+                    .node_outputs
+                    .get(node_id)
+                    .cloned()
+                    .unwrap_or(serde_json::json!({})),
+                _ => anyhow::bail!(
+                    "Node {} needs the output of node {}; run it from an execution where {} \
+                     succeeded or pin its output",
+                    target,
+                    node_id,
+                    node_id
+                ),
+            };
+            run.handled.insert(node_id.to_string());
+            run.node_outputs.insert(node_id.to_string(), output.clone());
+            run.node_ports.insert(node_id.to_string(), None);
+            seeds.push(NodeExecution::pinned(
+                Uuid::nil(),
+                node_id.to_string(),
+                output,
+            ));
+        }
+
+        run.restrict(workflow, partial);
+        Ok((run, seeds))
+    }
+
+    /// Top-level nodes a partial execution runs
+    fn part<'a>(workflow: &'a WorkflowDefinition, partial: &'a PartialRun) -> HashSet<&'a str> {
+        match partial {
+            PartialRun::Only(node_id) => HashSet::from([node_id.as_str()]),
+            PartialRun::Until(node_id) => routing::upstream_nodes(node_id, &workflow.edges),
+        }
+    }
+
+    /// Mark the top-level nodes outside the part of a partial execution as
+    /// handled, so that they do not run
+    fn restrict(&mut self, workflow: &WorkflowDefinition, partial: &PartialRun) {
+        let part = Self::part(workflow, partial);
+        for node_def in &workflow.nodes {
+            if !part.contains(node_def.id.as_str()) {
+                self.handled.insert(node_def.id.clone());
+            }
+        }
+    }
+
+    /// Mark the nodes of completed records as handled and apply their
+    /// outputs, ports and variables, in order
+    fn apply(&mut self, completed: &[&NodeExecution]) {
+        for node_execution in completed {
+            let node_id = node_execution.node_id.clone();
+            self.handled.insert(node_id.clone());
+            if node_execution.status == ExecutionStatus::Skipped {
+                continue;
+            }
+
+            let output = node_execution
+                .output_data
+                .clone()
+                .unwrap_or(serde_json::json!({}));
+            self.node_outputs.insert(node_id.clone(), output);
+            self.node_ports
+                .insert(node_id.clone(), node_execution.output_port.clone());
+            if let Some(serde_json::Value::Object(variables)) = &node_execution.variables {
+                self.workflow_variables
+                    .extend(variables.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+            self.last_executed = Some(node_id);
+        }
+    }
+}
+
+/// Latest record of each top-level node of the workflow; records must be
+/// ordered by start time
+fn latest_records<'a>(
+    workflow: &WorkflowDefinition,
+    node_executions: &'a [NodeExecution],
+) -> HashMap<&'a str, &'a NodeExecution> {
+    let mut latest = HashMap::new();
+    for node_execution in node_executions {
+        if node_execution.iteration.is_none()
+            && workflow
+                .nodes
+                .iter()
+                .any(|node| node.id == node_execution.node_id)
+        {
+            latest.insert(node_execution.node_id.as_str(), node_execution);
+        }
+    }
+    latest
+}
+
+/// Latest records of the top-level nodes that succeeded, were skipped or were pinned,
+/// in completion order
+fn completed_records<'a>(
+    workflow: &WorkflowDefinition,
+    node_executions: &'a [NodeExecution],
+) -> Vec<&'a NodeExecution> {
+    let mut completed: Vec<&NodeExecution> = latest_records(workflow, node_executions)
+        .into_values()
+        .filter(|node_execution| {
+            matches!(
+                node_execution.status,
+                ExecutionStatus::Success | ExecutionStatus::Skipped | ExecutionStatus::Pinned
+            )
+        })
+        .collect();
+    completed.sort_by_key(|node_execution| node_execution.finished_at);
+    completed
+}
+
+/// The top-level node that failed or was cancelled first, i.e. where a
+/// re-run of a failed execution should start
+pub fn first_failed_node(
+    workflow: &WorkflowDefinition,
+    node_executions: &[NodeExecution],
+) -> Option<String> {
+    latest_records(workflow, node_executions)
+        .into_values()
+        .filter(|node_execution| {
+            matches!(
+                node_execution.status,
+                ExecutionStatus::Failed | ExecutionStatus::Cancelled
+            )
+        })
+        .min_by_key(|node_execution| node_execution.started_at)
+        .map(|node_execution| node_execution.node_id.clone())
+}
+
+/// Whether a node returns without running: it is disabled or pinned
+fn short_circuited(execution: &WorkflowExecution, node_def: &NodeDefinition) -> bool {
+    node_def.disabled || pinned_output(execution, node_def).is_some()
+}
+
+/// Input of a node as a single value: the input itself for a single
+/// predecessor, inputs keyed by input name for several
+fn main_input(context: &NodeContext) -> serde_json::Value {
+    match context.inputs.len() {
+        0 => serde_json::Value::Null,
+        1 => context.get_main_input().cloned().unwrap_or_default(),
+        _ => serde_json::to_value(&context.inputs).unwrap_or_default(),
+    }
+}
+
+/// Output pinned on a node, unless the execution ignores pinned data
+fn pinned_output<'a>(
+    execution: &WorkflowExecution,
+    node_def: &'a NodeDefinition,
+) -> Option<&'a serde_json::Value> {
+    node_def
+        .pinned_output
+        .as_ref()
+        .filter(|_| !execution.ignore_pinned_data)
+}
+
+/// Build the data parameter expressions of a node can reference
+fn expression_scope<'a>(
+    context: &NodeContext,
+    node_outputs: &'a HashMap<String, serde_json::Value>,
+    workflow_variables: &'a HashMap<String, serde_json::Value>,
+    execution: &WorkflowExecution,
+) -> ExpressionScope<'a> {
+    ExpressionScope {
+        input: main_input(context),
+        nodes: node_outputs,
+        variables: workflow_variables,
+        execution: serde_json::json!({
+            "id": execution.id,
+            "workflow_id": execution.workflow_id,
+            "node_id": context.node_id,
+            "started_at": execution.started_at,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workflow(node_ids: &[&str]) -> WorkflowDefinition {
+        WorkflowDefinition {
+            name: "Recovery Workflow".to_string(),
+            description: None,
+            nodes: node_ids
+                .iter()
+                .map(|id| NodeDefinition {
+                    id: id.to_string(),
+                    node_type: "transform".to_string(),
+                    name: id.to_string(),
+                    parameters: serde_json::json!({}),
+                    timeout_seconds: None,
+                    nodes: vec![],
+                    edges: vec![],
+                    retry: None,
+                    idempotent: true,
+                    pinned_output: None,
+                    disabled: false,
+                })
+                .collect(),
+            edges: vec![],
+            execution_mode: ExecutionMode::Sequential,
+            timeout_seconds: None,
+            max_concurrency: None,
+            max_concurrent_executions: None,
+            overlap_policy: None,
+            concurrency_key: None,
+            idempotency_window_seconds: None,
+            error_workflow: None,
+        }
+    }
+
+    fn finished(
+        execution_id: Uuid,
+        node_id: &str,
+        status: ExecutionStatus,
+        seconds: i64,
+    ) -> NodeExecution {
+        let mut node_execution = NodeExecution::new(execution_id, node_id.to_string());
+        node_execution.status = status;
+        node_execution.started_at += chrono::Duration::seconds(seconds);
+        node_execution.finished_at = Some(node_execution.started_at);
+        node_execution
+    }
+
+    #[test]
+    fn test_recover_graph_run() {
+        let workflow = workflow(&["a", "b", "c", "d"]);
+        let execution_id = Uuid::new_v4();
+
+        let mut a = finished(execution_id, "a", ExecutionStatus::Success, 0);
+        a.output_data = Some(serde_json::json!({"value": 1}));
+        a.output_port = Some("true".to_string());
+        a.variables = Some(serde_json::json!({"status": "first", "kept": 1}));
+
+        let b_failed = finished(execution_id, "b", ExecutionStatus::Failed, 1);
+        let mut b = finished(execution_id, "b", ExecutionStatus::Success, 2);
+        b.output_data = Some(serde_json::json!({"value": 2}));
+        b.variables = Some(serde_json::json!({"status": "second"}));
+
+        let c = finished(execution_id, "c", ExecutionStatus::Skipped, 3);
+        let mut d = NodeExecution::new(execution_id, "d".to_string());
+        d.started_at += chrono::Duration::seconds(4);
+
+        let node_executions = vec![a, b_failed, b, c, d];
+        let (run, interrupted) = GraphRun::recover(
+            &workflow,
+            Some(serde_json::json!({"order": 1})),
+            &node_executions,
+        );
+
+        assert_eq!(
+            run.handled,
+            HashSet::from(["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(run.node_outputs["b"], serde_json::json!({"value": 2}));
+        assert_eq!(run.node_ports["a"], Some("true".to_string()));
+        assert!(!run.node_ports.contains_key("c"));
+        assert_eq!(run.workflow_variables["status"], "second");
+        assert_eq!(run.workflow_variables["kept"], 1);
+        assert_eq!(
+            run.workflow_variables["input"],
+            serde_json::json!({"order": 1})
+        );
+        assert_eq!(run.last_executed.as_deref(), Some("b"));
+
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].node_id, "d");
+    }
+
+    #[test]
+    fn test_rerun_graph_run() {
+        let mut workflow = workflow(&["fetch", "summarize", "notify", "audit"]);
+        let edge = |from: &str, to: &str| EdgeDefinition {
+            from: from.to_string(),
+            to: to.to_string(),
+            from_output: String::new(),
+            to_input: String::new(),
+        };
+        workflow.edges = vec![edge("fetch", "summarize"), edge("summarize", "notify")];
+        let execution_id = Uuid::new_v4();
+
+        let mut fetch = finished(execution_id, "fetch", ExecutionStatus::Success, 0);
+        fetch.output_data = Some(serde_json::json!({"document": "..."}));
+        let mut audit = finished(execution_id, "audit", ExecutionStatus::Success, 1);
+        audit.variables = Some(serde_json::json!({"audited": true}));
+        let summarize = finished(execution_id, "summarize", ExecutionStatus::Success, 2);
+        let notify = finished(execution_id, "notify", ExecutionStatus::Failed, 3);
+
+        let node_executions = [fetch, audit, summarize, notify];
+        assert_eq!(
+            first_failed_node(&workflow, &node_executions).as_deref(),
+            Some("notify")
+        );
+
+        let (run, kept) =
+            GraphRun::rerun(&workflow, None, &node_executions, Some("summarize")).unwrap();
+        assert_eq!(
+            run.handled,
+            HashSet::from(["fetch".to_string(), "audit".to_string()])
+        );
+        assert_eq!(
+            run.node_outputs["fetch"],
+            serde_json::json!({"document": "..."})
+        );
+        assert_eq!(run.workflow_variables["audited"], true);
+        assert_eq!(kept.len(), 2);
+
+        let (run, kept) = GraphRun::rerun(&workflow, None, &node_executions, None).unwrap();
+        assert!(run.handled.is_empty());
+        assert!(kept.is_empty());
+
+        assert!(GraphRun::rerun(&workflow, None, &node_executions, Some("missing")).is_err());
+    }
+
+    #[test]
+    fn test_partial_graph_run() {
+        let mut workflow = workflow(&["start", "fetch", "summarize", "notify", "audit"]);
+        workflow.nodes[0].node_type = "start".to_string();
+        let edge = |from: &str, to: &str| EdgeDefinition {
+            from: from.to_string(),
+            to: to.to_string(),
+            from_output: String::new(),
+            to_input: String::new(),
+        };
+        workflow.edges = vec![
+            edge("start", "fetch"),
+            edge("fetch", "summarize"),
+            edge("summarize", "notify"),
+            edge("start", "audit"),
+        ];
+        let execution_id = Uuid::new_v4();
+        let only = PartialRun::Only("summarize".to_string());
+
+        // Upstream outputs come from the seed execution
+        let mut fetch = finished(execution_id, "fetch", ExecutionStatus::Success, 0);
+        fetch.output_data = Some(serde_json::json!({"document": "..."}));
+        let seed_executions = [fetch];
+        let (run, seeds) =
+            GraphRun::partial(&workflow, None, &seed_executions, &only, false).unwrap();
+        assert_eq!(
+            run.handled,
+            HashSet::from(["start", "fetch", "notify", "audit"].map(String::from))
+        );
+        assert_eq!(
+            run.node_outputs["fetch"],
+            serde_json::json!({"document": "..."})
+        );
+        assert_eq!(run.node_ports["fetch"], None);
+        assert!(!run.node_outputs.contains_key("audit"));
+        assert_eq!(seeds.len(), 2);
+
+        // ... or from pinned output, unless pins are ignored
+        assert!(GraphRun::partial(&workflow, None, &[], &only, false).is_err());
+        workflow.nodes[1].pinned_output = Some(serde_json::json!({"document": "pinned"}));
+        let (run, seeds) = GraphRun::partial(&workflow, None, &[], &only, false).unwrap();
+        assert_eq!(
+            run.node_outputs["fetch"],
+            serde_json::json!({"document": "pinned"})
+        );
+        assert!(
+            seeds
+                .iter()
+                .any(|seed| seed.status == ExecutionStatus::Pinned)
+        );
+        assert!(GraphRun::partial(&workflow, None, &[], &only, true).is_err());
+
+        // Running until a node runs its ancestors too
+        let until = PartialRun::Until("summarize".to_string());
+        let (run, seeds) = GraphRun::partial(&workflow, None, &[], &until, true).unwrap();
+        assert_eq!(
+            run.handled,
+            HashSet::from(["notify", "audit"].map(String::from))
+        );
+        assert!(seeds.is_empty());
+
+        let missing = PartialRun::Only("missing".to_string());
+        assert!(GraphRun::partial(&workflow, None, &[], &missing, false).is_err());
+    }
+
+    #[test]
+    fn test_recover_ignores_loop_iterations() {
+        let workflow = workflow(&["a"]);
+        let execution_id = Uuid::new_v4();
+
+        let iteration =
+            finished(execution_id, "a", ExecutionStatus::Success, 0).with_iteration(Some(0));
+        let node_executions = [iteration];
+        let (run, interrupted) = GraphRun::recover(&workflow, None, &node_executions);
+
+        assert!(run.handled.is_empty());
+        assert!(run.last_executed.is_none());
+        assert!(interrupted.is_empty());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::models::ExecutionStatus;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+/// Number of events kept for in-process subscribers that fall behind
+pub const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Progress of an execution, emitted by the engine as it runs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ExecutionEvent {
+    /// The engine started running an execution
+    ExecutionStarted {
+        execution_id: Uuid,
+        workflow_id: Uuid,
+    },
+    /// The engine stopped running an execution. The status is `waiting` when
+    /// the execution suspended at a wait node.
+    ExecutionFinished {
+        execution_id: Uuid,
+        workflow_id: Uuid,
+        status: ExecutionStatus,
+        error: Option<String>,
+    },
+    /// An attempt of a node started
+    NodeStarted {
+        execution_id: Uuid,
+        node_id: String,
+        attempt: i32,
+        /// Loop iteration or split branch index, for nodes of a nested body
+        iteration: Option<i32>,
+        /// Split branch key, for nodes of a split body
+        branch: Option<String>,
+    },
+    /// An attempt of a node succeeded
+    NodeFinished {
+        execution_id: Uuid,
+        node_id: String,
+        attempt: i32,
+        output: serde_json::Value,
+        port: Option<String>,
+    },
+    /// An attempt of a node failed
+    NodeFailed {
+        execution_id: Uuid,
+        node_id: String,
+        attempt: i32,
+        error: String,
+    },
+    /// A node did not run because none of its incoming branches was taken
+    NodeSkipped { execution_id: Uuid, node_id: String },
+    /// A failed node is retried after `delay_ms`
+    NodeRetried {
+        execution_id: Uuid,
+        node_id: String,
+        /// The attempt that is about to start
+        attempt: i32,
+        delay_ms: u64,
+    },
+    /// A node set workflow variables
+    VariablesChanged {
+        execution_id: Uuid,
+        node_id: String,
+        variables: HashMap<String, serde_json::Value>,
+    },
+}
+
+impl ExecutionEvent {
+    /// The execution the event belongs to
+    pub fn execution_id(&self) -> Uuid {
+        match self {
+            ExecutionEvent::ExecutionStarted { execution_id, .. }
+            | ExecutionEvent::ExecutionFinished { execution_id, .. }
+            | ExecutionEvent::NodeStarted { execution_id, .. }
+            | ExecutionEvent::NodeFinished { execution_id, .. }
+            | ExecutionEvent::NodeFailed { execution_id, .. }
+            | ExecutionEvent::NodeSkipped { execution_id, .. }
+            | ExecutionEvent::NodeRetried { execution_id, .. }
+            | ExecutionEvent::VariablesChanged { execution_id, .. } => *execution_id,
+        }
+    }
+}
+
+/// Hook called by the engine for every [`ExecutionEvent`].
+///
+/// Observers run inline on the engine's task, so they should return quickly
+/// and hand slow work (network calls, database writes) to a task of their own.
+pub trait ExecutionObserver: Send + Sync {
+    fn on_event(&self, event: &ExecutionEvent);
+}
+
+/// Delivers engine events to the registered observers and to the
+/// subscribers of a broadcast channel
+#[derive(Clone)]
+pub struct EventBus {
+    observers: Vec<Arc<dyn ExecutionObserver>>,
+    sender: broadcast::Sender<ExecutionEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            observers: Vec::new(),
+            sender,
+        }
+    }
+
+    /// Call `observer` for every event
+    pub fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Receive the events emitted from now on.
+    ///
+    /// A receiver that falls more than [`EVENT_CHANNEL_CAPACITY`] events
+    /// behind loses the oldest ones.
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Deliver an event to every observer and subscriber
+    pub fn emit(&self, event: ExecutionEvent) {
+        for observer in &self.observers {
+            observer.on_event(&event);
+        }
+
+        // Sending only fails when nobody is subscribed
+        let _ = self.sender.send(event);
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<ExecutionEvent>>,
+    }
+
+    impl ExecutionObserver for Recorder {
+        fn on_event(&self, event: &ExecutionEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_event_bus_delivers_to_observers_and_subscribers() {
+        let recorder = Arc::new(Recorder::default());
+        let bus = EventBus::new().with_observer(recorder.clone());
+        let mut receiver = bus.subscribe();
+
+        let execution_id = Uuid::new_v4();
+        let event = ExecutionEvent::NodeSkipped {
+            execution_id,
+            node_id: "low".to_string(),
+        };
+        bus.emit(event.clone());
+
+        assert_eq!(*recorder.events.lock().unwrap(), vec![event.clone()]);
+        assert_eq!(receiver.recv().await.unwrap(), event);
+        assert_eq!(event.execution_id(), execution_id);
+    }
+
+    #[test]
+    fn test_emit_without_subscribers() {
+        let bus = EventBus::new();
+        bus.emit(ExecutionEvent::ExecutionStarted {
+            execution_id: Uuid::new_v4(),
+            workflow_id: Uuid::new_v4(),
+        });
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = ExecutionEvent::NodeRetried {
+            execution_id: Uuid::nil(),
+            node_id: "fetch".to_string(),
+            attempt: 2,
+            delay_ms: 500,
+        };
+
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["event"], "node_retried");
+        assert_eq!(value["node_id"], "fetch");
+        assert_eq!(value["attempt"], 2);
+    }
+}
@@ -1,20 +1,90 @@
 use crate::db;
-use crate::execution::WorkflowEngine;
-use crate::models::{NodeRegistry, WorkflowExecution};
+use crate::execution::{
+    CircuitStore, ExecutionEvent, ExecutionObserver, STALE_EXECUTION_AFTER, WorkflowEngine,
+    first_failed_node,
+};
+use crate::models::{
+    ExecutionStatus, ExecutionWait, NodeRegistry, OverlapPolicy, PartialRun, WorkflowDefinition,
+    WorkflowExecution,
+};
 use anyhow::Result;
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
+use std::sync::Arc;
+use tokio::sync::{Semaphore, broadcast};
 use uuid::Uuid;
 
+/// An execution queued by [`WorkflowExecutor::enqueue_by_id`] or
+/// [`WorkflowExecutor::enqueue_by_name`]
+#[derive(Debug, Clone)]
+pub struct QueuedExecution {
+    pub execution: WorkflowExecution,
+    /// Whether an earlier execution with the same idempotency key was
+    /// returned instead of queueing a new one
+    pub duplicate: bool,
+}
+
+/// Outcome of [`WorkflowExecutor::enqueue_resume`]
+#[derive(Debug, Clone)]
+pub enum ResumedWait {
+    /// The execution was queued for a worker to continue it
+    Queued(WorkflowExecution),
+    /// The wait expired before it was resumed, so the execution failed
+    Expired(WorkflowExecution),
+}
+
 /// High-level executor for running workflows
+#[derive(Clone)]
 pub struct WorkflowExecutor {
     engine: WorkflowEngine,
     pool: PgPool,
+    /// Whether executions started by the executor ignore pinned node output
+    ignore_pinned_data: bool,
+    /// Whether executions started by the executor mock side-effecting nodes
+    dry_run: bool,
 }
 
 impl WorkflowExecutor {
     pub fn new(pool: PgPool, registry: NodeRegistry) -> Self {
         let engine = WorkflowEngine::new(pool.clone(), registry);
-        Self { engine, pool }
+        Self {
+            engine,
+            pool,
+            ignore_pinned_data: false,
+            dry_run: false,
+        }
+    }
+
+    /// Keep circuit breaker state in the given store instead of Postgres
+    pub fn with_circuit_store(mut self, circuits: CircuitStore) -> Self {
+        self.engine = self.engine.with_circuit_store(circuits);
+        self
+    }
+
+    /// Run every node of the executions the executor starts for real,
+    /// ignoring pinned output, e.g. for production triggers
+    pub fn without_pinned_data(mut self) -> Self {
+        self.ignore_pinned_data = true;
+        self
+    }
+
+    /// Make the executions the executor starts dry runs, which mock the
+    /// nodes with external side effects
+    pub fn as_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Call `observer` for every event of every execution the executor runs
+    pub fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.engine = self.engine.with_observer(observer);
+        self
+    }
+
+    /// Receive the events of every execution the executor runs from now on.
+    ///
+    /// Clones of the executor share the same channel.
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.engine.subscribe()
     }
 
     /// Execute a workflow by ID
@@ -30,9 +100,7 @@ impl WorkflowExecutor {
         let definition = workflow.to_definition()?;
 
         // Execute
-        self.engine
-            .execute_workflow(&definition, workflow_id, input_data)
-            .await
+        self.start(&definition, workflow_id, input_data).await
     }
 
     /// Execute a workflow by name
@@ -48,13 +116,525 @@ impl WorkflowExecutor {
         let definition = workflow.to_definition()?;
 
         // Execute
-        self.engine
-            .execute_workflow(&definition, workflow.id, input_data)
+        self.start(&definition, workflow.id, input_data).await
+    }
+
+    /// Run a new execution of a workflow, unless the workflow already runs
+    /// its maximum number of concurrent executions.
+    ///
+    /// Over the limit, the execution is queued or skipped according to the
+    /// workflow's overlap policy.
+    async fn start(
+        &self,
+        definition: &WorkflowDefinition,
+        workflow_id: Uuid,
+        input_data: Option<serde_json::Value>,
+    ) -> Result<WorkflowExecution> {
+        let mut tx = self.pool.begin().await?;
+
+        match admit(&mut tx, workflow_id, definition, false).await? {
+            Overlap::Start => {
+                let execution = self.configure(WorkflowExecution::new(workflow_id, input_data));
+                let execution = db::create_workflow_execution(&mut *tx, &execution).await?;
+                tx.commit().await?;
+
+                self.engine.run_execution(definition, execution).await
+            }
+            Overlap::Wait => {
+                let execution = self.configure(WorkflowExecution::pending(workflow_id, input_data));
+                let execution = db::enqueue_execution(&mut *tx, &execution).await?;
+                tx.commit().await?;
+
+                tracing::info!(
+                    "Queued execution {}: workflow {} is at its execution limit",
+                    execution.id,
+                    workflow_id
+                );
+                Ok(execution)
+            }
+            Overlap::Skip(reason) => {
+                let execution =
+                    self.configure(WorkflowExecution::skipped(workflow_id, input_data, reason));
+                let execution = db::create_workflow_execution(&mut *tx, &execution).await?;
+                tx.commit().await?;
+
+                tracing::info!("Skipped execution {}", execution.id);
+                Ok(execution)
+            }
+        }
+    }
+
+    /// Queue an execution of a workflow by ID for a worker to run.
+    ///
+    /// With an idempotency key, an execution queued earlier with the same key
+    /// is returned instead while its retention window lasts.
+    pub async fn enqueue_by_id(
+        &self,
+        workflow_id: Uuid,
+        input_data: Option<serde_json::Value>,
+        idempotency_key: Option<String>,
+    ) -> Result<QueuedExecution> {
+        let workflow = db::get_workflow(&self.pool, workflow_id).await?;
+        let definition = workflow.to_definition()?;
+
+        self.enqueue(&definition, workflow.id, input_data, idempotency_key)
             .await
     }
 
+    /// Queue an execution of a workflow by name for a worker to run.
+    ///
+    /// With an idempotency key, an execution queued earlier with the same key
+    /// is returned instead while its retention window lasts.
+    pub async fn enqueue_by_name(
+        &self,
+        workflow_name: &str,
+        input_data: Option<serde_json::Value>,
+        idempotency_key: Option<String>,
+    ) -> Result<QueuedExecution> {
+        let workflow = db::get_workflow_by_name(&self.pool, workflow_name).await?;
+        let definition = workflow.to_definition()?;
+
+        self.enqueue(&definition, workflow.id, input_data, idempotency_key)
+            .await
+    }
+
+    async fn enqueue(
+        &self,
+        definition: &WorkflowDefinition,
+        workflow_id: Uuid,
+        input_data: Option<serde_json::Value>,
+        idempotency_key: Option<String>,
+    ) -> Result<QueuedExecution> {
+        let execution = self.configure(WorkflowExecution::pending(workflow_id, input_data));
+        let Some(key) = idempotency_key else {
+            let execution = db::enqueue_execution(&self.pool, &execution).await?;
+            return Ok(QueuedExecution {
+                execution,
+                duplicate: false,
+            });
+        };
+
+        let mut tx = self.pool.begin().await?;
+        db::lock_idempotency_key(&mut tx, workflow_id, &key).await?;
+
+        if let Some(existing) =
+            db::find_execution_by_idempotency_key(&mut tx, workflow_id, &key).await?
+        {
+            tx.commit().await?;
+
+            tracing::info!(
+                "Idempotency key {:?} matches execution {} of workflow {}",
+                key,
+                existing.id,
+                workflow_id
+            );
+            return Ok(QueuedExecution {
+                execution: existing,
+                duplicate: true,
+            });
+        }
+
+        let expires_at = chrono::Utc::now() + definition.idempotency_window();
+        let execution = execution.with_idempotency_key(key, expires_at);
+        let execution = db::enqueue_execution(&mut *tx, &execution).await?;
+        tx.commit().await?;
+
+        Ok(QueuedExecution {
+            execution,
+            duplicate: false,
+        })
+    }
+
+    /// Queue an execution that runs only part of a workflow for a worker to
+    /// run.
+    ///
+    /// The nodes the part depends on get their output in `seed_execution_id`,
+    /// or their pinned output.
+    pub async fn enqueue_partial(
+        &self,
+        workflow_id: Uuid,
+        input_data: Option<serde_json::Value>,
+        partial: PartialRun,
+        seed_execution_id: Option<Uuid>,
+    ) -> Result<WorkflowExecution> {
+        let workflow = db::get_workflow(&self.pool, workflow_id).await?;
+        let definition = workflow.to_definition()?;
+        if !definition
+            .nodes
+            .iter()
+            .any(|node| node.id == partial.node_id())
+        {
+            anyhow::bail!(
+                "Node {} is not a top-level node of workflow {}",
+                partial.node_id(),
+                definition.name
+            );
+        }
+
+        let execution = self.configure(
+            WorkflowExecution::pending(workflow.id, input_data)
+                .with_partial_run(partial, seed_execution_id),
+        );
+        db::enqueue_execution(&self.pool, &execution).await
+    }
+
+    /// Queue a re-run of a finished execution for a worker to run.
+    ///
+    /// The re-run starts from `from_node`, or from the node where the
+    /// execution failed when no node is given; nodes that do not depend on it
+    /// keep the results of the original execution. Executions that did not
+    /// fail are re-run from the start. Without `input_data` the re-run gets
+    /// the input of the original execution.
+    pub async fn enqueue_rerun(
+        &self,
+        execution_id: Uuid,
+        from_node: Option<String>,
+        input_data: Option<serde_json::Value>,
+    ) -> Result<WorkflowExecution> {
+        let original = db::get_workflow_execution(&self.pool, execution_id).await?;
+        if !original.status.is_finished() {
+            anyhow::bail!(
+                "Execution {} is {}; only finished executions can be re-run",
+                execution_id,
+                original.status
+            );
+        }
+
+        let workflow = db::get_workflow(&self.pool, original.workflow_id).await?;
+        let definition = workflow.to_definition()?;
+
+        let from_node = match from_node {
+            Some(node_id) => {
+                if !definition.nodes.iter().any(|node| node.id == node_id) {
+                    anyhow::bail!(
+                        "Node {} is not a top-level node of workflow {}",
+                        node_id,
+                        definition.name
+                    );
+                }
+                Some(node_id)
+            }
+            None => {
+                let node_executions = db::list_node_executions(&self.pool, execution_id).await?;
+                first_failed_node(&definition, &node_executions)
+            }
+        };
+
+        let input_data = input_data.or(original.input_data);
+        let execution = self.configure(
+            WorkflowExecution::pending(original.workflow_id, input_data)
+                .with_rerun_of(execution_id, from_node),
+        );
+        db::enqueue_execution(&self.pool, &execution).await
+    }
+
+    /// Take the oldest queued execution that may start and run it; returns
+    /// `None` when no queued execution may start.
+    ///
+    /// Executions of workflows at their execution limit stay queued, or are
+    /// skipped according to the workflow's overlap policy.
+    pub async fn run_next(&self) -> Result<Option<WorkflowExecution>> {
+        // Executions that wait for running executions of their workflow
+        let mut deferred = Vec::new();
+
+        loop {
+            let mut tx = self.pool.begin().await?;
+            let Some(execution) = db::next_queued_execution(&mut tx, &deferred).await? else {
+                return Ok(None);
+            };
+
+            let definition = match db::get_workflow(&mut *tx, execution.workflow_id)
+                .await
+                .and_then(|workflow| workflow.to_definition())
+            {
+                Ok(definition) => definition,
+                Err(e) => {
+                    let execution = db::finish_queued_execution(
+                        &mut tx,
+                        execution.id,
+                        ExecutionStatus::Failed,
+                        format!("Failed to load workflow: {:#}", e),
+                    )
+                    .await?;
+                    tx.commit().await?;
+                    return Ok(Some(execution));
+                }
+            };
+
+            // Executions resumed from a wait are admitted again, so they do
+            // not overlap executions started while they were waiting
+            let resuming = db::has_resumed_execution_wait(&mut *tx, execution.id).await?;
+            match admit(&mut tx, execution.workflow_id, &definition, resuming).await? {
+                Overlap::Start => {
+                    let wait = db::take_resumed_execution_wait(&mut *tx, execution.id).await?;
+                    let execution = db::start_queued_execution(&mut tx, execution.id).await?;
+                    tx.commit().await?;
+
+                    let execution = match wait {
+                        Some(wait) => {
+                            self.engine
+                                .resume_workflow(&definition, execution, wait)
+                                .await?
+                        }
+                        None => self.engine.run_execution(&definition, execution).await?,
+                    };
+                    return Ok(Some(execution));
+                }
+                Overlap::Wait => {
+                    // Keeps the cancellations requested for `cancel_previous`
+                    tx.commit().await?;
+                    deferred.push(execution.id);
+                }
+                Overlap::Skip(reason) => {
+                    let execution = db::finish_queued_execution(
+                        &mut tx,
+                        execution.id,
+                        ExecutionStatus::Skipped,
+                        reason,
+                    )
+                    .await?;
+                    tx.commit().await?;
+                    return Ok(Some(execution));
+                }
+            }
+        }
+    }
+
+    /// Limit the number of nodes running at the same time across every
+    /// executor sharing `slots`
+    pub fn with_node_slots(mut self, slots: Arc<Semaphore>) -> Self {
+        self.engine = self.engine.with_node_slots(slots);
+        self
+    }
+
+    /// Apply the settings of the executor to an execution it starts
+    fn configure(&self, mut execution: WorkflowExecution) -> WorkflowExecution {
+        execution.ignore_pinned_data |= self.ignore_pinned_data;
+        execution.dry_run |= self.dry_run;
+        execution
+    }
+
+    /// Resume the execution suspended at a wait with the given payload;
+    /// returns `None` when nothing waits on `wait_id`.
+    ///
+    /// The wait node completes with the payload as its output and the
+    /// execution is queued for a worker to run the remaining nodes. Both
+    /// happen in one transaction, so a resumed execution is never lost.
+    pub async fn enqueue_resume(
+        &self,
+        wait_id: &str,
+        payload: serde_json::Value,
+    ) -> Result<Option<ResumedWait>> {
+        let mut tx = self.pool.begin().await?;
+        let Some(wait) = db::lock_execution_wait(&mut tx, wait_id).await? else {
+            return Ok(None);
+        };
+
+        if wait.is_expired(chrono::Utc::now()) {
+            tx.rollback().await?;
+            let execution = self.engine.expire_wait(&wait).await?;
+            return Ok(Some(ResumedWait::Expired(execution)));
+        }
+
+        db::update_waiting_node_execution(
+            &mut *tx,
+            wait.execution_id,
+            &wait.node_id,
+            ExecutionStatus::Success,
+            Some(payload.clone()),
+            None,
+        )
+        .await?;
+        db::resume_execution_wait(&mut *tx, wait_id, &payload).await?;
+        let execution = db::requeue_waiting_execution(&mut tx, wait.execution_id).await?;
+        tx.commit().await?;
+
+        tracing::info!(
+            "Queued execution {} to resume from wait {}",
+            execution.id,
+            wait_id
+        );
+        Ok(Some(ResumedWait::Queued(execution)))
+    }
+
+    /// Fail an execution whose wait expired
+    pub async fn expire_wait(&self, wait: &ExecutionWait) -> Result<WorkflowExecution> {
+        self.engine.expire_wait(wait).await
+    }
+
+    /// Fail all executions whose waits expired; returns how many were expired
+    pub async fn expire_waits(&self) -> Result<usize> {
+        let waits = db::list_expired_execution_waits(&self.pool).await?;
+        for wait in &waits {
+            self.engine.expire_wait(wait).await?;
+        }
+
+        Ok(waits.len())
+    }
+
+    /// IDs of running executions whose process stopped reporting heartbeats
+    pub async fn interrupted_executions(&self) -> Result<Vec<Uuid>> {
+        let executions = db::list_stale_executions(&self.pool, STALE_EXECUTION_AFTER).await?;
+        Ok(executions
+            .into_iter()
+            .map(|execution| execution.id)
+            .collect())
+    }
+
+    /// Continue an interrupted execution from its last completed node
+    pub async fn recover(&self, execution_id: Uuid) -> Result<WorkflowExecution> {
+        let Some(execution) =
+            db::claim_stale_execution(&self.pool, execution_id, STALE_EXECUTION_AFTER).await?
+        else {
+            anyhow::bail!(
+                "Execution {} is not running or was active in the last {} seconds",
+                execution_id,
+                STALE_EXECUTION_AFTER.as_secs()
+            );
+        };
+
+        let workflow = db::get_workflow(&self.pool, execution.workflow_id).await?;
+        let definition = workflow.to_definition()?;
+
+        self.engine.recover_execution(&definition, execution).await
+    }
+
+    /// Cancel a running or waiting execution
+    pub async fn cancel(&self, execution_id: Uuid) -> Result<WorkflowExecution> {
+        self.engine.cancel_execution(execution_id).await
+    }
+
     /// Get execution result
     pub async fn get_execution(&self, execution_id: Uuid) -> Result<WorkflowExecution> {
         db::get_workflow_execution(&self.pool, execution_id).await
     }
 }
+
+/// Whether a new execution of a workflow may start now
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Overlap {
+    Start,
+    /// Queue the execution until a running one finishes
+    Wait,
+    /// Do not run the execution, for the given reason
+    Skip(String),
+}
+
+/// Decide whether an execution of a workflow may start, given the
+/// executions already running under its concurrency key.
+///
+/// Takes the lock of the concurrency key for the transaction of `conn`, so
+/// the execution must be started in the same transaction. An execution
+/// `resuming` from a wait is admitted again before it continues.
+pub(crate) async fn admit(
+    conn: &mut PgConnection,
+    workflow_id: Uuid,
+    definition: &WorkflowDefinition,
+    resuming: bool,
+) -> Result<Overlap> {
+    let Some((limit, policy)) = definition.execution_limit() else {
+        return Ok(Overlap::Start);
+    };
+    let key = definition
+        .concurrency_key
+        .clone()
+        .unwrap_or_else(|| workflow_id.to_string());
+
+    db::lock_concurrency_key(conn, &key).await?;
+    let running = db::list_running_executions(conn, &key).await?;
+
+    let (overlap, cancelled) = overlap(&running, limit, policy, &key, resuming);
+    for execution_id in cancelled {
+        tracing::info!(
+            "Cancelling execution {} to make room under concurrency key {}",
+            execution_id,
+            key
+        );
+        db::request_execution_cancel(&mut *conn, *execution_id).await?;
+    }
+
+    Ok(overlap)
+}
+
+/// How an execution overlaps the `running` executions under its concurrency
+/// key, oldest first, and which of them to cancel to make room.
+///
+/// An execution resuming from a wait already started once. It is never
+/// skipped and does not cancel others; it waits for a free slot instead.
+fn overlap<'a>(
+    running: &'a [Uuid],
+    limit: usize,
+    policy: OverlapPolicy,
+    key: &str,
+    resuming: bool,
+) -> (Overlap, &'a [Uuid]) {
+    if running.len() < limit || policy == OverlapPolicy::Allow {
+        return (Overlap::Start, &[]);
+    }
+    if resuming {
+        return (Overlap::Wait, &[]);
+    }
+
+    match policy {
+        OverlapPolicy::Skip => (
+            Overlap::Skip(format!(
+                "Skipped: {} executions with concurrency key {} were already running",
+                running.len(),
+                key
+            )),
+            &[],
+        ),
+        // Cancel the oldest executions to make room; this one starts once they stopped
+        OverlapPolicy::CancelPrevious => (Overlap::Wait, &running[..=running.len() - limit]),
+        OverlapPolicy::Queue | OverlapPolicy::Allow => (Overlap::Wait, &[]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlap() {
+        let running = [Uuid::new_v4(), Uuid::new_v4()];
+
+        assert_eq!(
+            overlap(&running, 3, OverlapPolicy::Skip, "key", false),
+            (Overlap::Start, &[][..])
+        );
+        assert_eq!(
+            overlap(&running, 2, OverlapPolicy::Queue, "key", false),
+            (Overlap::Wait, &[][..])
+        );
+        assert!(matches!(
+            overlap(&running, 2, OverlapPolicy::Skip, "key", false),
+            (Overlap::Skip(_), [])
+        ));
+        assert_eq!(
+            overlap(&running, 2, OverlapPolicy::CancelPrevious, "key", false),
+            (Overlap::Wait, &running[..1])
+        );
+    }
+
+    #[test]
+    fn test_resuming_execution_waits_for_overlap() {
+        let running = [Uuid::new_v4()];
+
+        // A resumed execution continues only when no other execution runs
+        // under its key, whatever the overlap policy
+        for policy in [
+            OverlapPolicy::Queue,
+            OverlapPolicy::Skip,
+            OverlapPolicy::CancelPrevious,
+        ] {
+            assert_eq!(
+                overlap(&running, 1, policy, "key", true),
+                (Overlap::Wait, &[][..])
+            );
+            assert_eq!(
+                overlap(&[], 1, policy, "key", true),
+                (Overlap::Start, &[][..])
+            );
+        }
+    }
+}
@@ -0,0 +1,758 @@
+use anyhow::{Context, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Roots that mark a `{{ ... }}` template as an engine expression.
+///
+/// Templates that reference none of these (e.g. `{{user.id}}` in `transform`)
+/// are left untouched so nodes can keep resolving them against their own input.
+const ROOTS: [&str; 4] = ["$input", "$node", "$vars", "$execution"];
+
+/// Data an expression can reference
+pub struct ExpressionScope<'a> {
+    /// `$input`: the main input of the node
+    pub input: Value,
+    /// `$node["id"]`: outputs of nodes that already ran
+    pub nodes: &'a HashMap<String, Value>,
+    /// `$vars`: workflow variables
+    pub variables: &'a HashMap<String, Value>,
+    /// `$execution`: metadata about the running execution
+    pub execution: Value,
+}
+
+/// Resolve every engine expression in a node's parameters.
+///
+/// A string that consists of a single template keeps the type of the
+/// expression result; templates embedded in a longer string are interpolated
+/// as text. Object keys are never resolved.
+pub fn resolve_parameters(parameters: &Value, scope: &ExpressionScope) -> Result<Value> {
+    match parameters {
+        Value::String(s) => resolve_string(s, scope),
+        Value::Object(obj) => {
+            let mut result = serde_json::Map::new();
+            for (key, value) in obj {
+                result.insert(key.clone(), resolve_parameters(value, scope)?);
+            }
+            Ok(Value::Object(result))
+        }
+        Value::Array(arr) => arr
+            .iter()
+            .map(|value| resolve_parameters(value, scope))
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Evaluate a single expression (without the surrounding braces)
+pub fn evaluate(expression: &str, scope: &ExpressionScope) -> Result<Value> {
+    let tokens = tokenize(expression)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.parse_expr()?;
+    if parser.pos != parser.tokens.len() {
+        anyhow::bail!("Unexpected trailing input in expression: {}", expression);
+    }
+    eval(&expr, scope).with_context(|| format!("Failed to evaluate expression: {}", expression))
+}
+
+/// Look up a path such as `user.name`, `items[0].id` or `items.0.id` in a value
+pub fn lookup_path<'a>(data: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = data;
+
+    for part in path.split('.') {
+        if part.is_empty() {
+            continue;
+        }
+
+        // Split "items[0][1]" into the field name and its indexes
+        let (field, indexes) = match part.find('[') {
+            Some(pos) => (&part[..pos], &part[pos..]),
+            None => (part, ""),
+        };
+
+        if !field.is_empty() {
+            current = match current {
+                Value::Array(arr) => arr.get(field.parse::<usize>().ok()?)?,
+                other => other.get(field)?,
+            };
+        }
+
+        for index in indexes.split('[').skip(1) {
+            let index = index.strip_suffix(']')?;
+            current = current.get(index.parse::<usize>().ok()?)?;
+        }
+    }
+
+    Some(current)
+}
+
+fn resolve_string(s: &str, scope: &ExpressionScope) -> Result<Value> {
+    let templates = find_templates(s);
+    if templates.is_empty() {
+        return Ok(Value::String(s.to_string()));
+    }
+
+    // A string made of a single template keeps the result's type
+    if let [(start, end, expr)] = templates.as_slice()
+        && *start == 0
+        && *end == s.len()
+    {
+        return evaluate(expr, scope);
+    }
+
+    let mut result = String::new();
+    let mut last = 0;
+    for (start, end, expr) in templates {
+        result.push_str(&s[last..start]);
+        result.push_str(&to_text(&evaluate(expr, scope)?));
+        last = end;
+    }
+    result.push_str(&s[last..]);
+
+    Ok(Value::String(result))
+}
+
+/// Find engine templates in a string as (start, end, expression) triples
+fn find_templates(s: &str) -> Vec<(usize, usize, &str)> {
+    let mut templates = Vec::new();
+    let mut offset = 0;
+
+    while let Some(open) = s[offset..].find("{{") {
+        let start = offset + open;
+        let Some(close) = s[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + close + 2;
+        let expr = s[start + 2..end - 2].trim();
+
+        if references_root(expr) {
+            templates.push((start, end, expr));
+        }
+        offset = end;
+    }
+
+    templates
+}
+
+fn references_root(expr: &str) -> bool {
+    ROOTS.iter().any(|root| {
+        expr.match_indices(root).any(|(pos, _)| {
+            !expr[pos + root.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    })
+}
+
+/// Render a value for string interpolation
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Number(f64),
+    Str(String),
+    Dot,
+    Comma,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            ',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '[' => {
+                tokens.push(Token::LBracket);
+                i += 1;
+            }
+            ']' => {
+                tokens.push(Token::RBracket);
+                i += 1;
+            }
+            '"' | '\'' => {
+                let mut value = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => anyhow::bail!("Unterminated string in expression: {}", input),
+                        Some(&q) if q == c => break,
+                        Some('\\') => {
+                            let escaped = chars.get(i + 1).copied().unwrap_or('\\');
+                            value.push(match escaped {
+                                'n' => '\n',
+                                't' => '\t',
+                                other => other,
+                            });
+                            i += 2;
+                        }
+                        Some(&other) => {
+                            value.push(other);
+                            i += 1;
+                        }
+                    }
+                }
+                i += 1;
+                tokens.push(Token::Str(value));
+            }
+            c if c.is_ascii_digit()
+                || (c == '-' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) =>
+            {
+                let start = i;
+                i += 1;
+                // A dot only continues the number when a digit follows (`items.0.id`)
+                while i < chars.len()
+                    && (chars[i].is_ascii_digit()
+                        || (chars[i] == '.'
+                            && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())))
+                {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid number in expression: {}", text))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            other => anyhow::bail!("Unexpected character '{}' in expression: {}", other, input),
+        }
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+enum Expr {
+    Literal(Value),
+    Path(String, Vec<Segment>),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug)]
+enum Segment {
+    Field(String),
+    Index(Expr),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            other => anyhow::bail!("Expected {:?}, found {:?}", expected, other),
+        }
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Literal(number(n))),
+            Some(Token::Str(s)) => Ok(Expr::Literal(Value::String(s))),
+            Some(Token::Ident(name)) if name.starts_with('$') => {
+                let mut segments = Vec::new();
+                loop {
+                    match self.peek() {
+                        Some(Token::Dot) => {
+                            self.pos += 1;
+                            match self.next() {
+                                Some(Token::Ident(field)) => segments.push(Segment::Field(field)),
+                                Some(Token::Number(n)) if n.fract() == 0.0 => {
+                                    segments.push(Segment::Index(Expr::Literal(number(n))))
+                                }
+                                other => anyhow::bail!("Expected field name, found {:?}", other),
+                            }
+                        }
+                        Some(Token::LBracket) => {
+                            self.pos += 1;
+                            segments.push(Segment::Index(self.parse_expr()?));
+                            self.expect(Token::RBracket)?;
+                        }
+                        _ => break,
+                    }
+                }
+                Ok(Expr::Path(name, segments))
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true" => Ok(Expr::Literal(Value::Bool(true))),
+                "false" => Ok(Expr::Literal(Value::Bool(false))),
+                "null" => Ok(Expr::Literal(Value::Null)),
+                _ => {
+                    self.expect(Token::LParen)?;
+                    let mut args = Vec::new();
+                    if self.peek() != Some(&Token::RParen) {
+                        loop {
+                            args.push(self.parse_expr()?);
+                            if self.peek() == Some(&Token::Comma) {
+                                self.pos += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                    }
+                    self.expect(Token::RParen)?;
+                    Ok(Expr::Call(name, args))
+                }
+            },
+            other => anyhow::bail!("Unexpected token {:?}", other),
+        }
+    }
+}
+
+fn eval(expr: &Expr, scope: &ExpressionScope) -> Result<Value> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Path(root, segments) => eval_path(root, segments, scope),
+        Expr::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, scope))
+                .collect::<Result<Vec<_>>>()?;
+            call_function(name, &args)
+        }
+    }
+}
+
+/// Walk a path from its root; missing fields resolve to null
+fn eval_path(root: &str, segments: &[Segment], scope: &ExpressionScope) -> Result<Value> {
+    let keys = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Field(field) => Ok(Value::String(field.clone())),
+            Segment::Index(expr) => eval(expr, scope),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let (mut current, rest) = match root {
+        "$input" => (Some(&scope.input), keys.as_slice()),
+        "$execution" => (Some(&scope.execution), keys.as_slice()),
+        "$node" => match keys.split_first() {
+            Some((Value::String(id), rest)) => (scope.nodes.get(id), rest),
+            _ => anyhow::bail!("$node must be followed by a node id, e.g. $node[\"fetch\"]"),
+        },
+        "$vars" => match keys.split_first() {
+            Some((Value::String(name), rest)) => (scope.variables.get(name), rest),
+            Some(_) => anyhow::bail!("$vars must be followed by a variable name"),
+            None => {
+                return Ok(serde_json::to_value(scope.variables)?);
+            }
+        },
+        other => anyhow::bail!("Unknown expression root: {}", other),
+    };
+
+    for key in rest {
+        current = current.and_then(|value| match (value, key) {
+            (Value::Array(arr), Value::Number(n)) => arr.get(n.as_u64()? as usize),
+            (Value::Array(arr), Value::String(s)) => arr.get(s.parse::<usize>().ok()?),
+            (Value::Object(obj), Value::String(s)) => obj.get(s),
+            _ => None,
+        });
+    }
+
+    Ok(current.cloned().unwrap_or(Value::Null))
+}
+
+/// Convert an f64 result into a JSON number, keeping integers integral
+fn number(n: f64) -> Value {
+    if n.fract() == 0.0 && n.abs() < 9_007_199_254_740_992.0 {
+        Value::from(n as i64)
+    } else {
+        serde_json::Number::from_f64(n)
+            .map(Value::Number)
+            .unwrap_or(Value::Null)
+    }
+}
+
+fn as_number(name: &str, value: &Value) -> Result<f64> {
+    match value {
+        Value::Number(n) => n
+            .as_f64()
+            .ok_or_else(|| anyhow::anyhow!("{}: invalid number", name)),
+        Value::String(s) => s
+            .trim()
+            .parse::<f64>()
+            .with_context(|| format!("{}: '{}' is not a number", name, s)),
+        Value::Bool(b) => Ok(if *b { 1.0 } else { 0.0 }),
+        Value::Null => Ok(0.0),
+        other => anyhow::bail!("{}: expected a number, got {}", name, other),
+    }
+}
+
+fn as_date(name: &str, value: &Value) -> Result<DateTime<Utc>> {
+    match value {
+        Value::String(s) => {
+            if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+                return Ok(date.with_timezone(&Utc));
+            }
+            if let Ok(date) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+                return Ok(date.and_utc());
+            }
+            let date = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .with_context(|| format!("{}: '{}' is not a date", name, s))?;
+            Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+        }
+        Value::Number(n) => DateTime::from_timestamp(n.as_i64().unwrap_or_default(), 0)
+            .ok_or_else(|| anyhow::anyhow!("{}: invalid timestamp {}", name, n)),
+        other => anyhow::bail!("{}: expected a date, got {}", name, other),
+    }
+}
+
+fn arg<'a>(name: &str, args: &'a [Value], index: usize) -> Result<&'a Value> {
+    args.get(index)
+        .ok_or_else(|| anyhow::anyhow!("{} expects at least {} argument(s)", name, index + 1))
+}
+
+fn str_arg(name: &str, args: &[Value], index: usize) -> Result<String> {
+    Ok(to_text(arg(name, args, index)?))
+}
+
+fn num_arg(name: &str, args: &[Value], index: usize) -> Result<f64> {
+    as_number(name, arg(name, args, index)?)
+}
+
+/// Built-in function library
+fn call_function(name: &str, args: &[Value]) -> Result<Value> {
+    let value = match name {
+        // String functions
+        "upper" => Value::String(str_arg(name, args, 0)?.to_uppercase()),
+        "lower" => Value::String(str_arg(name, args, 0)?.to_lowercase()),
+        "trim" => Value::String(str_arg(name, args, 0)?.trim().to_string()),
+        "concat" => Value::String(args.iter().map(to_text).collect()),
+        "replace" => Value::String(
+            str_arg(name, args, 0)?.replace(&str_arg(name, args, 1)?, &str_arg(name, args, 2)?),
+        ),
+        "split" => {
+            let s = str_arg(name, args, 0)?;
+            let separator = str_arg(name, args, 1)?;
+            Value::Array(
+                s.split(separator.as_str())
+                    .map(|part| Value::String(part.to_string()))
+                    .collect(),
+            )
+        }
+        "join" => {
+            let items = arg(name, args, 0)?
+                .as_array()
+                .ok_or_else(|| anyhow::anyhow!("join expects an array"))?;
+            let separator = args.get(1).map(to_text).unwrap_or_default();
+            Value::String(
+                items
+                    .iter()
+                    .map(to_text)
+                    .collect::<Vec<_>>()
+                    .join(&separator),
+            )
+        }
+        "substring" => {
+            let chars: Vec<char> = str_arg(name, args, 0)?.chars().collect();
+            let start = (num_arg(name, args, 1)?.max(0.0) as usize).min(chars.len());
+            let end = match args.get(2) {
+                Some(end) => (as_number(name, end)?.max(0.0) as usize).clamp(start, chars.len()),
+                None => chars.len(),
+            };
+            Value::String(chars[start..end].iter().collect())
+        }
+        "starts_with" => Value::Bool(str_arg(name, args, 0)?.starts_with(&str_arg(name, args, 1)?)),
+        "ends_with" => Value::Bool(str_arg(name, args, 0)?.ends_with(&str_arg(name, args, 1)?)),
+        "contains" => match arg(name, args, 0)? {
+            Value::Array(items) => Value::Bool(items.contains(arg(name, args, 1)?)),
+            Value::Object(obj) => Value::Bool(obj.contains_key(&str_arg(name, args, 1)?)),
+            other => Value::Bool(to_text(other).contains(&str_arg(name, args, 1)?)),
+        },
+        "length" => match arg(name, args, 0)? {
+            Value::Array(items) => Value::from(items.len()),
+            Value::Object(obj) => Value::from(obj.len()),
+            Value::Null => Value::from(0),
+            other => Value::from(to_text(other).chars().count()),
+        },
+        "to_string" => Value::String(str_arg(name, args, 0)?),
+
+        // Math functions
+        "add" => number(num_arg(name, args, 0)? + num_arg(name, args, 1)?),
+        "subtract" => number(num_arg(name, args, 0)? - num_arg(name, args, 1)?),
+        "multiply" => number(num_arg(name, args, 0)? * num_arg(name, args, 1)?),
+        "divide" => {
+            let divisor = num_arg(name, args, 1)?;
+            if divisor == 0.0 {
+                anyhow::bail!("divide: division by zero");
+            }
+            number(num_arg(name, args, 0)? / divisor)
+        }
+        "round" => {
+            let factor = 10f64.powi(
+                args.get(1)
+                    .map(|d| as_number(name, d))
+                    .transpose()?
+                    .unwrap_or(0.0) as i32,
+            );
+            number((num_arg(name, args, 0)? * factor).round() / factor)
+        }
+        "floor" => number(num_arg(name, args, 0)?.floor()),
+        "ceil" => number(num_arg(name, args, 0)?.ceil()),
+        "abs" => number(num_arg(name, args, 0)?.abs()),
+        "min" | "max" | "sum" => {
+            // Accept either a single array or a list of arguments
+            let values = match args {
+                [Value::Array(items)] => items.as_slice(),
+                _ => args,
+            };
+            let numbers = values
+                .iter()
+                .map(|v| as_number(name, v))
+                .collect::<Result<Vec<_>>>()?;
+            match name {
+                "sum" => number(numbers.iter().sum()),
+                "min" => numbers
+                    .into_iter()
+                    .reduce(f64::min)
+                    .map(number)
+                    .unwrap_or(Value::Null),
+                _ => numbers
+                    .into_iter()
+                    .reduce(f64::max)
+                    .map(number)
+                    .unwrap_or(Value::Null),
+            }
+        }
+        "to_number" => number(num_arg(name, args, 0)?),
+
+        // Date functions
+        "now" => Value::String(Utc::now().to_rfc3339()),
+        "today" => Value::String(Utc::now().format("%Y-%m-%d").to_string()),
+        "timestamp" => match args.first() {
+            Some(date) => Value::from(as_date(name, date)?.timestamp()),
+            None => Value::from(Utc::now().timestamp()),
+        },
+        "format_date" => {
+            let date = as_date(name, arg(name, args, 0)?)?;
+            let format = str_arg(name, args, 1)?;
+            // Formatting with an invalid specifier panics, so reject it up front
+            let items = StrftimeItems::new(&format).collect::<Vec<_>>();
+            if items.iter().any(|item| matches!(item, Item::Error)) {
+                anyhow::bail!("{}: invalid format string {}", name, format);
+            }
+            Value::String(date.format_with_items(items.into_iter()).to_string())
+        }
+        "add_days" | "add_hours" | "add_minutes" => {
+            let date = as_date(name, arg(name, args, 0)?)?;
+            let amount = num_arg(name, args, 1)? as i64;
+            let delta = match name {
+                "add_days" => Duration::try_days(amount),
+                "add_hours" => Duration::try_hours(amount),
+                _ => Duration::try_minutes(amount),
+            };
+            let shifted = delta
+                .and_then(|delta| date.checked_add_signed(delta))
+                .ok_or_else(|| anyhow::anyhow!("{}: date out of range", name))?;
+            Value::String(shifted.to_rfc3339())
+        }
+
+        // JSON functions
+        "json_encode" => Value::String(serde_json::to_string(arg(name, args, 0)?)?),
+        "json_decode" => {
+            serde_json::from_str(&str_arg(name, args, 0)?).context("json_decode: invalid JSON")?
+        }
+        "keys" => match arg(name, args, 0)? {
+            Value::Object(obj) => Value::Array(obj.keys().cloned().map(Value::String).collect()),
+            _ => Value::Array(Vec::new()),
+        },
+        "values" => match arg(name, args, 0)? {
+            Value::Object(obj) => Value::Array(obj.values().cloned().collect()),
+            _ => Value::Array(Vec::new()),
+        },
+        "get" => lookup_path(arg(name, args, 0)?, &str_arg(name, args, 1)?)
+            .cloned()
+            .unwrap_or(Value::Null),
+        "default" => args
+            .iter()
+            .find(|v| !v.is_null())
+            .cloned()
+            .unwrap_or(Value::Null),
+        "merge" => {
+            let mut merged = serde_json::Map::new();
+            for value in args {
+                if let Value::Object(obj) = value {
+                    merged.extend(obj.clone());
+                }
+            }
+            Value::Object(merged)
+        }
+
+        other => anyhow::bail!("Unknown function: {}", other),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope<'a>(
+        nodes: &'a HashMap<String, Value>,
+        variables: &'a HashMap<String, Value>,
+    ) -> ExpressionScope<'a> {
+        ExpressionScope {
+            input: json!({"name": "Alice", "items": [{"id": 1}, {"id": 2}]}),
+            nodes,
+            variables,
+            execution: json!({"id": "exec-1"}),
+        }
+    }
+
+    #[test]
+    fn test_lookup_path() {
+        let data = json!({"user": {"name": "John"}, "items": [{"id": 7}, [1, 2]]});
+
+        assert_eq!(lookup_path(&data, "user.name"), Some(&json!("John")));
+        assert_eq!(lookup_path(&data, "items[0].id"), Some(&json!(7)));
+        assert_eq!(lookup_path(&data, "items.0.id"), Some(&json!(7)));
+        assert_eq!(lookup_path(&data, "items[1][1]"), Some(&json!(2)));
+        assert_eq!(lookup_path(&data, "user.missing"), None);
+    }
+
+    #[test]
+    fn test_resolve_roots() {
+        let mut nodes = HashMap::new();
+        nodes.insert("fetch".to_string(), json!({"data": {"items": ["a", "b"]}}));
+        let mut variables = HashMap::new();
+        variables.insert("region".to_string(), json!("eu"));
+        let scope = scope(&nodes, &variables);
+
+        assert_eq!(evaluate("$input.name", &scope).unwrap(), json!("Alice"));
+        assert_eq!(evaluate("$input.items[1].id", &scope).unwrap(), json!(2));
+        assert_eq!(evaluate("$input.items.0.id", &scope).unwrap(), json!(1));
+        assert_eq!(
+            evaluate("$node[\"fetch\"].data.items[0]", &scope).unwrap(),
+            json!("a")
+        );
+        assert_eq!(evaluate("$vars.region", &scope).unwrap(), json!("eu"));
+        assert_eq!(evaluate("$execution.id", &scope).unwrap(), json!("exec-1"));
+        assert_eq!(
+            evaluate("$input.missing.field", &scope).unwrap(),
+            Value::Null
+        );
+        assert!(evaluate("$other.x", &scope).is_err());
+    }
+
+    #[test]
+    fn test_resolve_parameters() {
+        let nodes = HashMap::new();
+        let variables = HashMap::new();
+        let scope = scope(&nodes, &variables);
+
+        let params = json!({
+            "text": "Hello {{ $input.name }}, run {{ $execution.id }}",
+            "items": "{{ $input.items }}",
+            "legacy": "{{user.id}}",
+            "nested": [{"value": "{{ upper($input.name) }}"}]
+        });
+
+        let resolved = resolve_parameters(&params, &scope).unwrap();
+        assert_eq!(resolved["text"], json!("Hello Alice, run exec-1"));
+        assert_eq!(resolved["items"], json!([{"id": 1}, {"id": 2}]));
+        assert_eq!(resolved["legacy"], json!("{{user.id}}"));
+        assert_eq!(resolved["nested"][0]["value"], json!("ALICE"));
+    }
+
+    #[test]
+    fn test_functions() {
+        let nodes = HashMap::new();
+        let variables = HashMap::new();
+        let scope = scope(&nodes, &variables);
+
+        let eval = |expr: &str| evaluate(expr, &scope).unwrap();
+
+        assert_eq!(
+            eval("lower(concat($input.name, '-', 'X'))"),
+            json!("alice-x")
+        );
+        assert_eq!(eval("length($input.items)"), json!(2));
+        assert_eq!(eval("join(split('a,b', ','), '|')"), json!("a|b"));
+        assert_eq!(eval("add(1, 2.5)"), json!(3.5));
+        assert_eq!(eval("multiply(3, 4)"), json!(12));
+        assert_eq!(eval("round(2.345, 2)"), json!(2.35));
+        assert_eq!(eval("max($input.items[0].id, 5, -1)"), json!(5));
+        assert_eq!(
+            eval("format_date('2024-03-01', '%d/%m/%Y')"),
+            json!("01/03/2024")
+        );
+        assert_eq!(
+            eval("add_days('2024-03-01T00:00:00+00:00', 1)"),
+            json!("2024-03-02T00:00:00+00:00")
+        );
+        assert_eq!(eval("json_decode('{\"a\": 1}')"), json!({"a": 1}));
+        assert_eq!(eval("json_encode($input.items[0])"), json!("{\"id\":1}"));
+        assert_eq!(
+            eval("default($input.missing, 'fallback')"),
+            json!("fallback")
+        );
+        assert!(evaluate("divide(1, 0)", &scope).is_err());
+        assert!(evaluate("nope(1)", &scope).is_err());
+    }
+
+    #[test]
+    fn test_date_functions_reject_invalid_input() {
+        let nodes = HashMap::new();
+        let variables = HashMap::new();
+        let scope = scope(&nodes, &variables);
+
+        assert!(evaluate("format_date('2024-03-01', '%Q')", &scope).is_err());
+        assert!(evaluate("add_days(now(), 1000000000000000000)", &scope).is_err());
+        assert!(evaluate("add_minutes(now(), -1000000000000000000)", &scope).is_err());
+        assert!(evaluate("add_hours('2024-03-01', 100000000000000)", &scope).is_err());
+    }
+}
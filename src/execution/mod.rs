@@ -1,5 +1,17 @@
+pub mod circuit;
+pub mod dry_run;
 pub mod engine;
+pub mod events;
 pub mod executor;
+pub mod expression;
+pub mod routing;
+pub mod scheduler;
+pub mod worker;
 
+pub use circuit::*;
+pub use dry_run::*;
 pub use engine::*;
+pub use events::*;
 pub use executor::*;
+pub use scheduler::*;
+pub use worker::*;
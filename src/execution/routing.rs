@@ -0,0 +1,374 @@
+use crate::models::{EdgeDefinition, NodeDefinition};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// Port emitted by a node whose failure is routed along its error edges
+pub const ERROR_PORT: &str = "error";
+
+/// Port emitted by a `timeout` node whose deadline expired with `on_timeout: skip`
+pub const TIMEOUT_PORT: &str = "timeout";
+
+/// Node type that joins several incoming branches
+pub const MERGE_NODE_TYPE: &str = "merge";
+
+/// Check whether an edge is followed given the port emitted by its source node.
+///
+/// Edges without a `from_output` are followed unless the source node failed
+/// or timed out; labelled edges are only followed when the source node emitted
+/// the matching port.
+pub fn edge_is_taken(edge: &EdgeDefinition, port: Option<&str>) -> bool {
+    if edge.from_output.is_empty() {
+        !matches!(port, Some(ERROR_PORT | TIMEOUT_PORT))
+    } else {
+        port == Some(edge.from_output.as_str())
+    }
+}
+
+/// Check whether a node has outgoing edges for its error port
+pub fn has_error_edges(node_id: &str, edges: &[EdgeDefinition]) -> bool {
+    edges
+        .iter()
+        .any(|edge| edge.from == node_id && edge.from_output == ERROR_PORT)
+}
+
+/// Determine which incoming edges of a node were taken.
+///
+/// `completed` maps every node that ran successfully to the port it emitted.
+/// Nodes that were skipped are absent from the map, so their outgoing edges
+/// are never taken.
+///
+/// Returns `None` when the node has incoming edges but none of them were
+/// taken, meaning the node sits on an untaken branch and must be skipped.
+/// Root nodes (no incoming edges) always run.
+pub fn taken_incoming_edges<'a>(
+    node_id: &str,
+    edges: &'a [EdgeDefinition],
+    completed: &HashMap<String, Option<String>>,
+) -> Option<Vec<&'a EdgeDefinition>> {
+    let incoming: Vec<&EdgeDefinition> = edges.iter().filter(|e| e.to == node_id).collect();
+
+    if incoming.is_empty() {
+        return Some(incoming);
+    }
+
+    let taken: Vec<&EdgeDefinition> = incoming
+        .into_iter()
+        .filter(|edge| {
+            completed
+                .get(&edge.from)
+                .is_some_and(|port| edge_is_taken(edge, port.as_deref()))
+        })
+        .collect();
+
+    if taken.is_empty() { None } else { Some(taken) }
+}
+
+/// How many incoming branches a `merge` node waits for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Every branch that was not skipped
+    All,
+    /// The first branch to deliver its output
+    Any,
+    /// More than half of the branches that were not skipped
+    Majority,
+}
+
+impl MergeStrategy {
+    /// Number of delivered branches needed out of the `possible` ones, which
+    /// are the incoming edges not known to be untaken
+    pub fn required(self, possible: usize) -> usize {
+        match self {
+            MergeStrategy::All => possible,
+            MergeStrategy::Any => possible.min(1),
+            MergeStrategy::Majority => possible / 2 + 1,
+        }
+    }
+}
+
+/// Join settings of a `merge` node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MergeJoin {
+    pub strategy: MergeStrategy,
+    /// How long to wait for the remaining branches once the first one delivered
+    pub timeout: Option<Duration>,
+}
+
+impl MergeJoin {
+    /// Read the join settings of a node, or `None` if it is not a `merge` node
+    pub fn of(node: &NodeDefinition) -> Option<Self> {
+        if node.node_type != MERGE_NODE_TYPE {
+            return None;
+        }
+
+        let strategy = match node.parameters["strategy"].as_str() {
+            Some("any") => MergeStrategy::Any,
+            Some("majority") => MergeStrategy::Majority,
+            _ => MergeStrategy::All,
+        };
+        let timeout = node.parameters["timeout"].as_u64().map(Duration::from_secs);
+
+        Some(Self { strategy, timeout })
+    }
+
+    /// Check whether the node can run given the number of branches that
+    /// delivered so far and the number still running
+    pub fn is_satisfied(&self, delivered: usize, running: usize) -> bool {
+        delivered > 0 && delivered >= self.strategy.required(delivered + running)
+    }
+}
+
+/// Keep the taken incoming edges of a `merge` node that its strategy waits for.
+///
+/// Edges are ordered by when their source node finished, as listed in
+/// `finish_order`, so that the branches that delivered first are kept.
+pub fn merged_edges<'a>(
+    mut taken: Vec<&'a EdgeDefinition>,
+    strategy: MergeStrategy,
+    finish_order: &[String],
+) -> Vec<&'a EdgeDefinition> {
+    taken.sort_by_key(|edge| {
+        finish_order
+            .iter()
+            .position(|id| *id == edge.from)
+            .unwrap_or(usize::MAX)
+    });
+    taken.truncate(strategy.required(taken.len()));
+    taken
+}
+
+/// Input key under which a predecessor's output is passed along an edge
+pub fn input_key(edge: &EdgeDefinition) -> String {
+    if edge.to_input.is_empty() {
+        edge.from.clone()
+    } else {
+        edge.to_input.clone()
+    }
+}
+
+/// A node and every node reachable from it along the edges
+pub fn downstream_nodes<'a>(node_id: &'a str, edges: &'a [EdgeDefinition]) -> HashSet<&'a str> {
+    let mut reached = HashSet::from([node_id]);
+    let mut stack = vec![node_id];
+    while let Some(current) = stack.pop() {
+        for edge in edges.iter().filter(|edge| edge.from == current) {
+            if reached.insert(edge.to.as_str()) {
+                stack.push(edge.to.as_str());
+            }
+        }
+    }
+    reached
+}
+
+/// A node and every node it is reachable from along the edges
+pub fn upstream_nodes<'a>(node_id: &'a str, edges: &'a [EdgeDefinition]) -> HashSet<&'a str> {
+    let mut reached = HashSet::from([node_id]);
+    let mut stack = vec![node_id];
+    while let Some(current) = stack.pop() {
+        for edge in edges.iter().filter(|edge| edge.to == current) {
+            if reached.insert(edge.from.as_str()) {
+                stack.push(edge.from.as_str());
+            }
+        }
+    }
+    reached
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from: &str, to: &str, from_output: &str) -> EdgeDefinition {
+        EdgeDefinition {
+            from: from.to_string(),
+            to: to.to_string(),
+            from_output: from_output.to_string(),
+            to_input: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_downstream_and_upstream_nodes() {
+        let edges = [
+            edge("start", "fetch", ""),
+            edge("fetch", "summarize", ""),
+            edge("summarize", "notify", "true"),
+            edge("summarize", "log", "error"),
+            edge("start", "audit", ""),
+        ];
+
+        assert_eq!(
+            downstream_nodes("summarize", &edges),
+            HashSet::from(["summarize", "notify", "log"])
+        );
+        assert_eq!(downstream_nodes("audit", &edges), HashSet::from(["audit"]));
+        assert_eq!(downstream_nodes("start", &edges).len(), 6);
+
+        assert_eq!(
+            upstream_nodes("notify", &edges),
+            HashSet::from(["notify", "summarize", "fetch", "start"])
+        );
+        assert_eq!(
+            upstream_nodes("audit", &edges),
+            HashSet::from(["audit", "start"])
+        );
+    }
+
+    #[test]
+    fn test_edge_is_taken() {
+        assert!(edge_is_taken(&edge("a", "b", ""), None));
+        assert!(edge_is_taken(&edge("a", "b", ""), Some("true")));
+        assert!(edge_is_taken(&edge("a", "b", "true"), Some("true")));
+        assert!(!edge_is_taken(&edge("a", "b", "true"), Some("false")));
+        assert!(!edge_is_taken(&edge("a", "b", "true"), None));
+        assert!(!edge_is_taken(&edge("a", "b", ""), Some(ERROR_PORT)));
+        assert!(edge_is_taken(&edge("a", "b", "error"), Some(ERROR_PORT)));
+        assert!(!edge_is_taken(&edge("a", "b", ""), Some(TIMEOUT_PORT)));
+        assert!(edge_is_taken(
+            &edge("a", "b", "timeout"),
+            Some(TIMEOUT_PORT)
+        ));
+    }
+
+    #[test]
+    fn test_error_edges() {
+        let edges = vec![
+            edge("fetch", "process", ""),
+            edge("fetch", "handle_error", "error"),
+        ];
+        assert!(has_error_edges("fetch", &edges));
+        assert!(!has_error_edges("process", &edges));
+
+        let mut completed = HashMap::new();
+        completed.insert("fetch".to_string(), Some(ERROR_PORT.to_string()));
+
+        assert!(taken_incoming_edges("process", &edges, &completed).is_none());
+        assert!(taken_incoming_edges("handle_error", &edges, &completed).is_some());
+
+        completed.insert("fetch".to_string(), None);
+        assert!(taken_incoming_edges("process", &edges, &completed).is_some());
+        assert!(taken_incoming_edges("handle_error", &edges, &completed).is_none());
+    }
+
+    #[test]
+    fn test_conditional_branches() {
+        let edges = vec![
+            edge("start", "check", ""),
+            edge("check", "high", "true"),
+            edge("check", "low", "false"),
+        ];
+
+        let mut completed = HashMap::new();
+        completed.insert("start".to_string(), None);
+        completed.insert("check".to_string(), Some("true".to_string()));
+
+        assert_eq!(
+            taken_incoming_edges("check", &edges, &completed)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(taken_incoming_edges("high", &edges, &completed).is_some());
+        assert!(taken_incoming_edges("low", &edges, &completed).is_none());
+    }
+
+    #[test]
+    fn test_skip_propagates_downstream() {
+        let edges = vec![edge("check", "low", "false"), edge("low", "notify", "")];
+
+        let mut completed = HashMap::new();
+        completed.insert("check".to_string(), Some("true".to_string()));
+
+        // "low" is skipped, so it never appears in `completed`
+        assert!(taken_incoming_edges("low", &edges, &completed).is_none());
+        assert!(taken_incoming_edges("notify", &edges, &completed).is_none());
+    }
+
+    #[test]
+    fn test_join_after_branches_runs_with_taken_edge() {
+        let edges = vec![
+            edge("check", "high", "true"),
+            edge("check", "low", "false"),
+            edge("high", "join", ""),
+            edge("low", "join", ""),
+        ];
+
+        let mut completed = HashMap::new();
+        completed.insert("check".to_string(), Some("true".to_string()));
+        completed.insert("high".to_string(), None);
+
+        let taken = taken_incoming_edges("join", &edges, &completed).unwrap();
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].from, "high");
+    }
+
+    #[test]
+    fn test_root_node_always_runs() {
+        let edges = vec![edge("a", "b", "")];
+        let completed = HashMap::new();
+
+        let taken = taken_incoming_edges("a", &edges, &completed).unwrap();
+        assert!(taken.is_empty());
+    }
+
+    #[test]
+    fn test_merge_strategy_required() {
+        assert_eq!(MergeStrategy::All.required(3), 3);
+        assert_eq!(MergeStrategy::Any.required(3), 1);
+        assert_eq!(MergeStrategy::Any.required(0), 0);
+        assert_eq!(MergeStrategy::Majority.required(3), 2);
+        assert_eq!(MergeStrategy::Majority.required(4), 3);
+        assert_eq!(MergeStrategy::Majority.required(1), 1);
+    }
+
+    #[test]
+    fn test_merge_join_satisfied() {
+        let join = |strategy| MergeJoin {
+            strategy,
+            timeout: None,
+        };
+
+        // Two of three branches delivered, one still running
+        assert!(!join(MergeStrategy::All).is_satisfied(2, 1));
+        assert!(join(MergeStrategy::Any).is_satisfied(2, 1));
+        assert!(join(MergeStrategy::Majority).is_satisfied(2, 1));
+
+        // A skipped branch no longer counts towards the majority
+        assert!(join(MergeStrategy::Majority).is_satisfied(1, 0));
+        assert!(!join(MergeStrategy::Majority).is_satisfied(1, 2));
+
+        // Nothing delivered: the merge is skipped, not run
+        assert!(!join(MergeStrategy::Any).is_satisfied(0, 2));
+        assert!(!join(MergeStrategy::All).is_satisfied(0, 0));
+    }
+
+    #[test]
+    fn test_merged_edges_keep_first_delivered() {
+        let edges = [
+            edge("a", "join", ""),
+            edge("b", "join", ""),
+            edge("c", "join", ""),
+        ];
+        let taken: Vec<&EdgeDefinition> = edges.iter().collect();
+        let order = vec!["c".to_string(), "a".to_string(), "b".to_string()];
+
+        let any = merged_edges(taken.clone(), MergeStrategy::Any, &order);
+        assert_eq!(any.len(), 1);
+        assert_eq!(any[0].from, "c");
+
+        let majority = merged_edges(taken.clone(), MergeStrategy::Majority, &order);
+        let sources: Vec<&str> = majority.iter().map(|e| e.from.as_str()).collect();
+        assert_eq!(sources, vec!["c", "a"]);
+
+        assert_eq!(merged_edges(taken, MergeStrategy::All, &order).len(), 3);
+    }
+
+    #[test]
+    fn test_input_key() {
+        let mut e = edge("fetch", "combine", "");
+        assert_eq!(input_key(&e), "fetch");
+
+        e.to_input = "users".to_string();
+        assert_eq!(input_key(&e), "users");
+    }
+}
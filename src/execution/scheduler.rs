@@ -0,0 +1,265 @@
+use crate::db;
+use crate::models::WorkflowExecution;
+use crate::nodes::schedule_trigger::{MisfirePolicy, ScheduleTriggerParams};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use sqlx::PgPool;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// Node type whose `cron` schedule starts executions of its workflow
+pub const SCHEDULE_TRIGGER_TYPE: &str = "schedule_trigger";
+
+/// How often the scheduler checks for due fire times
+pub const SCHEDULER_TICK: Duration = Duration::from_secs(1);
+
+/// How late a fire time may be and still fire with the `skip` misfire policy
+pub const MISFIRE_GRACE: chrono::Duration = chrono::Duration::seconds(60);
+
+/// Maximum number of missed fire times caught up per schedule and tick
+pub const MAX_CATCH_UP_FIRES: usize = 100;
+
+/// Queue executions for due `schedule_trigger` nodes every [`SCHEDULER_TICK`]
+/// until the process stops.
+///
+/// Any number of replicas can run the scheduler; an advisory lock makes sure
+/// only one of them fires each tick. With `ignore_pinned_data`, the queued
+/// executions run every node for real.
+pub async fn run_scheduler(pool: PgPool, ignore_pinned_data: bool) {
+    tracing::info!("Starting scheduler");
+
+    let mut interval = tokio::time::interval(SCHEDULER_TICK);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        match fire_due_schedules(&pool, Utc::now(), ignore_pinned_data).await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Scheduler queued {} executions", count),
+            Err(e) => tracing::error!("Scheduler tick failed: {}", e),
+        }
+    }
+}
+
+/// Queue an execution for every fire time of the schedules of active
+/// workflows due at `now`; returns how many were queued.
+///
+/// Fire times and the executions they queue are committed together, so each
+/// fire time starts exactly one execution.
+pub async fn fire_due_schedules(
+    pool: &PgPool,
+    now: DateTime<Utc>,
+    ignore_pinned_data: bool,
+) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+    if !db::try_lock_scheduler(&mut tx).await? {
+        return Ok(0);
+    }
+
+    let mut fired = 0;
+    for workflow in db::list_workflows(pool, true).await? {
+        let definition = match workflow.to_definition() {
+            Ok(definition) => definition,
+            Err(e) => {
+                tracing::warn!("Skipping schedules of workflow {}: {}", workflow.id, e);
+                continue;
+            }
+        };
+
+        let triggers = definition
+            .nodes
+            .iter()
+            .filter(|node| node.node_type == SCHEDULE_TRIGGER_TYPE);
+        for node in triggers {
+            let params = serde_json::from_value::<ScheduleTriggerParams>(node.parameters.clone())
+                .map_err(anyhow::Error::from)
+                .and_then(|params| Ok((params.schedule()?, params.misfire)));
+            let ((schedule, timezone), misfire) = match params {
+                Ok(params) => params,
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping schedule {} of workflow {}: {}",
+                        node.id,
+                        workflow.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+
+            let Some(last_fired_at) =
+                db::get_schedule_last_fired(&mut tx, workflow.id, &node.id).await?
+            else {
+                // New schedules start counting from now
+                db::set_schedule_last_fired(&mut tx, workflow.id, &node.id, now).await?;
+                continue;
+            };
+
+            let (fire_times, last_fired_at) =
+                due_fire_times(&schedule, timezone, last_fired_at, now, misfire);
+            for scheduled_for in fire_times {
+                let input = serde_json::json!({
+                    "trigger_type": "schedule",
+                    "trigger_node_id": node.id,
+                    "scheduled_for": scheduled_for.to_rfc3339(),
+                    "triggered_at": now.to_rfc3339(),
+                });
+                let mut execution = WorkflowExecution::pending(workflow.id, Some(input));
+                execution.ignore_pinned_data = ignore_pinned_data;
+                let execution = db::enqueue_execution(&mut *tx, &execution).await?;
+
+                tracing::info!(
+                    "Schedule {} of workflow {} queued execution {} for {}",
+                    node.id,
+                    workflow.id,
+                    execution.id,
+                    scheduled_for
+                );
+                fired += 1;
+            }
+
+            if let Some(last_fired_at) = last_fired_at {
+                db::set_schedule_last_fired(&mut tx, workflow.id, &node.id, last_fired_at).await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(fired)
+}
+
+/// Fire times of a schedule that should fire at `now`, given the last fire
+/// time it reached.
+///
+/// Also returns the fire time the schedule reaches, to be stored as its new
+/// last fire time, or `None` if no fire time passed.
+pub fn due_fire_times(
+    schedule: &Schedule,
+    timezone: Tz,
+    last_fired_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+    misfire: MisfirePolicy,
+) -> (Vec<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let mut passed = schedule
+        .after(&last_fired_at.with_timezone(&timezone))
+        .map(|fire_time| fire_time.with_timezone(&Utc))
+        .take_while(|fire_time| *fire_time <= now);
+
+    match misfire {
+        MisfirePolicy::CatchUp => {
+            let fire_times: Vec<_> = passed.by_ref().take(MAX_CATCH_UP_FIRES).collect();
+            let reached = fire_times.last().copied();
+            (fire_times, reached)
+        }
+        MisfirePolicy::FireOnce => {
+            let reached = passed.last();
+            (reached.into_iter().collect(), reached)
+        }
+        MisfirePolicy::Skip => {
+            let reached = passed.last();
+            let fire_times = reached
+                .filter(|fire_time| now - *fire_time <= MISFIRE_GRACE)
+                .into_iter()
+                .collect();
+            (fire_times, reached)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::str::FromStr;
+
+    fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_due_fire_times_on_time() {
+        let hourly = Schedule::from_str("0 0 * * * *").unwrap();
+
+        for misfire in [
+            MisfirePolicy::Skip,
+            MisfirePolicy::FireOnce,
+            MisfirePolicy::CatchUp,
+        ] {
+            let (fire_times, reached) =
+                due_fire_times(&hourly, Tz::UTC, at(9, 0), at(10, 0), misfire);
+            assert_eq!(fire_times, vec![at(10, 0)]);
+            assert_eq!(reached, Some(at(10, 0)));
+
+            let (fire_times, reached) =
+                due_fire_times(&hourly, Tz::UTC, at(10, 0), at(10, 30), misfire);
+            assert!(fire_times.is_empty());
+            assert_eq!(reached, None);
+        }
+    }
+
+    #[test]
+    fn test_due_fire_times_misfire() {
+        let hourly = Schedule::from_str("0 0 * * * *").unwrap();
+
+        // Down from 9:00 to 12:30
+        let (fire_times, reached) = due_fire_times(
+            &hourly,
+            Tz::UTC,
+            at(9, 0),
+            at(12, 30),
+            MisfirePolicy::CatchUp,
+        );
+        assert_eq!(fire_times, vec![at(10, 0), at(11, 0), at(12, 0)]);
+        assert_eq!(reached, Some(at(12, 0)));
+
+        let (fire_times, reached) = due_fire_times(
+            &hourly,
+            Tz::UTC,
+            at(9, 0),
+            at(12, 30),
+            MisfirePolicy::FireOnce,
+        );
+        assert_eq!(fire_times, vec![at(12, 0)]);
+        assert_eq!(reached, Some(at(12, 0)));
+
+        let (fire_times, reached) =
+            due_fire_times(&hourly, Tz::UTC, at(9, 0), at(12, 30), MisfirePolicy::Skip);
+        assert!(fire_times.is_empty());
+        assert_eq!(reached, Some(at(12, 0)));
+    }
+
+    #[test]
+    fn test_due_fire_times_timezone() {
+        // Daily at 9:00 in Madrid, which is 8:00 UTC in March (CET)
+        let daily = Schedule::from_str("0 0 9 * * *").unwrap();
+
+        let (fire_times, _) = due_fire_times(
+            &daily,
+            Tz::Europe__Madrid,
+            at(7, 0),
+            at(8, 0),
+            MisfirePolicy::FireOnce,
+        );
+        assert_eq!(fire_times, vec![at(8, 0)]);
+
+        let (fire_times, _) =
+            due_fire_times(&daily, Tz::UTC, at(7, 0), at(8, 0), MisfirePolicy::FireOnce);
+        assert!(fire_times.is_empty());
+    }
+
+    #[test]
+    fn test_due_fire_times_catch_up_limit() {
+        let every_second = Schedule::from_str("* * * * * *").unwrap();
+
+        let (fire_times, reached) = due_fire_times(
+            &every_second,
+            Tz::UTC,
+            at(9, 0),
+            at(10, 0),
+            MisfirePolicy::CatchUp,
+        );
+        assert_eq!(fire_times.len(), MAX_CATCH_UP_FIRES);
+        assert_eq!(reached, fire_times.last().copied());
+    }
+}
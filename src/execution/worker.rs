@@ -0,0 +1,79 @@
+use crate::execution::WorkflowExecutor;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+/// How long an idle worker waits before checking the queue again
+pub const QUEUE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often interrupted executions are looked up
+pub const RECOVERY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Run `workers` workers that take executions from the queue and run them.
+///
+/// Workers run until the process stops; any number of processes can run
+/// workers against the same database.
+pub async fn run_workers(executor: WorkflowExecutor, workers: usize) -> anyhow::Result<()> {
+    tracing::info!("Starting {} workers", workers);
+
+    let mut tasks = JoinSet::new();
+    for worker in 1..=workers {
+        tasks.spawn(run_worker(executor.clone(), worker));
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        joined?;
+    }
+
+    Ok(())
+}
+
+/// Take executions from the queue one at a time and run them
+async fn run_worker(executor: WorkflowExecutor, worker: usize) {
+    loop {
+        match executor.run_next().await {
+            Ok(Some(execution)) => tracing::info!(
+                "Worker {} finished execution {} ({})",
+                worker,
+                execution.id,
+                execution.status
+            ),
+            Ok(None) => tokio::time::sleep(QUEUE_POLL_INTERVAL).await,
+            Err(e) => {
+                tracing::error!("Worker {} failed to run an execution: {}", worker, e);
+                tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// Periodically recover executions whose process stopped while running them,
+/// starting right away
+pub async fn recover_interrupted_executions(executor: WorkflowExecutor) {
+    let mut interval = tokio::time::interval(RECOVERY_INTERVAL);
+    loop {
+        interval.tick().await;
+        let execution_ids = match executor.interrupted_executions().await {
+            Ok(execution_ids) => execution_ids,
+            Err(e) => {
+                tracing::error!("Failed to look up interrupted executions: {}", e);
+                continue;
+            }
+        };
+
+        for execution_id in execution_ids {
+            let executor = executor.clone();
+            tokio::spawn(async move {
+                match executor.recover(execution_id).await {
+                    Ok(execution) => tracing::info!(
+                        "Recovered execution {} ({})",
+                        execution.id,
+                        execution.status
+                    ),
+                    Err(e) => {
+                        tracing::warn!("Failed to recover execution {}: {}", execution_id, e)
+                    }
+                }
+            });
+        }
+    }
+}
@@ -1,9 +1,21 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use pmp_workflow::{WorkflowExecutor, config, create_node_registry, db, server};
+use pmp_workflow::{
+    CircuitStore, ExecutionStatus, NodeDefinition, PartialRun, WorkflowExecution, WorkflowExecutor,
+    config, create_node_registry, db, recover_interrupted_executions, run_scheduler, run_workers,
+    server,
+};
+use std::future::Future;
 use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+/// How often `execute --wait` checks whether the execution finished
+const EXECUTION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 #[derive(Parser)]
 #[command(name = "pmp-workflow")]
 #[command(about = "A workflow automation engine", long_about = None)]
@@ -14,6 +26,19 @@ struct Cli {
     /// Database URL (can also be set via DATABASE_URL environment variable)
     #[arg(long, env = "DATABASE_URL")]
     database_url: Option<String>,
+
+    /// Redis URL for circuit breaker state (defaults to keeping it in the database)
+    #[arg(long, env = "REDIS_URL")]
+    redis_url: Option<String>,
+
+    /// Maximum number of nodes running at the same time across all executions
+    #[arg(long, env = "PMP_MAX_CONCURRENCY")]
+    max_concurrency: Option<usize>,
+
+    /// Run nodes with pinned output for real in the executions this process
+    /// starts (for production servers and schedulers)
+    #[arg(long, env = "PMP_IGNORE_PINNED_DATA")]
+    ignore_pinned_data: bool,
 }
 
 #[derive(Subcommand)]
@@ -43,6 +68,59 @@ enum Commands {
         /// Input data as JSON
         #[arg(short, long)]
         input: Option<String>,
+
+        /// Wait for a worker to finish the execution and show its result
+        #[arg(short, long)]
+        wait: bool,
+
+        /// Return the execution started earlier with this key instead of
+        /// queueing a new one
+        #[arg(long)]
+        idempotency_key: Option<String>,
+
+        /// Run only this node
+        #[arg(long, conflicts_with_all = ["until", "idempotency_key"])]
+        only: Option<String>,
+
+        /// Run this node and the nodes it depends on
+        #[arg(long, conflicts_with = "idempotency_key")]
+        until: Option<String>,
+
+        /// Feed the nodes that run the outputs of this execution
+        #[arg(long)]
+        from_execution: Option<String>,
+
+        /// Log what nodes with external side effects would do instead of
+        /// running them
+        #[arg(long, conflicts_with = "idempotency_key")]
+        dry_run: bool,
+    },
+
+    /// Pin the output of a node, so that executions return it instead of
+    /// running the node
+    Pin {
+        /// Workflow name or ID
+        workflow: String,
+
+        /// Node ID
+        node: String,
+
+        /// Pin the output the node had in this execution
+        #[arg(long, conflicts_with = "data", required_unless_present = "data")]
+        execution: Option<String>,
+
+        /// Pin this output, as JSON
+        #[arg(long)]
+        data: Option<String>,
+    },
+
+    /// Remove the pinned output of a node
+    Unpin {
+        /// Workflow name or ID
+        workflow: String,
+
+        /// Node ID
+        node: String,
     },
 
     /// Show workflow execution history
@@ -55,12 +133,50 @@ enum Commands {
         limit: i64,
     },
 
-    /// Show execution details
+    /// Show execution details, with the sub-workflow executions it started
     Show {
         /// Execution ID
         execution_id: String,
     },
 
+    /// Cancel a running or waiting execution
+    Cancel {
+        /// Execution ID
+        execution_id: String,
+    },
+
+    /// Continue an execution interrupted by a crash from its last completed node
+    Resume {
+        /// Execution ID
+        execution_id: String,
+    },
+
+    /// Re-run a finished execution, keeping the results of the nodes that do
+    /// not depend on the node it starts from
+    Rerun {
+        /// Execution ID
+        execution_id: String,
+
+        /// Node to start from (defaults to the node where the execution failed)
+        #[arg(long)]
+        from_node: Option<String>,
+
+        /// Input data as JSON (defaults to the input of the execution)
+        #[arg(short, long)]
+        input: Option<String>,
+
+        /// Wait for a worker to finish the re-run and show its result
+        #[arg(short, long)]
+        wait: bool,
+    },
+
+    /// Run workers that take queued executions and run them
+    Worker {
+        /// Number of executions to run at the same time
+        #[arg(short, long, default_value = "4", env = "PMP_WORKERS")]
+        concurrency: usize,
+    },
+
     /// Start webhook server
     Serve {
         /// Host to bind to
@@ -70,7 +186,19 @@ enum Commands {
         /// Port to bind to
         #[arg(long, default_value = "3000")]
         port: u16,
+
+        /// Workers running queued executions in the server process (0 to
+        /// leave them to `worker` processes)
+        #[arg(long, default_value = "4", env = "PMP_WORKERS")]
+        workers: usize,
+
+        /// Do not run the scheduler in the server process
+        #[arg(long)]
+        no_scheduler: bool,
     },
+
+    /// Run the scheduler that queues executions for `schedule_trigger` nodes
+    Scheduler,
 }
 
 #[tokio::main]
@@ -96,6 +224,12 @@ async fn main() -> Result<()> {
     // Create database pool
     let pool = db::create_pool(&database_url).await?;
 
+    // Circuit breaker state lives in Redis when configured, otherwise in the database
+    let circuits = match &cli.redis_url {
+        Some(url) => CircuitStore::redis(url)?,
+        None => CircuitStore::Postgres(pool.clone()),
+    };
+
     match cli.command {
         Commands::Init => {
             tracing::info!("Initializing database...");
@@ -139,7 +273,16 @@ async fn main() -> Result<()> {
             }
         }
 
-        Commands::Execute { workflow, input } => {
+        Commands::Execute {
+            workflow,
+            input,
+            wait,
+            idempotency_key,
+            only,
+            until,
+            from_execution,
+            dry_run,
+        } => {
             tracing::info!("Executing workflow: {}", workflow);
 
             // Parse input data
@@ -151,28 +294,153 @@ async fn main() -> Result<()> {
 
             // Create executor
             let registry = create_node_registry(&pool);
-            let executor = WorkflowExecutor::new(pool.clone(), registry);
+            let mut executor =
+                WorkflowExecutor::new(pool.clone(), registry).with_circuit_store(circuits);
+            if let Some(limit) = cli.max_concurrency {
+                executor = executor.with_node_slots(Arc::new(Semaphore::new(limit.max(1))));
+            }
+            if cli.ignore_pinned_data {
+                executor = executor.without_pinned_data();
+            }
+            if dry_run {
+                executor = executor.as_dry_run();
+            }
+
+            let partial = match (only, until) {
+                (Some(node_id), _) => Some(PartialRun::Only(node_id)),
+                (None, Some(node_id)) => Some(PartialRun::Until(node_id)),
+                (None, None) => None,
+            };
+            if let Some(partial) = partial {
+                let workflow_id = match workflow.parse::<uuid::Uuid>() {
+                    Ok(workflow_id) => workflow_id,
+                    Err(_) => db::get_workflow_by_name(&pool, &workflow).await?.id,
+                };
+                let seed_execution_id = from_execution
+                    .map(|id| id.parse::<uuid::Uuid>())
+                    .transpose()?;
+                let execution = executor
+                    .enqueue_partial(workflow_id, input_data, partial, seed_execution_id)
+                    .await?;
+
+                if !wait {
+                    println!("✓ Partial workflow execution queued");
+                    println!("  Execution ID: {}", execution.id);
+                    println!("  Status: {}", execution.status);
+                    return Ok(());
+                }
+                return wait_for_execution(&executor, execution).await;
+            } else if from_execution.is_some() {
+                anyhow::bail!("--from-execution requires --only or --until");
+            }
 
             // Try to parse as UUID first, otherwise treat as name
-            let execution = if let Ok(workflow_id) = workflow.parse::<uuid::Uuid>() {
-                executor.execute_by_id(workflow_id, input_data).await?
+            let queued = if let Ok(workflow_id) = workflow.parse::<uuid::Uuid>() {
+                executor
+                    .enqueue_by_id(workflow_id, input_data, idempotency_key)
+                    .await?
             } else {
-                executor.execute_by_name(&workflow, input_data).await?
+                executor
+                    .enqueue_by_name(&workflow, input_data, idempotency_key)
+                    .await?
             };
+            let execution = queued.execution;
 
-            println!("✓ Workflow execution completed");
-            println!("  Execution ID: {}", execution.id);
-            println!("  Status: {}", execution.status);
-            println!("  Started: {}", execution.started_at);
-            if let Some(finished) = execution.finished_at {
-                println!("  Finished: {}", finished);
+            if queued.duplicate {
+                println!("• An execution already exists for this idempotency key");
             }
-            if let Some(output) = execution.output_data {
-                println!("  Output: {}", serde_json::to_string_pretty(&output)?);
+            if !wait {
+                if !queued.duplicate {
+                    println!("✓ Workflow execution queued");
+                }
+                println!("  Execution ID: {}", execution.id);
+                println!("  Status: {}", execution.status);
+                return Ok(());
             }
-            if let Some(error) = execution.error {
-                println!("  Error: {}", error);
+
+            wait_for_execution(&executor, execution).await?;
+        }
+
+        Commands::Rerun {
+            execution_id,
+            from_node,
+            input,
+            wait,
+        } => {
+            let execution_id = execution_id.parse::<uuid::Uuid>()?;
+            let input_data = match input {
+                Some(input_str) => Some(serde_json::from_str(&input_str)?),
+                None => None,
+            };
+
+            let registry = create_node_registry(&pool);
+            let mut executor =
+                WorkflowExecutor::new(pool.clone(), registry).with_circuit_store(circuits);
+            if let Some(limit) = cli.max_concurrency {
+                executor = executor.with_node_slots(Arc::new(Semaphore::new(limit.max(1))));
+            }
+            if cli.ignore_pinned_data {
+                executor = executor.without_pinned_data();
+            }
+
+            let execution = executor
+                .enqueue_rerun(execution_id, from_node, input_data)
+                .await?;
+
+            if !wait {
+                println!("✓ Workflow execution re-run queued");
+                println!("  Execution ID: {}", execution.id);
+                match &execution.rerun_from_node {
+                    Some(node_id) => println!("  From node: {}", node_id),
+                    None => println!("  From node: (start)"),
+                }
+                return Ok(());
             }
+
+            wait_for_execution(&executor, execution).await?;
+        }
+
+        Commands::Pin {
+            workflow,
+            node,
+            execution,
+            data,
+        } => {
+            let output = match (execution, data) {
+                (Some(execution_id), _) => {
+                    let execution_id = execution_id.parse::<uuid::Uuid>()?;
+                    db::list_node_executions(&pool, execution_id)
+                        .await?
+                        .into_iter()
+                        .rev()
+                        .find(|node_exec| {
+                            node_exec.node_id == node
+                                && node_exec.iteration.is_none()
+                                && matches!(
+                                    node_exec.status,
+                                    ExecutionStatus::Success | ExecutionStatus::Pinned
+                                )
+                        })
+                        .and_then(|node_exec| node_exec.output_data)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!(
+                                "Node {} has no successful output in execution {}",
+                                node,
+                                execution_id
+                            )
+                        })?
+                }
+                (None, Some(data)) => serde_json::from_str(&data)?,
+                (None, None) => anyhow::bail!("Either --execution or --data is required"),
+            };
+
+            set_pinned_output(&pool, &workflow, &node, Some(output)).await?;
+            println!("✓ Pinned the output of node {}", node);
+        }
+
+        Commands::Unpin { workflow, node } => {
+            set_pinned_output(&pool, &workflow, &node, None).await?;
+            println!("✓ Removed the pinned output of node {}", node);
         }
 
         Commands::History { workflow, limit } => {
@@ -206,10 +474,25 @@ async fn main() -> Result<()> {
         Commands::Show { execution_id } => {
             let execution_uuid = execution_id.parse::<uuid::Uuid>()?;
             let execution = db::get_workflow_execution(&pool, execution_uuid).await?;
-            let node_executions = db::list_node_executions(&pool, execution_uuid).await?;
 
             println!("Execution: {}", execution.id);
             println!("  Workflow ID: {}", execution.workflow_id);
+            if let (Some(parent_id), Some(parent_node)) =
+                (execution.parent_execution_id, &execution.parent_node_id)
+            {
+                println!("  Parent: {} (node {})", parent_id, parent_node);
+            }
+            if let Some(original_id) = execution.rerun_of {
+                match &execution.rerun_from_node {
+                    Some(node_id) => {
+                        println!("  Re-run of: {} (from node {})", original_id, node_id)
+                    }
+                    None => println!("  Re-run of: {}", original_id),
+                }
+            }
+            if execution.dry_run {
+                println!("  Dry run: side-effecting nodes were mocked");
+            }
             println!("  Status: {}", execution.status);
             println!("  Started: {}", execution.started_at);
             if let Some(finished) = execution.finished_at {
@@ -229,24 +512,190 @@ async fn main() -> Result<()> {
             }
 
             println!("\nNode executions:");
-            for node_exec in node_executions {
-                println!(
-                    "  {} - {} ({})",
-                    node_exec.node_id, node_exec.status, node_exec.started_at
-                );
-                if let Some(error) = node_exec.error {
-                    println!("    Error: {}", error);
-                }
+            print_node_executions(&pool, execution_uuid, 1).await?;
+        }
+
+        Commands::Cancel { execution_id } => {
+            let execution_id = execution_id.parse::<uuid::Uuid>()?;
+
+            let registry = create_node_registry(&pool);
+            let executor =
+                WorkflowExecutor::new(pool.clone(), registry).with_circuit_store(circuits);
+            let execution = executor.cancel(execution_id).await?;
+
+            if execution.status == ExecutionStatus::Cancelled {
+                println!("✓ Execution cancelled");
+            } else {
+                println!("✓ Cancellation requested; the running execution will stop shortly");
+            }
+            println!("  Execution ID: {}", execution.id);
+            println!("  Status: {}", execution.status);
+        }
+
+        Commands::Resume { execution_id } => {
+            let execution_id = execution_id.parse::<uuid::Uuid>()?;
+
+            let registry = create_node_registry(&pool);
+            let mut executor =
+                WorkflowExecutor::new(pool.clone(), registry).with_circuit_store(circuits);
+            if let Some(limit) = cli.max_concurrency {
+                executor = executor.with_node_slots(Arc::new(Semaphore::new(limit.max(1))));
+            }
+            let execution = executor.recover(execution_id).await?;
+
+            println!("✓ Execution resumed");
+            println!("  Execution ID: {}", execution.id);
+            println!("  Status: {}", execution.status);
+            if let Some(error) = execution.error {
+                println!("  Error: {}", error);
+            }
+        }
+
+        Commands::Worker { concurrency } => {
+            let registry = create_node_registry(&pool);
+            let mut executor =
+                WorkflowExecutor::new(pool.clone(), registry).with_circuit_store(circuits);
+            if let Some(limit) = cli.max_concurrency {
+                executor = executor.with_node_slots(Arc::new(Semaphore::new(limit.max(1))));
             }
+
+            tokio::spawn(recover_interrupted_executions(executor.clone()));
+            run_workers(executor, concurrency.max(1)).await?;
+        }
+
+        Commands::Scheduler => {
+            run_scheduler(pool, cli.ignore_pinned_data).await;
         }
 
-        Commands::Serve { host, port } => {
+        Commands::Serve {
+            host,
+            port,
+            workers,
+            no_scheduler,
+        } => {
             tracing::info!("Starting webhook server on {}:{}", host, port);
 
             // Start the webhook server
-            server::start_server(pool, &host, port).await?;
+            let mut state = server::AppState::new(pool)
+                .with_circuit_store(circuits)
+                .with_workers(workers)
+                .with_scheduler(!no_scheduler);
+            if let Some(limit) = cli.max_concurrency {
+                state = state.with_max_concurrency(limit);
+            }
+            if cli.ignore_pinned_data {
+                state = state.without_pinned_data();
+            }
+            server::start_server(state, &host, port).await?;
         }
     }
 
     Ok(())
 }
+
+/// Pin the output of a node of a workflow, or remove it with `None`
+async fn set_pinned_output(
+    pool: &sqlx::PgPool,
+    workflow: &str,
+    node_id: &str,
+    output: Option<serde_json::Value>,
+) -> Result<()> {
+    let mut workflow = if let Ok(id) = workflow.parse::<uuid::Uuid>() {
+        db::get_workflow(pool, id).await?
+    } else {
+        db::get_workflow_by_name(pool, workflow).await?
+    };
+
+    let mut definition = workflow.to_definition()?;
+    let node = NodeDefinition::find_mut(&mut definition.nodes, node_id)
+        .ok_or_else(|| anyhow::anyhow!("Node {} not found in workflow", node_id))?;
+    node.pinned_output = output;
+
+    workflow.nodes = serde_json::to_value(&definition.nodes)?;
+    db::update_workflow(pool, &workflow).await?;
+
+    Ok(())
+}
+
+/// Wait for a worker to finish a queued execution and print its result
+async fn wait_for_execution(
+    executor: &WorkflowExecutor,
+    mut execution: WorkflowExecution,
+) -> Result<()> {
+    while matches!(
+        execution.status,
+        ExecutionStatus::Pending | ExecutionStatus::Running
+    ) {
+        tokio::time::sleep(EXECUTION_POLL_INTERVAL).await;
+        execution = executor.get_execution(execution.id).await?;
+    }
+
+    if execution.status == ExecutionStatus::Waiting {
+        println!("⏸ Workflow execution is waiting to be resumed");
+    } else {
+        println!("✓ Workflow execution completed");
+    }
+    println!("  Execution ID: {}", execution.id);
+    println!("  Status: {}", execution.status);
+    println!("  Started: {}", execution.started_at);
+    if let Some(finished) = execution.finished_at {
+        println!("  Finished: {}", finished);
+    }
+    if let Some(output) = execution.output_data {
+        println!("  Output: {}", serde_json::to_string_pretty(&output)?);
+    }
+    if let Some(error) = execution.error {
+        println!("  Error: {}", error);
+    }
+
+    Ok(())
+}
+
+/// Print the node executions of an execution, nesting under each node the
+/// sub-workflow executions it started
+fn print_node_executions<'a>(
+    pool: &'a sqlx::PgPool,
+    execution_id: uuid::Uuid,
+    level: usize,
+) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+    Box::pin(async move {
+        let node_executions = db::list_node_executions(pool, execution_id).await?;
+        let children = db::list_child_executions(pool, execution_id).await?;
+        let indent = "  ".repeat(level);
+
+        for node_exec in node_executions {
+            let mut label = node_exec.node_id.clone();
+            match (&node_exec.branch, node_exec.iteration) {
+                (Some(branch), _) => label.push_str(&format!("[{}]", branch)),
+                (None, Some(iteration)) => label.push_str(&format!("[{}]", iteration)),
+                (None, None) => {}
+            }
+            if node_exec.attempt > 1 {
+                label.push_str(&format!(" attempt {}", node_exec.attempt));
+            }
+            println!(
+                "{}{} - {} ({})",
+                indent, label, node_exec.status, node_exec.started_at
+            );
+            if let Some(error) = &node_exec.error {
+                println!("{}  Error: {}", indent, error);
+            }
+
+            // Sub-workflows started while this node execution was running
+            let finished_at = node_exec.finished_at.unwrap_or_else(chrono::Utc::now);
+            for child in children.iter().filter(|child| {
+                child.parent_node_id.as_deref() == Some(node_exec.node_id.as_str())
+                    && child.started_at >= node_exec.started_at
+                    && child.started_at <= finished_at
+            }) {
+                println!(
+                    "{}  Sub-workflow execution {} (workflow {}) - {}",
+                    indent, child.id, child.workflow_id, child.status
+                );
+                print_node_executions(pool, child.id, level + 2).await?;
+            }
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,262 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// State of a circuit breaker
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Calls go through and failures are counted
+    Closed,
+    /// Calls are rejected until the open timeout elapses
+    Open,
+    /// A limited number of probe calls decide whether to close or reopen
+    HalfOpen,
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "closed"),
+            CircuitState::Open => write!(f, "open"),
+            CircuitState::HalfOpen => write!(f, "half_open"),
+        }
+    }
+}
+
+impl std::convert::TryFrom<String> for CircuitState {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        match value.as_str() {
+            "closed" => Ok(CircuitState::Closed),
+            "open" => Ok(CircuitState::Open),
+            "half_open" => Ok(CircuitState::HalfOpen),
+            _ => Err(format!("Invalid circuit state: {}", value)),
+        }
+    }
+}
+
+/// Thresholds of a circuit breaker
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open a closed circuit
+    pub failure_threshold: u32,
+    /// Successful probes that close a half-open circuit
+    pub success_threshold: u32,
+    /// Time an open circuit waits before letting probes through
+    pub open_timeout: Duration,
+}
+
+/// Outcome of asking a circuit breaker to let a call through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// The circuit is closed
+    Allowed,
+    /// The circuit is half-open and the call is one of its probes
+    Probe,
+    /// The circuit is open; the call must not be made
+    Rejected,
+}
+
+/// Circuit breaker state shared across workflow executions, keyed by circuit id
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CircuitBreakerState {
+    pub circuit_id: String,
+    #[sqlx(try_from = "String")]
+    pub state: CircuitState,
+    pub failure_count: i32,
+    pub success_count: i32,
+    pub probes_in_flight: i32,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CircuitBreakerState {
+    pub fn new(circuit_id: String) -> Self {
+        Self {
+            circuit_id,
+            state: CircuitState::Closed,
+            failure_count: 0,
+            success_count: 0,
+            probes_in_flight: 0,
+            opened_at: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// Decide whether a call may go through, moving an open circuit to
+    /// half-open once its timeout has elapsed.
+    ///
+    /// A half-open circuit admits at most `success_threshold` probes at a time.
+    /// Probes that never reported back stop counting after another open timeout.
+    pub fn admit(&mut self, config: &CircuitBreakerConfig, now: DateTime<Utc>) -> Admission {
+        match self.state {
+            CircuitState::Closed => return Admission::Allowed,
+            CircuitState::Open => {
+                let opened_at = self.opened_at.unwrap_or(self.updated_at);
+                if now < opened_at + config.open_timeout {
+                    return Admission::Rejected;
+                }
+                self.state = CircuitState::HalfOpen;
+                self.success_count = 0;
+                self.probes_in_flight = 0;
+            }
+            CircuitState::HalfOpen => {
+                if now >= self.updated_at + config.open_timeout {
+                    self.probes_in_flight = 0;
+                }
+            }
+        }
+
+        if self.probes_in_flight >= config.success_threshold as i32 {
+            return Admission::Rejected;
+        }
+
+        self.probes_in_flight += 1;
+        self.updated_at = now;
+        Admission::Probe
+    }
+
+    /// Record a successful call admitted with `admission`
+    pub fn record_success(
+        &mut self,
+        config: &CircuitBreakerConfig,
+        admission: Admission,
+        now: DateTime<Utc>,
+    ) {
+        match (self.state, admission) {
+            (CircuitState::Closed, _) => self.failure_count = 0,
+            (CircuitState::HalfOpen, Admission::Probe) => {
+                self.probes_in_flight = (self.probes_in_flight - 1).max(0);
+                self.success_count += 1;
+                if self.success_count >= config.success_threshold as i32 {
+                    self.close();
+                }
+            }
+            // Outcomes of calls admitted before the circuit changed state are ignored
+            _ => return,
+        }
+        self.updated_at = now;
+    }
+
+    /// Record a failed call admitted with `admission`
+    pub fn record_failure(
+        &mut self,
+        config: &CircuitBreakerConfig,
+        admission: Admission,
+        now: DateTime<Utc>,
+    ) {
+        match (self.state, admission) {
+            (CircuitState::Closed, _) => {
+                self.failure_count += 1;
+                if self.failure_count >= config.failure_threshold as i32 {
+                    self.open(now);
+                }
+            }
+            (CircuitState::HalfOpen, Admission::Probe) => self.open(now),
+            _ => return,
+        }
+        self.updated_at = now;
+    }
+
+    fn open(&mut self, now: DateTime<Utc>) {
+        self.state = CircuitState::Open;
+        self.opened_at = Some(now);
+        self.success_count = 0;
+        self.probes_in_flight = 0;
+    }
+
+    fn close(&mut self) {
+        self.state = CircuitState::Closed;
+        self.opened_at = None;
+        self.failure_count = 0;
+        self.success_count = 0;
+        self.probes_in_flight = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CircuitBreakerConfig {
+        CircuitBreakerConfig {
+            failure_threshold: 3,
+            success_threshold: 2,
+            open_timeout: Duration::seconds(60),
+        }
+    }
+
+    #[test]
+    fn test_opens_after_failure_threshold() {
+        let config = config();
+        let now = Utc::now();
+        let mut circuit = CircuitBreakerState::new("api".to_string());
+
+        for _ in 0..2 {
+            assert_eq!(circuit.admit(&config, now), Admission::Allowed);
+            circuit.record_failure(&config, Admission::Allowed, now);
+        }
+        assert_eq!(circuit.state, CircuitState::Closed);
+
+        // A success resets the consecutive failure count
+        circuit.record_success(&config, Admission::Allowed, now);
+        assert_eq!(circuit.failure_count, 0);
+
+        for _ in 0..3 {
+            circuit.record_failure(&config, Admission::Allowed, now);
+        }
+        assert_eq!(circuit.state, CircuitState::Open);
+        assert_eq!(circuit.admit(&config, now), Admission::Rejected);
+    }
+
+    #[test]
+    fn test_half_open_probes_close_circuit() {
+        let config = config();
+        let now = Utc::now();
+        let mut circuit = CircuitBreakerState::new("api".to_string());
+        circuit.open(now);
+
+        let later = now + Duration::seconds(61);
+        assert_eq!(circuit.admit(&config, later), Admission::Probe);
+        assert_eq!(circuit.state, CircuitState::HalfOpen);
+        assert_eq!(circuit.admit(&config, later), Admission::Probe);
+        // Only success_threshold probes may be in flight
+        assert_eq!(circuit.admit(&config, later), Admission::Rejected);
+
+        circuit.record_success(&config, Admission::Probe, later);
+        assert_eq!(circuit.state, CircuitState::HalfOpen);
+        circuit.record_success(&config, Admission::Probe, later);
+        assert_eq!(circuit.state, CircuitState::Closed);
+        assert_eq!(circuit.admit(&config, later), Admission::Allowed);
+    }
+
+    #[test]
+    fn test_failed_probe_reopens_circuit() {
+        let config = config();
+        let now = Utc::now();
+        let mut circuit = CircuitBreakerState::new("api".to_string());
+        circuit.open(now);
+
+        let later = now + Duration::seconds(61);
+        assert_eq!(circuit.admit(&config, later), Admission::Probe);
+        circuit.record_failure(&config, Admission::Probe, later);
+
+        assert_eq!(circuit.state, CircuitState::Open);
+        assert_eq!(circuit.opened_at, Some(later));
+        assert_eq!(circuit.admit(&config, later), Admission::Rejected);
+    }
+
+    #[test]
+    fn test_circuit_state_round_trip() {
+        for state in [
+            CircuitState::Closed,
+            CircuitState::Open,
+            CircuitState::HalfOpen,
+        ] {
+            assert_eq!(CircuitState::try_from(state.to_string()), Ok(state));
+        }
+        assert!(CircuitState::try_from("broken".to_string()).is_err());
+    }
+}
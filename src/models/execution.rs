@@ -11,6 +11,24 @@ pub enum ExecutionStatus {
     Success,
     Failed,
     Cancelled,
+    /// Node was not executed because none of its incoming edges were taken
+    Skipped,
+    /// Execution is suspended at a `wait_webhook` node until it is resumed
+    Waiting,
+    /// Execution is queued until a worker takes it
+    Pending,
+    /// Node returned its pinned output instead of running
+    Pinned,
+}
+
+impl ExecutionStatus {
+    /// Whether the status is final, i.e. the execution will not make progress anymore
+    pub fn is_finished(&self) -> bool {
+        !matches!(
+            self,
+            ExecutionStatus::Running | ExecutionStatus::Waiting | ExecutionStatus::Pending
+        )
+    }
 }
 
 impl std::fmt::Display for ExecutionStatus {
@@ -20,6 +38,10 @@ impl std::fmt::Display for ExecutionStatus {
             ExecutionStatus::Success => write!(f, "success"),
             ExecutionStatus::Failed => write!(f, "failed"),
             ExecutionStatus::Cancelled => write!(f, "cancelled"),
+            ExecutionStatus::Skipped => write!(f, "skipped"),
+            ExecutionStatus::Waiting => write!(f, "waiting"),
+            ExecutionStatus::Pending => write!(f, "pending"),
+            ExecutionStatus::Pinned => write!(f, "pinned"),
         }
     }
 }
@@ -33,11 +55,33 @@ impl std::convert::TryFrom<String> for ExecutionStatus {
             "success" => Ok(ExecutionStatus::Success),
             "failed" => Ok(ExecutionStatus::Failed),
             "cancelled" => Ok(ExecutionStatus::Cancelled),
+            "skipped" => Ok(ExecutionStatus::Skipped),
+            "waiting" => Ok(ExecutionStatus::Waiting),
+            "pending" => Ok(ExecutionStatus::Pending),
+            "pinned" => Ok(ExecutionStatus::Pinned),
             _ => Err(format!("Invalid execution status: {}", value)),
         }
     }
 }
 
+/// Part of a workflow run by a partial execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialRun {
+    /// Run a single node
+    Only(String),
+    /// Run a node and every node it depends on
+    Until(String),
+}
+
+impl PartialRun {
+    /// The node the partial execution targets
+    pub fn node_id(&self) -> &str {
+        match self {
+            PartialRun::Only(node_id) | PartialRun::Until(node_id) => node_id,
+        }
+    }
+}
+
 /// Represents a workflow execution
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct WorkflowExecution {
@@ -54,6 +98,51 @@ pub struct WorkflowExecution {
     #[sqlx(json)]
     pub output_data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Set when cancellation was requested while the execution was running
+    #[serde(default)]
+    pub cancel_requested_at: Option<DateTime<Utc>>,
+    /// Last time the process running the execution reported that it is alive
+    #[serde(default)]
+    pub heartbeat_at: Option<DateTime<Utc>>,
+    /// Execution whose `execute_workflow` node started this one
+    #[serde(default)]
+    pub parent_execution_id: Option<Uuid>,
+    /// The `execute_workflow` node that started this execution
+    #[serde(default)]
+    pub parent_node_id: Option<String>,
+    /// Number of sub-workflow calls between this execution and the top-level one
+    #[serde(default)]
+    pub depth: i32,
+    /// Key identifying the request that started the execution; repeated
+    /// requests with the same key return this execution
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// End of the window in which the idempotency key is deduplicated
+    #[serde(default)]
+    pub idempotency_expires_at: Option<DateTime<Utc>>,
+    /// Execution this one re-runs
+    #[serde(default)]
+    pub rerun_of: Option<Uuid>,
+    /// Node the re-run starts from; nodes that do not depend on it keep the
+    /// results of the original execution. `None` re-runs every node.
+    #[serde(default)]
+    pub rerun_from_node: Option<String>,
+    /// Run nodes with pinned output for real, as production triggers do
+    #[serde(default)]
+    pub ignore_pinned_data: bool,
+    /// Node run on its own by a partial execution
+    #[serde(default)]
+    pub only_node: Option<String>,
+    /// Node run with its ancestors by a partial execution
+    #[serde(default)]
+    pub until_node: Option<String>,
+    /// Execution whose node outputs feed the nodes a partial execution runs
+    #[serde(default)]
+    pub seed_execution_id: Option<Uuid>,
+    /// Record what nodes with external side effects would have done instead
+    /// of running them
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl WorkflowExecution {
@@ -69,8 +158,101 @@ impl WorkflowExecution {
             input_data,
             output_data: None,
             error: None,
+            cancel_requested_at: None,
+            heartbeat_at: None,
+            parent_execution_id: None,
+            parent_node_id: None,
+            depth: 0,
+            idempotency_key: None,
+            idempotency_expires_at: None,
+            rerun_of: None,
+            rerun_from_node: None,
+            ignore_pinned_data: false,
+            only_node: None,
+            until_node: None,
+            seed_execution_id: None,
+            dry_run: false,
         }
     }
+
+    /// Create an execution that waits in the queue for a worker
+    pub fn pending(workflow_id: Uuid, input_data: Option<serde_json::Value>) -> Self {
+        let mut execution = Self::new(workflow_id, input_data);
+        execution.status = ExecutionStatus::Pending;
+        execution
+    }
+
+    /// Create a finished record for an execution that was not run
+    pub fn skipped(
+        workflow_id: Uuid,
+        input_data: Option<serde_json::Value>,
+        reason: String,
+    ) -> Self {
+        let mut execution = Self::new(workflow_id, input_data);
+        execution.status = ExecutionStatus::Skipped;
+        execution.finished_at = Some(execution.started_at);
+        execution.error = Some(reason);
+        execution
+    }
+
+    /// Mark the execution as a sub-workflow started by `node_id` of the
+    /// parent execution, which runs at `parent_depth`
+    pub fn with_parent(mut self, execution_id: Uuid, node_id: String, parent_depth: i32) -> Self {
+        self.parent_execution_id = Some(execution_id);
+        self.parent_node_id = Some(node_id);
+        self.depth = parent_depth + 1;
+        self
+    }
+
+    /// Run every node for real, ignoring the output pinned on nodes
+    pub fn without_pinned_data(mut self) -> Self {
+        self.ignore_pinned_data = true;
+        self
+    }
+
+    /// Mock the nodes with external side effects, running the others for real
+    pub fn as_dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Run only part of the workflow, feeding it the node outputs of
+    /// `seed_execution_id`
+    pub fn with_partial_run(
+        mut self,
+        partial: PartialRun,
+        seed_execution_id: Option<Uuid>,
+    ) -> Self {
+        match partial {
+            PartialRun::Only(node_id) => self.only_node = Some(node_id),
+            PartialRun::Until(node_id) => self.until_node = Some(node_id),
+        }
+        self.seed_execution_id = seed_execution_id;
+        self
+    }
+
+    /// Part of the workflow the execution runs, or `None` for all of it
+    pub fn partial_run(&self) -> Option<PartialRun> {
+        match (&self.only_node, &self.until_node) {
+            (Some(node_id), _) => Some(PartialRun::Only(node_id.clone())),
+            (None, Some(node_id)) => Some(PartialRun::Until(node_id.clone())),
+            (None, None) => None,
+        }
+    }
+
+    /// Mark the execution as a re-run of `execution_id` starting from `from_node`
+    pub fn with_rerun_of(mut self, execution_id: Uuid, from_node: Option<String>) -> Self {
+        self.rerun_of = Some(execution_id);
+        self.rerun_from_node = from_node;
+        self
+    }
+
+    /// Deduplicate the execution by `key` until `expires_at`
+    pub fn with_idempotency_key(mut self, key: String, expires_at: DateTime<Utc>) -> Self {
+        self.idempotency_key = Some(key);
+        self.idempotency_expires_at = Some(expires_at);
+        self
+    }
 }
 
 /// Represents a node execution within a workflow execution
@@ -90,6 +272,26 @@ pub struct NodeExecution {
     #[sqlx(json)]
     pub output_data: Option<serde_json::Value>,
     pub error: Option<String>,
+    /// Iteration index when the node ran inside a loop or split body
+    #[serde(default)]
+    pub iteration: Option<i32>,
+    /// Key of the split branch the node ran in
+    #[serde(default)]
+    pub branch: Option<String>,
+    /// Attempt number (1-based) when the node is retried
+    #[serde(default = "default_attempt")]
+    pub attempt: i32,
+    /// Output port emitted by the node, used to rebuild routing on recovery
+    #[serde(default)]
+    pub output_port: Option<String>,
+    /// Workflow variables set by the node
+    #[sqlx(json)]
+    #[serde(default)]
+    pub variables: Option<serde_json::Value>,
+}
+
+fn default_attempt() -> i32 {
+    1
 }
 
 impl NodeExecution {
@@ -106,8 +308,95 @@ impl NodeExecution {
             input_data: None,
             output_data: None,
             error: None,
+            iteration: None,
+            branch: None,
+            attempt: 1,
+            output_port: None,
+            variables: None,
         }
     }
+
+    /// Set the attempt number of the record
+    pub fn with_attempt(mut self, attempt: i32) -> Self {
+        self.attempt = attempt;
+        self
+    }
+
+    /// Tag the record with the loop iteration it belongs to
+    pub fn with_iteration(mut self, iteration: Option<i32>) -> Self {
+        self.iteration = iteration;
+        self
+    }
+
+    /// Tag the record with the split branch it belongs to
+    pub fn with_branch(mut self, branch: Option<String>) -> Self {
+        self.branch = branch;
+        self
+    }
+
+    /// Create a finished record for a node that was skipped by branch routing
+    pub fn skipped(execution_id: Uuid, node_id: String) -> Self {
+        let mut execution = Self::new(execution_id, node_id);
+        execution.status = ExecutionStatus::Skipped;
+        execution.finished_at = Some(execution.started_at);
+        execution
+    }
+
+    /// Create a finished record for a node that failed without running
+    pub fn failed(execution_id: Uuid, node_id: String, error: String) -> Self {
+        let mut execution = Self::new(execution_id, node_id);
+        execution.status = ExecutionStatus::Failed;
+        execution.finished_at = Some(execution.started_at);
+        execution.error = Some(error);
+        execution
+    }
+
+    /// Create a finished record for a node that returned its pinned output
+    pub fn pinned(execution_id: Uuid, node_id: String, output: serde_json::Value) -> Self {
+        let mut execution = Self::new(execution_id, node_id);
+        execution.status = ExecutionStatus::Pinned;
+        execution.finished_at = Some(execution.started_at);
+        execution.output_data = Some(output);
+        execution
+    }
+
+    /// Create a finished record for a node that never ran because the execution was cancelled
+    pub fn cancelled(execution_id: Uuid, node_id: String) -> Self {
+        let mut execution = Self::new(execution_id, node_id);
+        execution.status = ExecutionStatus::Cancelled;
+        execution.finished_at = Some(execution.started_at);
+        execution.error = Some("Execution was cancelled".to_string());
+        execution
+    }
+}
+
+/// State of an execution suspended at a `wait_webhook` node
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExecutionWait {
+    pub wait_id: String,
+    pub execution_id: Uuid,
+    /// The `wait_webhook` node the execution resumes from
+    pub node_id: String,
+    /// Snapshot of the run: node outputs, taken ports and workflow variables
+    #[sqlx(json)]
+    pub state: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// Output of the wait node, set when the wait is resumed
+    #[serde(default)]
+    #[sqlx(json)]
+    pub payload: Option<serde_json::Value>,
+    /// When the wait was resumed; the execution is then queued until a
+    /// worker continues it
+    #[serde(default)]
+    pub resumed_at: Option<DateTime<Utc>>,
+}
+
+impl ExecutionWait {
+    /// Whether the wait has passed its deadline
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +409,22 @@ mod tests {
         assert_eq!(ExecutionStatus::Success.to_string(), "success");
         assert_eq!(ExecutionStatus::Failed.to_string(), "failed");
         assert_eq!(ExecutionStatus::Cancelled.to_string(), "cancelled");
+        assert_eq!(ExecutionStatus::Skipped.to_string(), "skipped");
+        assert_eq!(ExecutionStatus::Waiting.to_string(), "waiting");
+        assert_eq!(ExecutionStatus::Pending.to_string(), "pending");
+        assert_eq!(ExecutionStatus::Pinned.to_string(), "pinned");
+    }
+
+    #[test]
+    fn test_execution_status_is_finished() {
+        assert!(!ExecutionStatus::Running.is_finished());
+        assert!(!ExecutionStatus::Waiting.is_finished());
+        assert!(!ExecutionStatus::Pending.is_finished());
+        assert!(ExecutionStatus::Success.is_finished());
+        assert!(ExecutionStatus::Failed.is_finished());
+        assert!(ExecutionStatus::Cancelled.is_finished());
+        assert!(ExecutionStatus::Skipped.is_finished());
+        assert!(ExecutionStatus::Pinned.is_finished());
     }
 
     #[test]
@@ -140,6 +445,22 @@ mod tests {
             ExecutionStatus::try_from("cancelled".to_string()),
             Ok(ExecutionStatus::Cancelled)
         ));
+        assert!(matches!(
+            ExecutionStatus::try_from("skipped".to_string()),
+            Ok(ExecutionStatus::Skipped)
+        ));
+        assert!(matches!(
+            ExecutionStatus::try_from("waiting".to_string()),
+            Ok(ExecutionStatus::Waiting)
+        ));
+        assert!(matches!(
+            ExecutionStatus::try_from("pending".to_string()),
+            Ok(ExecutionStatus::Pending)
+        ));
+        assert!(matches!(
+            ExecutionStatus::try_from("pinned".to_string()),
+            Ok(ExecutionStatus::Pinned)
+        ));
         assert!(ExecutionStatus::try_from("invalid".to_string()).is_err());
     }
 
@@ -162,6 +483,31 @@ mod tests {
         assert!(diff < 2, "updated_at should be close to current time");
     }
 
+    #[test]
+    fn test_workflow_execution_pending() {
+        let execution = WorkflowExecution::pending(Uuid::new_v4(), None);
+
+        assert_eq!(execution.status, ExecutionStatus::Pending);
+        assert!(execution.finished_at.is_none());
+    }
+
+    #[test]
+    fn test_workflow_execution_partial_run() {
+        let execution = WorkflowExecution::pending(Uuid::new_v4(), None);
+        assert_eq!(execution.partial_run(), None);
+
+        let seed = Uuid::new_v4();
+        let execution =
+            execution.with_partial_run(PartialRun::Until("llm".to_string()), Some(seed));
+        assert_eq!(execution.until_node.as_deref(), Some("llm"));
+        assert_eq!(execution.seed_execution_id, Some(seed));
+        assert_eq!(
+            execution.partial_run(),
+            Some(PartialRun::Until("llm".to_string()))
+        );
+        assert_eq!(execution.partial_run().unwrap().node_id(), "llm");
+    }
+
     #[test]
     fn test_node_execution_new() {
         let execution_id = Uuid::new_v4();
@@ -182,6 +528,79 @@ mod tests {
         assert!(diff < 2, "updated_at should be close to current time");
     }
 
+    #[test]
+    fn test_node_execution_skipped() {
+        let node_exec = NodeExecution::skipped(Uuid::new_v4(), "branch".to_string());
+
+        assert_eq!(node_exec.status, ExecutionStatus::Skipped);
+        assert_eq!(node_exec.finished_at, Some(node_exec.started_at));
+        assert!(node_exec.output_data.is_none());
+    }
+
+    #[test]
+    fn test_node_execution_pinned() {
+        let output = serde_json::json!({"summary": "cached"});
+        let node_exec = NodeExecution::pinned(Uuid::new_v4(), "llm".to_string(), output.clone());
+
+        assert_eq!(node_exec.status, ExecutionStatus::Pinned);
+        assert_eq!(node_exec.finished_at, Some(node_exec.started_at));
+        assert_eq!(node_exec.output_data, Some(output));
+    }
+
+    #[test]
+    fn test_node_execution_cancelled() {
+        let node_exec = NodeExecution::cancelled(Uuid::new_v4(), "pending".to_string());
+
+        assert_eq!(node_exec.status, ExecutionStatus::Cancelled);
+        assert_eq!(node_exec.finished_at, Some(node_exec.started_at));
+        assert!(node_exec.error.is_some());
+    }
+
+    #[test]
+    fn test_node_execution_with_iteration() {
+        let node_exec =
+            NodeExecution::new(Uuid::new_v4(), "body".to_string()).with_iteration(Some(2));
+        assert_eq!(node_exec.iteration, Some(2));
+
+        let node_exec = NodeExecution::new(Uuid::new_v4(), "body".to_string());
+        assert_eq!(node_exec.iteration, None);
+    }
+
+    #[test]
+    fn test_workflow_execution_with_parent() {
+        let parent = WorkflowExecution::new(Uuid::new_v4(), None);
+        assert_eq!(parent.depth, 0);
+        assert!(parent.parent_execution_id.is_none());
+
+        let child = WorkflowExecution::pending(Uuid::new_v4(), None).with_parent(
+            parent.id,
+            "call_child".to_string(),
+            parent.depth,
+        );
+        assert_eq!(child.parent_execution_id, Some(parent.id));
+        assert_eq!(child.parent_node_id.as_deref(), Some("call_child"));
+        assert_eq!(child.depth, 1);
+        assert_eq!(child.status, ExecutionStatus::Pending);
+    }
+
+    #[test]
+    fn test_node_execution_with_branch() {
+        let node_exec = NodeExecution::new(Uuid::new_v4(), "body".to_string())
+            .with_iteration(Some(1))
+            .with_branch(Some("order-42".to_string()));
+        assert_eq!(node_exec.iteration, Some(1));
+        assert_eq!(node_exec.branch.as_deref(), Some("order-42"));
+    }
+
+    #[test]
+    fn test_node_execution_with_attempt() {
+        let node_exec = NodeExecution::new(Uuid::new_v4(), "fetch".to_string());
+        assert_eq!(node_exec.attempt, 1);
+
+        let node_exec = node_exec.with_attempt(3);
+        assert_eq!(node_exec.attempt, 3);
+    }
+
     #[test]
     fn test_workflow_execution_tracks_updates() {
         let workflow_id = Uuid::new_v4();
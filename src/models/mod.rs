@@ -1,4 +1,5 @@
 pub mod audit;
+pub mod circuit_breaker;
 pub mod credential_type;
 pub mod credentials;
 pub mod execution;
@@ -7,6 +8,7 @@ pub mod rbac;
 pub mod workflow;
 
 pub use audit::*;
+pub use circuit_breaker::*;
 pub use credential_type::*;
 pub use credentials::*;
 pub use execution::*;
@@ -9,6 +9,14 @@ pub struct NodeOutput {
     pub data: serde_json::Value,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Output port taken by the node (e.g. "true"/"false" for conditionals).
+    /// Only edges whose `from_output` is empty or matches this port are followed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<String>,
+    /// Workflow variables set by the node.
+    /// The engine merges these into the shared variables seen by later nodes.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, serde_json::Value>,
 }
 
 impl NodeOutput {
@@ -17,6 +25,8 @@ impl NodeOutput {
             success: true,
             data,
             error: None,
+            port: None,
+            variables: HashMap::new(),
         }
     }
 
@@ -25,8 +35,22 @@ impl NodeOutput {
             success: false,
             data: serde_json::Value::Null,
             error: Some(message),
+            port: None,
+            variables: HashMap::new(),
         }
     }
+
+    /// Set the output port taken by the node
+    pub fn with_port(mut self, port: impl Into<String>) -> Self {
+        self.port = Some(port.into());
+        self
+    }
+
+    /// Set a workflow variable that later nodes will see
+    pub fn with_variable(mut self, key: impl Into<String>, value: serde_json::Value) -> Self {
+        self.variables.insert(key.into(), value);
+        self
+    }
 }
 
 /// Context passed to nodes during execution
@@ -38,8 +62,20 @@ pub struct NodeContext {
     pub node_id: String,
     /// Input data from previous nodes
     pub inputs: HashMap<String, serde_json::Value>,
+    /// Keys of `inputs` in the order they were added
+    #[serde(default)]
+    pub input_order: Vec<String>,
     /// Global workflow variables
     pub variables: HashMap<String, serde_json::Value>,
+    /// Sub-workflow depth of the execution, 0 for a top-level execution
+    #[serde(default)]
+    pub depth: i32,
+    /// Whether the execution runs nodes with pinned output for real
+    #[serde(default)]
+    pub ignore_pinned_data: bool,
+    /// Whether nodes with external side effects are mocked
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl NodeContext {
@@ -48,7 +84,11 @@ impl NodeContext {
             execution_id,
             node_id,
             inputs: HashMap::new(),
+            input_order: Vec::new(),
             variables: HashMap::new(),
+            depth: 0,
+            ignore_pinned_data: false,
+            dry_run: false,
         }
     }
 
@@ -64,7 +104,31 @@ impl NodeContext {
 
     /// Add input data
     pub fn add_input(&mut self, key: String, value: serde_json::Value) {
-        self.inputs.insert(key, value);
+        if self.inputs.insert(key.clone(), value).is_none() {
+            self.input_order.push(key);
+        }
+    }
+
+    /// Inputs in the order they were added
+    pub fn ordered_inputs(&self) -> Vec<(&String, &serde_json::Value)> {
+        let mut keys: Vec<&String> = self
+            .input_order
+            .iter()
+            .filter(|key| self.inputs.contains_key(*key))
+            .collect();
+
+        // Inputs inserted into the map directly come last, by key
+        let mut unordered: Vec<&String> = self
+            .inputs
+            .keys()
+            .filter(|key| !self.input_order.contains(key))
+            .collect();
+        unordered.sort();
+        keys.extend(unordered);
+
+        keys.into_iter()
+            .map(|key| (key, &self.inputs[key]))
+            .collect()
     }
 
     /// Get a workflow variable
@@ -146,6 +210,35 @@ pub trait NodeType {
         })
     }
 
+    /// Get the JSON schema for the data the node outputs on success.
+    /// Dry runs output a placeholder of this shape for nodes with side effects.
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": true
+        })
+    }
+
+    /// Whether running the node with these parameters has effects outside
+    /// the workflow (messages sent, records or files written, paid API calls).
+    /// Dry runs mock these nodes.
+    ///
+    /// The subcategory only sets the default: nodes in other subcategories
+    /// that call external services override this, and nodes with read
+    /// operations can look at the `operation` parameter so reads still run.
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        self.category() == NodeCategory::Action
+            && matches!(
+                self.subcategory(),
+                NodeSubcategory::Communication
+                    | NodeSubcategory::Storage
+                    | NodeSubcategory::Database
+                    | NodeSubcategory::AI
+                    | NodeSubcategory::Http
+            )
+    }
+
     /// Get the required credential type name for this node
     /// Returns None if the node doesn't require credentials
     /// Returns Some(credential_type_name) if the node requires a specific credential type
@@ -244,6 +337,26 @@ mod tests {
         assert!(main_input.is_some());
     }
 
+    #[test]
+    fn test_node_context_ordered_inputs() {
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+
+        context.add_input("zeta".to_string(), serde_json::json!(1));
+        context.add_input("alpha".to_string(), serde_json::json!(2));
+        context.add_input("zeta".to_string(), serde_json::json!(3));
+        context
+            .inputs
+            .insert("beta".to_string(), serde_json::json!(4));
+
+        let keys: Vec<&str> = context
+            .ordered_inputs()
+            .into_iter()
+            .map(|(key, _)| key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["zeta", "alpha", "beta"]);
+        assert_eq!(context.inputs["zeta"], serde_json::json!(3));
+    }
+
     #[test]
     fn test_node_context_variable_operations() {
         let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
@@ -274,6 +387,31 @@ mod tests {
         assert_eq!(output.data, serde_json::Value::Null);
     }
 
+    #[test]
+    fn test_node_output_with_port() {
+        let output = NodeOutput::success(serde_json::json!({})).with_port("true");
+        assert_eq!(output.port, Some("true".to_string()));
+
+        let json = serde_json::to_value(&output).unwrap();
+        assert_eq!(json["port"], "true");
+
+        let plain = serde_json::to_value(NodeOutput::success(serde_json::json!({}))).unwrap();
+        assert!(plain.get("port").is_none());
+    }
+
+    #[test]
+    fn test_node_output_with_variable() {
+        let output = NodeOutput::success(serde_json::json!({}))
+            .with_variable("count", serde_json::json!(3))
+            .with_variable("status", serde_json::json!("ok"));
+
+        assert_eq!(output.variables.len(), 2);
+        assert_eq!(output.variables["count"], serde_json::json!(3));
+
+        let plain = serde_json::to_value(NodeOutput::success(serde_json::json!({}))).unwrap();
+        assert!(plain.get("variables").is_none());
+    }
+
     struct TestNode;
 
     impl NodeType for TestNode {
@@ -335,6 +473,7 @@ mod tests {
         // Test that test node has correct category
         let node = TestNode;
         assert_eq!(node.category(), NodeCategory::Action);
+        assert!(!node.has_side_effects(&serde_json::json!({})));
     }
 
     // Test trigger node
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Execution mode for workflow nodes
@@ -14,6 +15,24 @@ pub enum ExecutionMode {
     Parallel,
 }
 
+/// Default window in which repeated idempotency keys are deduplicated
+pub const DEFAULT_IDEMPOTENCY_WINDOW_SECONDS: u64 = 24 * 60 * 60;
+
+/// What happens to a new execution of a workflow that already runs its
+/// maximum number of concurrent executions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverlapPolicy {
+    /// Start it anyway; the limit is not enforced
+    Allow,
+    /// Queue it until a running execution finishes
+    Queue,
+    /// Record it as skipped without running it
+    Skip,
+    /// Cancel the oldest running executions and queue it until they stopped
+    CancelPrevious,
+}
+
 /// Represents a workflow definition
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Workflow {
@@ -26,12 +45,24 @@ pub struct Workflow {
     pub nodes: serde_json::Value,
     #[sqlx(json)]
     pub edges: serde_json::Value,
+    /// Workflow-level settings of the definition (execution mode, timeouts, ...)
+    #[sqlx(json)]
+    #[serde(default = "empty_settings")]
+    pub settings: serde_json::Value,
     #[serde(default = "Utc::now")]
     pub created_at: DateTime<Utc>,
     #[serde(default = "Utc::now")]
     pub updated_at: DateTime<Utc>,
 }
 
+fn empty_settings() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// Top-level fields of a definition that are stored in their own columns
+/// rather than in `settings`
+const DEFINITION_COLUMNS: [&str; 4] = ["name", "description", "nodes", "edges"];
+
 /// Represents a workflow definition in YAML format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowDefinition {
@@ -44,6 +75,28 @@ pub struct WorkflowDefinition {
     pub execution_mode: ExecutionMode,
     /// Global timeout in seconds (optional)
     pub timeout_seconds: Option<u64>,
+    /// Maximum number of nodes running at the same time in parallel mode (unlimited if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrency: Option<usize>,
+    /// Maximum number of executions running at the same time (1 if only
+    /// `overlap_policy` is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_executions: Option<usize>,
+    /// What happens to executions over the limit (`queue` if only
+    /// `max_concurrent_executions` is set)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlap_policy: Option<OverlapPolicy>,
+    /// Workflows sharing a key share the limit on concurrent executions
+    /// (defaults to the workflow's own ID)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub concurrency_key: Option<String>,
+    /// How long an idempotency key deduplicates execution starts, in seconds
+    /// (defaults to 24 hours)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub idempotency_window_seconds: Option<u64>,
+    /// Name of a workflow to queue when an execution of this one fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_workflow: Option<String>,
 }
 
 /// Represents a node in the workflow
@@ -56,6 +109,147 @@ pub struct NodeDefinition {
     pub parameters: serde_json::Value,
     /// Node-specific timeout in seconds (overrides workflow timeout)
     pub timeout_seconds: Option<u64>,
+    /// Nested body nodes, run by the engine once per item of a `loop` node or branch of a `split` node
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<NodeDefinition>,
+    /// Edges between the nested body nodes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub edges: Vec<EdgeDefinition>,
+    /// Retry policy applied by the engine when the node fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetryPolicy>,
+    /// Whether the node can safely run again after an interrupted attempt
+    #[serde(default = "default_idempotent", skip_serializing_if = "is_idempotent")]
+    pub idempotent: bool,
+    /// Output returned instead of running the node, unless the execution
+    /// ignores pinned data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned_output: Option<serde_json::Value>,
+    /// A disabled node does not run and passes its input through unchanged
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+fn default_idempotent() -> bool {
+    true
+}
+
+fn is_idempotent(idempotent: &bool) -> bool {
+    *idempotent
+}
+
+impl NodeDefinition {
+    /// Find a node by ID among `nodes` and their nested bodies
+    pub fn find<'a>(nodes: &'a [NodeDefinition], id: &str) -> Option<&'a NodeDefinition> {
+        nodes.iter().find_map(|node| {
+            if node.id == id {
+                Some(node)
+            } else {
+                Self::find(&node.nodes, id)
+            }
+        })
+    }
+
+    /// Find a node by ID among `nodes` and their nested bodies, for editing
+    pub fn find_mut<'a>(
+        nodes: &'a mut [NodeDefinition],
+        id: &str,
+    ) -> Option<&'a mut NodeDefinition> {
+        nodes.iter_mut().find_map(|node| {
+            if node.id == id {
+                Some(node)
+            } else {
+                Self::find_mut(&mut node.nodes, id)
+            }
+        })
+    }
+}
+
+/// Class of a node failure, used to decide whether to retry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorClass {
+    /// The node exceeded its timeout
+    Timeout,
+    /// The node returned an error
+    Error,
+    /// The node ran but reported an unsuccessful output
+    Failure,
+}
+
+/// Engine-level retry policy with exponential backoff
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds
+    #[serde(default = "default_initial_delay_ms")]
+    pub initial_delay_ms: u64,
+    /// Factor applied to the delay after each retry
+    #[serde(default = "default_multiplier")]
+    pub multiplier: f64,
+    /// Upper bound for the delay in milliseconds
+    #[serde(default = "default_max_delay_ms")]
+    pub max_delay_ms: u64,
+    /// Random fraction (0.0-1.0) of the delay that is added or removed
+    #[serde(default)]
+    pub jitter: f64,
+    /// Error classes that trigger a retry (all classes when empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub retry_on: Vec<ErrorClass>,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_initial_delay_ms() -> u64 {
+    1000
+}
+
+fn default_multiplier() -> f64 {
+    2.0
+}
+
+fn default_max_delay_ms() -> u64 {
+    30000
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_delay_ms: default_initial_delay_ms(),
+            multiplier: default_multiplier(),
+            max_delay_ms: default_max_delay_ms(),
+            jitter: 0.0,
+            retry_on: Vec::new(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Whether a failure of the given class should be retried
+    pub fn retries(&self, class: ErrorClass) -> bool {
+        self.retry_on.is_empty() || self.retry_on.contains(&class)
+    }
+
+    /// Delay to wait after the given (1-based) failed attempt
+    pub fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let delay = (self.initial_delay_ms as f64 * self.multiplier.powi(exponent))
+            .min(self.max_delay_ms as f64);
+
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let delay = if jitter > 0.0 {
+            delay * (1.0 + jitter * (rand::random::<f64>() * 2.0 - 1.0))
+        } else {
+            delay
+        };
+
+        Duration::from_millis(delay.max(0.0) as u64)
+    }
 }
 
 /// Represents an edge connecting two nodes
@@ -73,6 +267,7 @@ impl WorkflowDefinition {
     /// Convert to a Workflow entity for database storage
     pub fn to_workflow(&self) -> anyhow::Result<Workflow> {
         Ok(Workflow {
+            settings: self.settings()?,
             id: Uuid::new_v4(),
             name: self.name.clone(),
             description: self.description.clone(),
@@ -83,19 +278,55 @@ impl WorkflowDefinition {
             updated_at: Utc::now(),
         })
     }
+
+    /// Limit on concurrent executions and the policy applied over it, or
+    /// `None` when executions may overlap freely
+    pub fn execution_limit(&self) -> Option<(usize, OverlapPolicy)> {
+        let policy = match (self.overlap_policy, self.max_concurrent_executions) {
+            (Some(policy), _) => policy,
+            (None, Some(_)) => OverlapPolicy::Queue,
+            (None, None) => OverlapPolicy::Allow,
+        };
+        if policy == OverlapPolicy::Allow {
+            return None;
+        }
+
+        Some((self.max_concurrent_executions.unwrap_or(1).max(1), policy))
+    }
+
+    /// How long an idempotency key deduplicates execution starts
+    pub fn idempotency_window(&self) -> chrono::Duration {
+        let seconds = self
+            .idempotency_window_seconds
+            .unwrap_or(DEFAULT_IDEMPOTENCY_WINDOW_SECONDS);
+        chrono::Duration::seconds(seconds as i64)
+    }
+
+    /// Every top-level setting of the definition, keyed by its YAML name
+    pub fn settings(&self) -> anyhow::Result<serde_json::Value> {
+        let mut settings = serde_json::to_value(self)?;
+        if let Some(fields) = settings.as_object_mut() {
+            for column in DEFINITION_COLUMNS {
+                fields.remove(column);
+            }
+        }
+        Ok(settings)
+    }
 }
 
 impl Workflow {
     /// Convert to WorkflowDefinition for execution
     pub fn to_definition(&self) -> anyhow::Result<WorkflowDefinition> {
-        Ok(WorkflowDefinition {
-            name: self.name.clone(),
-            description: self.description.clone(),
-            nodes: serde_json::from_value(self.nodes.clone())?,
-            edges: serde_json::from_value(self.edges.clone())?,
-            execution_mode: ExecutionMode::Sequential, // Default to sequential
-            timeout_seconds: None,
-        })
+        let mut definition = match &self.settings {
+            serde_json::Value::Object(settings) => settings.clone(),
+            _ => serde_json::Map::new(),
+        };
+        definition.insert("name".into(), self.name.clone().into());
+        definition.insert("description".into(), self.description.clone().into());
+        definition.insert("nodes".into(), self.nodes.clone());
+        definition.insert("edges".into(), self.edges.clone());
+
+        Ok(serde_json::from_value(definition.into())?)
     }
 }
 
@@ -114,10 +345,22 @@ mod tests {
                 name: "Start".to_string(),
                 parameters: serde_json::json!({}),
                 timeout_seconds: None,
+                nodes: vec![],
+                edges: vec![],
+                retry: None,
+                idempotent: true,
+                pinned_output: None,
+                disabled: false,
             }],
             edges: vec![],
             execution_mode: ExecutionMode::Sequential,
             timeout_seconds: None,
+            max_concurrency: None,
+            max_concurrent_executions: None,
+            overlap_policy: None,
+            concurrency_key: None,
+            idempotency_window_seconds: None,
+            error_workflow: None,
         };
 
         let workflow = def.to_workflow().unwrap();
@@ -140,6 +383,7 @@ mod tests {
                 "parameters": {}
             }]),
             edges: serde_json::json!([]),
+            settings: serde_json::json!({}),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -196,6 +440,12 @@ mod tests {
                     name: "Fast Node".to_string(),
                     parameters: serde_json::json!({}),
                     timeout_seconds: Some(5),
+                    nodes: vec![],
+                    edges: vec![],
+                    retry: None,
+                    idempotent: true,
+                    pinned_output: None,
+                    disabled: false,
                 },
                 NodeDefinition {
                     id: "node2".to_string(),
@@ -203,11 +453,23 @@ mod tests {
                     name: "Regular Node".to_string(),
                     parameters: serde_json::json!({}),
                     timeout_seconds: None,
+                    nodes: vec![],
+                    edges: vec![],
+                    retry: None,
+                    idempotent: true,
+                    pinned_output: None,
+                    disabled: false,
                 },
             ],
             edges: vec![],
             execution_mode: ExecutionMode::Parallel,
             timeout_seconds: Some(30),
+            max_concurrency: Some(4),
+            max_concurrent_executions: Some(2),
+            overlap_policy: Some(OverlapPolicy::Skip),
+            concurrency_key: Some("billing-api".to_string()),
+            idempotency_window_seconds: Some(3600),
+            error_workflow: Some("Notify On-call".to_string()),
         };
 
         let workflow = def.to_workflow().unwrap();
@@ -217,10 +479,175 @@ mod tests {
         assert_eq!(restored_def.nodes[0].timeout_seconds, Some(5));
         assert_eq!(restored_def.nodes[1].timeout_seconds, None);
 
-        // Note: execution_mode and timeout_seconds are not stored in the Workflow table,
-        // so they default when converting back to WorkflowDefinition
-        assert_eq!(restored_def.execution_mode, ExecutionMode::Sequential);
-        assert_eq!(restored_def.timeout_seconds, None);
+        // Workflow-level settings are preserved in the settings JSON
+        assert_eq!(restored_def.execution_mode, ExecutionMode::Parallel);
+        assert_eq!(restored_def.timeout_seconds, Some(30));
+        assert_eq!(restored_def.max_concurrency, Some(4));
+        assert_eq!(
+            restored_def.execution_limit(),
+            Some((2, OverlapPolicy::Skip))
+        );
+        assert_eq!(restored_def.concurrency_key.as_deref(), Some("billing-api"));
+        assert_eq!(
+            restored_def.idempotency_window(),
+            chrono::Duration::hours(1)
+        );
+        assert_eq!(
+            restored_def.error_workflow.as_deref(),
+            Some("Notify On-call")
+        );
+    }
+
+    #[test]
+    fn test_error_workflow_survives_reexport() {
+        let yaml = r#"
+name: Billing Sync
+error_workflow: Notify On-call
+nodes:
+  - id: start
+    node_type: start
+    name: Start
+edges: []
+"#;
+
+        // Import stores the setting, export writes the definition back out
+        let workflow = crate::config::load_workflow_from_str(yaml)
+            .unwrap()
+            .to_workflow()
+            .unwrap();
+        assert_eq!(
+            workflow.settings["error_workflow"],
+            serde_json::json!("Notify On-call")
+        );
+        let exported = serde_yaml::to_string(&workflow.to_definition().unwrap()).unwrap();
+        assert!(exported.contains("error_workflow: Notify On-call"));
+
+        let reimported = crate::config::load_workflow_from_str(&exported).unwrap();
+        assert_eq!(reimported.error_workflow.as_deref(), Some("Notify On-call"));
+    }
+
+    #[test]
+    fn test_execution_limit_defaults() {
+        let mut def: WorkflowDefinition =
+            serde_yaml::from_str("name: Limited\nnodes: []\nedges: []\n").unwrap();
+        assert_eq!(def.execution_limit(), None);
+        assert_eq!(def.idempotency_window(), chrono::Duration::hours(24));
+
+        def.max_concurrent_executions = Some(3);
+        assert_eq!(def.execution_limit(), Some((3, OverlapPolicy::Queue)));
+
+        def.max_concurrent_executions = None;
+        def.overlap_policy = Some(OverlapPolicy::CancelPrevious);
+        assert_eq!(
+            def.execution_limit(),
+            Some((1, OverlapPolicy::CancelPrevious))
+        );
+
+        def.max_concurrent_executions = Some(3);
+        def.overlap_policy = Some(OverlapPolicy::Allow);
+        assert_eq!(def.execution_limit(), None);
+
+        let def: WorkflowDefinition = serde_yaml::from_str(
+            "name: Limited\noverlap_policy: cancel_previous\nnodes: []\nedges: []\n",
+        )
+        .unwrap();
+        assert_eq!(def.overlap_policy, Some(OverlapPolicy::CancelPrevious));
+    }
+
+    #[test]
+    fn test_workflow_settings_exclude_columns() {
+        let def: WorkflowDefinition = serde_yaml::from_str(
+            r#"
+name: Settings Workflow
+description: Stored in its own column
+execution_mode: parallel
+nodes: []
+edges: []
+"#,
+        )
+        .unwrap();
+
+        let workflow = def.to_workflow().unwrap();
+        assert_eq!(
+            workflow.settings,
+            serde_json::json!({
+                "execution_mode": "parallel",
+                "timeout_seconds": null
+            })
+        );
+        assert_eq!(workflow.description, def.description);
+    }
+
+    #[test]
+    fn test_pinned_output() {
+        let yaml = r#"
+name: Summaries
+nodes:
+  - id: loop
+    node_type: loop
+    name: Loop
+    nodes:
+      - id: summarize
+        node_type: openai
+        name: Summarize
+        pinned_output:
+          summary: "A short summary"
+edges: []
+"#;
+        let mut def: WorkflowDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            NodeDefinition::find(&def.nodes, "summarize")
+                .unwrap()
+                .pinned_output,
+            Some(serde_json::json!({"summary": "A short summary"}))
+        );
+
+        NodeDefinition::find_mut(&mut def.nodes, "summarize")
+            .unwrap()
+            .pinned_output = None;
+        let nodes = serde_json::to_value(&def.nodes).unwrap();
+        assert!(nodes[0]["nodes"][0].get("pinned_output").is_none());
+        assert!(NodeDefinition::find_mut(&mut def.nodes, "missing").is_none());
+    }
+
+    #[test]
+    fn test_retry_policy_defaults() {
+        let policy: RetryPolicy = serde_yaml::from_str("max_attempts: 5").unwrap();
+        assert_eq!(policy.max_attempts, 5);
+        assert_eq!(policy.initial_delay_ms, 1000);
+        assert_eq!(policy.multiplier, 2.0);
+        assert!(policy.retries(ErrorClass::Timeout));
+        assert!(policy.retries(ErrorClass::Failure));
+    }
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy {
+            initial_delay_ms: 100,
+            multiplier: 3.0,
+            max_delay_ms: 1000,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(policy.delay_for_attempt(2), Duration::from_millis(300));
+        assert_eq!(policy.delay_for_attempt(3), Duration::from_millis(900));
+        assert_eq!(policy.delay_for_attempt(4), Duration::from_millis(1000));
+
+        let jittered = RetryPolicy {
+            jitter: 0.5,
+            ..policy
+        };
+        let delay = jittered.delay_for_attempt(2).as_millis();
+        assert!((150..=450).contains(&delay));
+    }
+
+    #[test]
+    fn test_retry_policy_retry_on() {
+        let policy: RetryPolicy = serde_yaml::from_str("retry_on: [timeout]").unwrap();
+        assert!(policy.retries(ErrorClass::Timeout));
+        assert!(!policy.retries(ErrorClass::Error));
+        assert!(!policy.retries(ErrorClass::Failure));
     }
 
     #[test]
@@ -234,10 +661,22 @@ mod tests {
                 name: "Start".to_string(),
                 parameters: serde_json::json!({}),
                 timeout_seconds: None,
+                nodes: vec![],
+                edges: vec![],
+                retry: None,
+                idempotent: true,
+                pinned_output: None,
+                disabled: false,
             }],
             edges: vec![],
             execution_mode: ExecutionMode::Parallel,
             timeout_seconds: None,
+            max_concurrency: None,
+            max_concurrent_executions: None,
+            overlap_policy: None,
+            concurrency_key: None,
+            idempotency_window_seconds: None,
+            error_workflow: None,
         };
 
         assert_eq!(def.execution_mode, ExecutionMode::Parallel);
@@ -110,6 +110,70 @@ impl NodeType for AuditTrailNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "action_type": {
+                    "type": ["string", "null"],
+                    "description": "Type of the audited action"
+                },
+                "resource_id": {
+                    "type": ["string", "null"],
+                    "description": "Resource the action targeted"
+                },
+                "user_id": {
+                    "type": ["string", "null"],
+                    "description": "User who performed the action"
+                },
+                "severity": {
+                    "type": "string",
+                    "description": "Severity of the entry"
+                },
+                "retention_days": {
+                    "type": "integer",
+                    "description": "Days the entry is kept"
+                },
+                "details": {
+                    "description": "Details of the action"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "audit_log_id": {
+                    "type": "string",
+                    "description": "ID of the audit entry"
+                },
+                "timestamp": {
+                    "type": "string",
+                    "description": "Time of the entry"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("query_audit_log" | "export_audit_log" | "compliance_report")
+        )
+    }
 }
 
 #[async_trait]
@@ -201,6 +201,157 @@ impl NodeType for AwsCloudWatchNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("aws")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation, for operations that change something"
+                },
+                "namespace": {
+                    "type": ["string", "null"],
+                    "description": "Metric namespace"
+                },
+                "metric_name": {
+                    "type": ["string", "null"],
+                    "description": "Metric the operation targeted"
+                },
+                "value": {
+                    "type": ["number", "null"],
+                    "description": "Metric value, for `put_metric_data`"
+                },
+                "unit": {
+                    "type": ["string", "null"],
+                    "description": "Metric unit, for `put_metric_data`"
+                },
+                "dimensions": {
+                    "description": "Metric dimensions, for `put_metric_data`"
+                },
+                "statistics": {
+                    "type": ["array", "null"],
+                    "description": "Statistics requested, for `get_metric_statistics`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "period": {
+                    "type": ["integer", "null"],
+                    "description": "Period in seconds, for `get_metric_statistics`"
+                },
+                "start_time": {
+                    "type": ["string", "null"],
+                    "description": "Start of the period, for `get_metric_statistics`"
+                },
+                "end_time": {
+                    "type": ["string", "null"],
+                    "description": "End of the period, for `get_metric_statistics`"
+                },
+                "datapoints": {
+                    "type": "array",
+                    "description": "Datapoints, for `get_metric_statistics`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "label": {
+                    "type": ["string", "null"],
+                    "description": "Label of the datapoints, for `get_metric_statistics`"
+                },
+                "metrics": {
+                    "type": "array",
+                    "description": "Metrics found, for `list_metrics`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "log_group_name": {
+                    "type": ["string", "null"],
+                    "description": "Log group the operation targeted"
+                },
+                "log_stream_name": {
+                    "type": ["string", "null"],
+                    "description": "Log stream the operation targeted"
+                },
+                "next_sequence_token": {
+                    "type": "string",
+                    "description": "Token for the next batch of events, for `put_log_events`"
+                },
+                "filter_pattern": {
+                    "type": ["string", "null"],
+                    "description": "Filter pattern, for `filter_log_events`"
+                },
+                "events": {
+                    "type": "array",
+                    "description": "Log events found, for `filter_log_events`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "searched_log_streams": {
+                    "type": "array",
+                    "description": "Log streams searched, for `filter_log_events`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "log_streams": {
+                    "type": "array",
+                    "description": "Log streams, for `describe_log_streams`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "alarm_name": {
+                    "type": ["string", "null"],
+                    "description": "Alarm the operation targeted"
+                },
+                "comparison_operator": {
+                    "type": ["string", "null"],
+                    "description": "Alarm comparison, for `put_metric_alarm`"
+                },
+                "threshold": {
+                    "type": ["number", "null"],
+                    "description": "Alarm threshold, for `put_metric_alarm`"
+                },
+                "evaluation_periods": {
+                    "type": ["integer", "null"],
+                    "description": "Alarm evaluation periods, for `put_metric_alarm`"
+                },
+                "metric_alarms": {
+                    "type": "array",
+                    "description": "Alarms, for `describe_alarms`",
+                    "items": {
+                        "type": "object"
+                    }
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some(
+                "get_metric_statistics"
+                    | "list_metrics"
+                    | "filter_log_events"
+                    | "describe_log_streams"
+                    | "describe_alarms"
+            )
+        )
+    }
 }
 
 #[async_trait]
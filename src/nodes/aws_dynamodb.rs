@@ -152,6 +152,174 @@ impl NodeType for AwsDynamoDBNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("aws")
     }
+
+    fn output_schema(&self) -> Value {
+        let consumed_capacity = json!({
+            "description": "Capacity consumed, per table for `batch_get_item` and `batch_write_item`",
+            "type": "object",
+            "properties": {
+                "table_name": {
+                    "type": "string",
+                    "description": "Table the capacity was consumed on"
+                },
+                "capacity_units": {
+                    "type": "number",
+                    "description": "Capacity units consumed"
+                }
+            }
+        });
+
+        let table_description = json!({
+            "type": "object",
+            "description": "Table being created or deleted, for `create_table` and `delete_table`",
+            "properties": {
+                "table_name": {
+                    "type": "string",
+                    "description": "Name of the table"
+                },
+                "table_status": {
+                    "type": "string",
+                    "description": "Status of the table"
+                },
+                "creation_date_time": {
+                    "type": "string",
+                    "description": "Creation time of the table"
+                },
+                "table_arn": {
+                    "type": "string",
+                    "description": "ARN of the table"
+                }
+            }
+        });
+
+        let table = json!({
+            "type": "object",
+            "description": "Table details, for `describe_table`",
+            "properties": {
+                "table_name": {
+                    "type": "string",
+                    "description": "Name of the table"
+                },
+                "table_status": {
+                    "type": "string",
+                    "description": "Status of the table"
+                },
+                "creation_date_time": {
+                    "type": "string",
+                    "description": "Creation time of the table"
+                },
+                "item_count": {
+                    "type": "integer",
+                    "description": "Number of items in the table"
+                },
+                "table_size_bytes": {
+                    "type": "integer",
+                    "description": "Size of the table in bytes"
+                },
+                "provisioned_throughput": {
+                    "type": "object",
+                    "properties": {
+                        "read_capacity_units": {
+                            "type": "integer",
+                            "description": "Provisioned read capacity"
+                        },
+                        "write_capacity_units": {
+                            "type": "integer",
+                            "description": "Provisioned write capacity"
+                        }
+                    }
+                }
+            }
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "table_name": {
+                    "type": "string",
+                    "description": "Table the operation targeted, for every operation but `list_tables`"
+                },
+                "key": {
+                    "type": "object",
+                    "description": "Primary key of the item, for `get_item`, `update_item` and `delete_item`"
+                },
+                "item": {
+                    "type": "object",
+                    "description": "Item written by `put_item` or read by `get_item`, in DynamoDB attribute-value format"
+                },
+                "update_expression": {
+                    "type": "string",
+                    "description": "Update expression, for `update_item`"
+                },
+                "attributes": {
+                    "type": "object",
+                    "description": "Updated attributes, for `update_item`"
+                },
+                "deleted": {
+                    "type": "boolean",
+                    "description": "Whether the item was deleted, for `delete_item`"
+                },
+                "key_condition": {
+                    "type": "object",
+                    "description": "Key condition values, for `query`"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Maximum number of items, for `scan`"
+                },
+                "items": {
+                    "type": "array",
+                    "description": "Items returned by `query` and `scan`, in DynamoDB attribute-value format",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "Number of items returned, for `query` and `scan`"
+                },
+                "scanned_count": {
+                    "type": "integer",
+                    "description": "Number of items evaluated, for `query` and `scan`"
+                },
+                "responses": {
+                    "type": "object",
+                    "description": "Items per table, for `batch_get_item`"
+                },
+                "unprocessed_keys": {
+                    "type": "object",
+                    "description": "Keys not read, for `batch_get_item`"
+                },
+                "unprocessed_items": {
+                    "type": "object",
+                    "description": "Items not written, for `batch_write_item`"
+                },
+                "consumed_capacity": consumed_capacity,
+                "table_description": table_description,
+                "table": table,
+                "table_names": {
+                    "type": "array",
+                    "description": "Tables in the account, for `list_tables`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "last_evaluated_table_name": {
+                    "type": ["string", "null"],
+                    "description": "Last table listed when there are more, for `list_tables`"
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -202,6 +202,47 @@ impl NodeType for AwsLambdaNode {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "function_name": {
+                    "type": ["string", "null"],
+                    "description": "Function the operation targeted"
+                },
+                "invocation_type": {
+                    "type": ["string", "null"],
+                    "description": "How the function was invoked"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -194,6 +194,55 @@ impl NodeType for AwsMessagingNode {
             }
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "service": {
+                    "type": "string",
+                    "description": "Service used, `sqs` or `sns`"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "queue_url": {
+                    "type": ["string", "null"],
+                    "description": "URL of the queue, for `sqs`"
+                },
+                "queue_name": {
+                    "type": ["string", "null"],
+                    "description": "Name of the queue, for `sqs`"
+                },
+                "topic_arn": {
+                    "type": ["string", "null"],
+                    "description": "ARN of the topic, for `sns`"
+                },
+                "topic_name": {
+                    "type": ["string", "null"],
+                    "description": "Name of the topic, for `sns`"
+                },
+                "execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -187,6 +187,209 @@ impl NodeType for AwsS3Node {
     fn required_credential_type(&self) -> Option<&str> {
         Some("aws")
     }
+
+    fn output_schema(&self) -> Value {
+        let contents = json!({
+            "type": "array",
+            "description": "Objects in the bucket, for `list_objects`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "key": {
+                        "type": "string",
+                        "description": "Object key"
+                    },
+                    "size": {
+                        "type": "integer",
+                        "description": "Size of the object in bytes"
+                    },
+                    "last_modified": {
+                        "type": "string",
+                        "description": "Last modification time of the object"
+                    },
+                    "etag": {
+                        "type": "string",
+                        "description": "ETag of the object"
+                    },
+                    "storage_class": {
+                        "type": "string",
+                        "description": "Storage class of the object"
+                    }
+                }
+            }
+        });
+
+        let buckets = json!({
+            "type": "array",
+            "description": "Buckets in the account, for `list_buckets`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Name of the bucket"
+                    },
+                    "creation_date": {
+                        "type": "string",
+                        "description": "Creation time of the bucket"
+                    }
+                }
+            }
+        });
+
+        let owner = json!({
+            "type": "object",
+            "description": "Owner of the buckets, for `list_buckets`",
+            "properties": {
+                "id": {
+                    "type": "string",
+                    "description": "ID of the owner"
+                },
+                "display_name": {
+                    "type": "string",
+                    "description": "Display name of the owner"
+                }
+            }
+        });
+
+        let metadata = json!({
+            "type": "object",
+            "description": "Object metadata, for `get_object_metadata`",
+            "properties": {
+                "content_type": {
+                    "type": "string",
+                    "description": "Content type of the object"
+                },
+                "content_length": {
+                    "type": "integer",
+                    "description": "Size of the object in bytes"
+                },
+                "last_modified": {
+                    "type": "string",
+                    "description": "Last modification time of the object"
+                },
+                "etag": {
+                    "type": "string",
+                    "description": "ETag of the object"
+                },
+                "version_id": {
+                    "type": "string",
+                    "description": "Version of the object"
+                },
+                "storage_class": {
+                    "type": "string",
+                    "description": "Storage class of the object"
+                },
+                "server_side_encryption": {
+                    "type": "string",
+                    "description": "Server-side encryption of the object"
+                }
+            }
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "bucket": {
+                    "type": "string",
+                    "description": "Bucket the operation targeted"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Object key the operation targeted"
+                },
+                "etag": {
+                    "type": "string",
+                    "description": "ETag of the object"
+                },
+                "version_id": {
+                    "type": "string",
+                    "description": "Version of the object"
+                },
+                "size": {
+                    "type": "integer",
+                    "description": "Size of the object in bytes"
+                },
+                "location": {
+                    "type": "string",
+                    "description": "Location of the uploaded object or created bucket"
+                },
+                "content": {
+                    "type": "string",
+                    "description": "Base64 encoded object content, for `download_object`"
+                },
+                "content_type": {
+                    "type": "string",
+                    "description": "Content type of the object, for `download_object`"
+                },
+                "last_modified": {
+                    "type": "string",
+                    "description": "Last modification time of the object, for `download_object`"
+                },
+                "deleted": {
+                    "type": "boolean",
+                    "description": "Whether the object was deleted, for `delete_object`"
+                },
+                "source": {
+                    "type": "string",
+                    "description": "Copied object, for `copy_object`"
+                },
+                "destination": {
+                    "type": "string",
+                    "description": "Copy of the object, for `copy_object`"
+                },
+                "prefix": {
+                    "type": "string",
+                    "description": "Listed key prefix, for `list_objects`"
+                },
+                "contents": contents,
+                "is_truncated": {
+                    "type": "boolean",
+                    "description": "Whether there are more objects, for `list_objects`"
+                },
+                "key_count": {
+                    "type": "integer",
+                    "description": "Number of objects listed, for `list_objects`"
+                },
+                "buckets": buckets,
+                "owner": owner,
+                "url": {
+                    "type": "string",
+                    "description": "Presigned URL, for `generate_presigned_url`"
+                },
+                "expires_in": {
+                    "type": "integer",
+                    "description": "Seconds until the presigned URL expires, for `generate_presigned_url`"
+                },
+                "upload_id": {
+                    "type": "string",
+                    "description": "ID of the upload, for `multipart_upload`"
+                },
+                "parts": {
+                    "type": "integer",
+                    "description": "Number of parts uploaded, for `multipart_upload`"
+                },
+                "completed": {
+                    "type": "boolean",
+                    "description": "Whether the upload completed, for `multipart_upload`"
+                },
+                "versioning": {
+                    "type": "string",
+                    "description": "Versioning state of the bucket, for `set_bucket_versioning`"
+                },
+                "metadata": metadata
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -120,6 +120,135 @@ impl NodeType for AwsSecretsManagerNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("aws")
     }
+
+    fn output_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "secret_name": {
+                    "type": "string",
+                    "description": "Secret the operation targeted, for every operation but `list_secrets`"
+                },
+                "arn": {
+                    "type": "string",
+                    "description": "ARN of the secret"
+                },
+                "version_id": {
+                    "type": "string",
+                    "description": "Version of the secret"
+                },
+                "version_stages": {
+                    "type": "array",
+                    "description": "Stages of the version, for `get_secret_value` and `put_secret_value`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "secret_string": {
+                    "type": "string",
+                    "description": "Secret value, for `get_secret_value`"
+                },
+                "created_date": {
+                    "type": "string",
+                    "description": "Creation time of the secret"
+                },
+                "updated_date": {
+                    "type": "string",
+                    "description": "Update time of the secret, for `update_secret`"
+                },
+                "deletion_date": {
+                    "type": "string",
+                    "description": "When the secret will be deleted, for `delete_secret`"
+                },
+                "scheduled_deletion": {
+                    "type": "boolean",
+                    "description": "Whether the deletion is scheduled, for `delete_secret`"
+                },
+                "secrets": {
+                    "type": "array",
+                    "description": "Secrets in the account, for `list_secrets`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "next_token": {
+                    "type": ["string", "null"],
+                    "description": "Token for the next page, for `list_secrets`"
+                },
+                "description": {
+                    "type": "string",
+                    "description": "Description of the secret, for `describe_secret`"
+                },
+                "kms_key_id": {
+                    "type": "string",
+                    "description": "KMS key of the secret, for `describe_secret`"
+                },
+                "rotation_enabled": {
+                    "type": "boolean",
+                    "description": "Whether rotation is enabled, for `describe_secret`"
+                },
+                "rotation_lambda_arn": {
+                    "type": "string",
+                    "description": "Rotation function, for `describe_secret` and `rotate_secret`"
+                },
+                "rotation_rules": {
+                    "type": "object",
+                    "description": "Rotation schedule, for `describe_secret`"
+                },
+                "last_rotated_date": {
+                    "type": "string",
+                    "description": "Last rotation time, for `describe_secret`"
+                },
+                "last_changed_date": {
+                    "type": "string",
+                    "description": "Last change time, for `describe_secret`"
+                },
+                "last_accessed_date": {
+                    "type": "string",
+                    "description": "Last access time, for `describe_secret`"
+                },
+                "version_ids_to_stages": {
+                    "type": "object",
+                    "description": "Stages per version, for `describe_secret`"
+                },
+                "tags": {
+                    "type": "array",
+                    "description": "Tags of the secret, for `describe_secret`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "rotation_initiated": {
+                    "type": "string",
+                    "description": "When rotation started, for `rotate_secret`"
+                },
+                "restored": {
+                    "type": "boolean",
+                    "description": "Whether the secret was restored, for `restore_secret`"
+                },
+                "tags_applied": {
+                    "description": "Tags added, for `tag_resource`"
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("get_secret_value" | "list_secrets" | "describe_secret")
+        )
+    }
 }
 
 #[async_trait]
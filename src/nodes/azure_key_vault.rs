@@ -135,6 +135,157 @@ impl NodeType for AzureKeyVaultNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("azure")
     }
+
+    fn output_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "vault_url": {
+                    "type": "string",
+                    "description": "Vault the operation targeted"
+                },
+                "name": {
+                    "type": "string",
+                    "description": "Secret, key or certificate the operation targeted"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "ID of the secret, key or certificate"
+                },
+                "version": {
+                    "type": "string",
+                    "description": "Version of the secret, key or certificate"
+                },
+                "enabled": {
+                    "type": "boolean",
+                    "description": "Whether the secret, key or certificate is enabled"
+                },
+                "created_on": {
+                    "type": "string",
+                    "description": "Creation time"
+                },
+                "updated_on": {
+                    "type": "string",
+                    "description": "Update time"
+                },
+                "content_type": {
+                    "type": "string",
+                    "description": "Content type of the secret"
+                },
+                "value": {
+                    "type": "string",
+                    "description": "Secret value, for `get_secret`"
+                },
+                "tags": {
+                    "type": "object",
+                    "description": "Tags"
+                },
+                "deleted_date": {
+                    "type": "string",
+                    "description": "Deletion time, for the delete operations"
+                },
+                "scheduled_purge_date": {
+                    "type": "string",
+                    "description": "When the deleted item will be purged, for the delete operations"
+                },
+                "recovery_id": {
+                    "type": "string",
+                    "description": "ID to recover the deleted item, for the delete operations"
+                },
+                "key_type": {
+                    "type": "string",
+                    "description": "Type of the key, for `create_key` and `get_key`"
+                },
+                "key_size": {
+                    "type": "integer",
+                    "description": "Size of the key, for `create_key` and `get_key`"
+                },
+                "key_operations": {
+                    "type": "array",
+                    "description": "Operations the key allows, for `create_key` and `get_key`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "thumbprint": {
+                    "type": "string",
+                    "description": "Thumbprint of the certificate"
+                },
+                "subject": {
+                    "type": "string",
+                    "description": "Subject of the certificate"
+                },
+                "issuer": {
+                    "type": "string",
+                    "description": "Issuer of the certificate"
+                },
+                "expires_on": {
+                    "type": "string",
+                    "description": "Expiry of the certificate"
+                },
+                "not_before": {
+                    "type": "string",
+                    "description": "Start of validity of the certificate, for `get_certificate`"
+                },
+                "certificate_type": {
+                    "type": "string",
+                    "description": "Type of the certificate, for `get_certificate`"
+                },
+                "secrets": {
+                    "type": "array",
+                    "description": "Secrets in the vault, for `list_secrets`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "versions": {
+                    "type": "array",
+                    "description": "Versions of the secret, for `list_secret_versions`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "keys": {
+                    "type": "array",
+                    "description": "Keys in the vault, for `list_keys`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "certificates": {
+                    "type": "array",
+                    "description": "Certificates in the vault, for `list_certificates`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "Number of items listed, for the list operations"
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some(
+                "get_secret"
+                    | "list_secrets"
+                    | "list_secret_versions"
+                    | "get_key"
+                    | "list_keys"
+                    | "get_certificate"
+                    | "list_certificates"
+            )
+        )
+    }
 }
 
 #[async_trait]
@@ -207,6 +207,47 @@ impl NodeType for BedrockNode {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "model_id": {
+                    "type": "string",
+                    "description": "Model used"
+                },
+                "temperature": {
+                    "type": ["number", "null"],
+                    "description": "Sampling temperature"
+                },
+                "max_tokens": {
+                    "type": ["integer", "null"],
+                    "description": "Maximum number of tokens to generate"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 /// Circuit Breaker node to prevent cascading failures
+///
+/// The engine guards the nested body of the node with the circuit's shared
+/// state, which is kept per `circuit_id` across executions.
 #[derive(Clone)]
 pub struct CircuitBreakerNode {}
 
@@ -17,6 +20,8 @@ struct CircuitBreakerParams {
     timeout_seconds: Option<u64>,
     /// Circuit identifier for tracking state
     circuit_id: Option<String>,
+    /// Output returned instead of running the body while the circuit is open
+    fallback: Option<Value>,
 }
 
 impl CircuitBreakerNode {
@@ -68,7 +73,10 @@ impl NodeType for CircuitBreakerNode {
                 },
                 "circuit_id": {
                     "type": "string",
-                    "description": "Unique identifier for this circuit"
+                    "description": "Unique identifier for this circuit, shared across workflows"
+                },
+                "fallback": {
+                    "description": "Output returned while the circuit is open (default: null)"
                 }
             }
         })
@@ -91,19 +99,13 @@ impl Node for CircuitBreakerNode {
             .circuit_id
             .unwrap_or_else(|| format!("circuit-{}", context.node_id));
 
-        // In a real implementation, this would:
-        // 1. Check circuit state from shared state store (Redis, etc.)
-        // 2. If OPEN: return error or fallback immediately
-        // 3. If HALF_OPEN: allow one request through, update state based on result
-        // 4. If CLOSED: execute normally, track failures
-        // 5. Update circuit state based on thresholds
-
+        // The engine checks and updates the shared circuit state using this configuration
         let result = json!({
             "circuit_id": circuit_id,
             "failure_threshold": failure_threshold,
             "success_threshold": success_threshold,
             "timeout_seconds": timeout_seconds,
-            "current_state": "closed", // Would be tracked in state store
+            "fallback": params.fallback.unwrap_or(Value::Null),
             "context_execution_id": context.execution_id
         });
 
@@ -153,7 +155,8 @@ mod tests {
         assert!(result.is_ok());
         let output = result.unwrap();
         assert_eq!(output.data["failure_threshold"], 5);
-        assert_eq!(output.data["current_state"], "closed");
+        assert_eq!(output.data["circuit_id"], "circuit-node-1");
+        assert!(output.data["fallback"].is_null());
     }
 
     #[test]
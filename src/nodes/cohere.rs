@@ -196,6 +196,169 @@ impl NodeType for CohereNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("cohere_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        let generations = serde_json::json!({
+            "type": "array",
+            "description": "Generated texts, for `generate`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "string",
+                        "description": "ID of the generation"
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "Generated text"
+                    },
+                    "finish_reason": {
+                        "type": "string",
+                        "description": "Why the generation stopped"
+                    }
+                }
+            }
+        });
+
+        let classifications = serde_json::json!({
+            "type": "array",
+            "description": "Classified inputs, for `classify`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "input": {
+                        "type": "string",
+                        "description": "Classified text"
+                    },
+                    "prediction": {
+                        "type": "string",
+                        "description": "Predicted label"
+                    },
+                    "confidence": {
+                        "type": "number",
+                        "description": "Confidence of the prediction"
+                    }
+                }
+            }
+        });
+
+        let results = serde_json::json!({
+            "type": "array",
+            "description": "Reranked documents for `rerank`, or detected languages for `detect_language`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "index": {
+                        "type": "integer",
+                        "description": "Index of the document"
+                    },
+                    "relevance_score": {
+                        "type": "number",
+                        "description": "Relevance of the document to the query"
+                    },
+                    "language_code": {
+                        "type": "string",
+                        "description": "Detected language code"
+                    },
+                    "language_name": {
+                        "type": "string",
+                        "description": "Detected language name"
+                    }
+                }
+            }
+        });
+
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Model used, for every operation but `detect_language`, `tokenize` and `detokenize`"
+                },
+                "prompt": {
+                    "type": ["string", "null"],
+                    "description": "Prompt, for `generate`"
+                },
+                "temperature": {
+                    "type": ["number", "null"],
+                    "description": "Sampling temperature, for `generate` and `chat`"
+                },
+                "max_tokens": {
+                    "type": ["integer", "null"],
+                    "description": "Maximum number of tokens to generate, for `generate` and `chat`"
+                },
+                "generations": generations,
+                "text": {
+                    "type": "string",
+                    "description": "Chat response for `chat`, or the text for `detokenize`"
+                },
+                "generation_id": {
+                    "type": "string",
+                    "description": "ID of the chat response, for `chat`"
+                },
+                "finish_reason": {
+                    "type": "string",
+                    "description": "Why the chat response stopped, for `chat`"
+                },
+                "embeddings": {
+                    "type": "array",
+                    "description": "One embedding per input text, for `embed`",
+                    "items": {
+                        "type": "array",
+                        "description": "Embedding vector",
+                        "items": {
+                            "type": "number"
+                        }
+                    }
+                },
+                "classifications": classifications,
+                "summary": {
+                    "type": "string",
+                    "description": "Summary, for `summarize`"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "ID of the summary, for `summarize`"
+                },
+                "results": results,
+                "tokens": {
+                    "type": "array",
+                    "description": "Token IDs, for `tokenize`",
+                    "items": {
+                        "type": "integer"
+                    }
+                },
+                "token_strings": {
+                    "type": "array",
+                    "description": "Token texts, for `tokenize`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -385,30 +548,20 @@ impl Node for CohereNode {
 
         // Validate operation-specific requirements
         match params.operation.as_str() {
-            "generate" => {
-                if params.prompt.is_none() {
-                    anyhow::bail!("'prompt' parameter is required for generate operation");
-                }
+            "generate" if params.prompt.is_none() => {
+                anyhow::bail!("'prompt' parameter is required for generate operation");
             }
-            "chat" => {
-                if params.messages.is_none() {
-                    anyhow::bail!("'messages' parameter is required for chat operation");
-                }
+            "chat" if params.messages.is_none() => {
+                anyhow::bail!("'messages' parameter is required for chat operation");
             }
-            "embed" => {
-                if params.texts.is_none() {
-                    anyhow::bail!("'texts' parameter is required for embed operation");
-                }
+            "embed" if params.texts.is_none() => {
+                anyhow::bail!("'texts' parameter is required for embed operation");
             }
-            "classify" => {
-                if params.texts.is_none() {
-                    anyhow::bail!("'texts' parameter is required for classify operation");
-                }
+            "classify" if params.texts.is_none() => {
+                anyhow::bail!("'texts' parameter is required for classify operation");
             }
-            "summarize" => {
-                if params.prompt.is_none() {
-                    anyhow::bail!("'prompt' parameter is required for summarize operation");
-                }
+            "summarize" if params.prompt.is_none() => {
+                anyhow::bail!("'prompt' parameter is required for summarize operation");
             }
             "rerank" => {
                 if params.texts.is_none() {
@@ -418,23 +571,15 @@ impl Node for CohereNode {
                     anyhow::bail!("'prompt' parameter (query) is required for rerank operation");
                 }
             }
-            "detect_language" => {
-                if params.texts.is_none() {
-                    anyhow::bail!("'texts' parameter is required for detect_language operation");
-                }
+            "detect_language" if params.texts.is_none() => {
+                anyhow::bail!("'texts' parameter is required for detect_language operation");
             }
-            "tokenize" => {
-                if params.prompt.is_none() {
-                    anyhow::bail!("'prompt' parameter (text) is required for tokenize operation");
-                }
+            "tokenize" if params.prompt.is_none() => {
+                anyhow::bail!("'prompt' parameter (text) is required for tokenize operation");
             }
-            "detokenize" => {
-                // detokenize would typically require token IDs, but we'll use texts for simplicity
-                if params.texts.is_none() {
-                    anyhow::bail!(
-                        "'texts' parameter (token IDs) is required for detokenize operation"
-                    );
-                }
+            // detokenize would typically require token IDs, but we'll use texts for simplicity
+            "detokenize" if params.texts.is_none() => {
+                anyhow::bail!("'texts' parameter (token IDs) is required for detokenize operation");
             }
             _ => {}
         }
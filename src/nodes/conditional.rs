@@ -1,3 +1,4 @@
+use crate::execution::expression::lookup_path;
 use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -12,7 +13,7 @@ struct ConditionalParams {
     value: serde_json::Value,
 }
 
-/// Conditional node - evaluates conditions and returns result
+/// Conditional node - evaluates conditions and routes to the "true" or "false" output
 pub struct ConditionalNode;
 
 impl NodeType for ConditionalNode {
@@ -70,11 +71,13 @@ impl Node for ConditionalNode {
         // Evaluate the condition
         let result = evaluate_condition(&field_value, &params.operator, &params.value)?;
 
-        // Return result with condition outcome
+        // Return result with condition outcome, routed to the matching port
+        let port = if result { "true" } else { "false" };
         Ok(NodeOutput::success(serde_json::json!({
             "condition": result,
             "input": input
-        })))
+        }))
+        .with_port(port))
     }
 
     fn validate_parameters(&self, parameters: &serde_json::Value) -> anyhow::Result<()> {
@@ -94,19 +97,9 @@ impl Node for ConditionalNode {
 }
 
 fn extract_field(data: &serde_json::Value, path: &str) -> anyhow::Result<serde_json::Value> {
-    let parts: Vec<&str> = path.split('.').collect();
-    let mut current = data;
-
-    for part in parts {
-        if part.is_empty() {
-            continue;
-        }
-        current = current
-            .get(part)
-            .ok_or_else(|| anyhow::anyhow!("Field not found: {}", part))?;
-    }
-
-    Ok(current.clone())
+    lookup_path(data, path)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Field not found: {}", path))
 }
 
 fn evaluate_condition(
@@ -171,6 +164,21 @@ mod tests {
         assert!(!evaluate_condition(&serde_json::json!(5), "gt", &serde_json::json!(10)).unwrap());
     }
 
+    #[tokio::test]
+    async fn test_conditional_routes_to_port() {
+        let node = ConditionalNode;
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        context.add_input("start".to_string(), serde_json::json!({"value": 70}));
+
+        let params = serde_json::json!({"field": "value", "operator": "gt", "value": 50});
+        let output = node.execute(&context, &params).await.unwrap();
+        assert_eq!(output.port, Some("true".to_string()));
+
+        let params = serde_json::json!({"field": "value", "operator": "lt", "value": 50});
+        let output = node.execute(&context, &params).await.unwrap();
+        assert_eq!(output.port, Some("false".to_string()));
+    }
+
     #[test]
     fn test_string_contains() {
         assert!(
@@ -69,6 +69,45 @@ impl NodeType for DatabaseQueryNode {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "query": {
+                    "type": "string",
+                    "description": "SQL statement that was run"
+                },
+                "params_count": {
+                    "type": "integer",
+                    "description": "Number of bound parameters"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "rows_affected": {
+                    "type": "integer",
+                    "description": "Number of rows changed by the statement"
+                },
+                "results": {
+                    "type": "array",
+                    "description": "Rows returned by the statement",
+                    "items": {
+                        "type": "object"
+                    }
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -121,6 +121,58 @@ impl NodeType for DatadogNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("datadog_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "metric_name": {
+                    "type": ["string", "null"],
+                    "description": "Metric the operation targeted"
+                },
+                "value": {
+                    "type": ["number", "null"],
+                    "description": "Metric value"
+                },
+                "tags": {
+                    "type": ["array", "null"],
+                    "description": "Tags of the metric or event",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("query_metrics" | "get_monitor_status")
+        )
+    }
 }
 
 #[async_trait]
@@ -278,6 +278,27 @@ impl NodeType for DiscordNode {
             }
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -1,8 +1,8 @@
 use crate::db;
-use crate::execution::WorkflowEngine;
 use crate::models::{
-    Node, NodeCategory, NodeContext, NodeOutput, NodeRegistry, NodeSubcategory, NodeType,
+    Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType, WorkflowExecution,
 };
+use anyhow::Context;
 use async_trait::async_trait;
 use serde::Deserialize;
 use sqlx::PgPool;
@@ -19,13 +19,28 @@ struct ExecuteWorkflowParams {
     /// Whether to wait for the workflow to complete (default: true)
     #[serde(default = "default_wait")]
     wait: bool,
+    /// Maximum sub-workflow depth, counting the top-level execution as 0
+    #[serde(default = "default_max_depth")]
+    max_depth: i32,
 }
 
 fn default_wait() -> bool {
     true
 }
 
-/// Execute Workflow node - executes another workflow as a sub-workflow
+/// Default limit on nested sub-workflow calls, which stops workflows that
+/// call themselves from recursing forever
+pub const DEFAULT_MAX_DEPTH: i32 = 10;
+
+fn default_max_depth() -> i32 {
+    DEFAULT_MAX_DEPTH
+}
+
+/// Execute Workflow node - executes another workflow as a sub-workflow.
+///
+/// With `wait: false` the node queues the sub-workflow itself. Otherwise it
+/// only checks the request: the engine running the parent execution then
+/// runs the sub-workflow and the node outputs its result.
 pub struct ExecuteWorkflowNode {
     pool: PgPool,
 }
@@ -67,8 +82,14 @@ impl NodeType for ExecuteWorkflowNode {
                 },
                 "wait": {
                     "type": "boolean",
-                    "description": "Whether to wait for the sub-workflow to complete",
+                    "description": "Whether to wait for the sub-workflow to complete. When false, the sub-workflow is queued for a worker",
                     "default": true
+                },
+                "max_depth": {
+                    "type": "integer",
+                    "description": "Maximum depth of nested sub-workflow calls",
+                    "minimum": 1,
+                    "default": DEFAULT_MAX_DEPTH
                 }
             },
             "oneOf": [
@@ -93,6 +114,17 @@ impl Node for ExecuteWorkflowNode {
     ) -> anyhow::Result<NodeOutput> {
         let params: ExecuteWorkflowParams = serde_json::from_value(parameters.clone())?;
 
+        // Stop workflows that (indirectly) call themselves
+        if context.depth >= params.max_depth {
+            anyhow::bail!(
+                "Sub-workflow call depth limit of {} reached at node {}",
+                params.max_depth,
+                context.node_id
+            );
+        }
+        let parent_execution_id = Uuid::parse_str(&context.execution_id)
+            .context("Sub-workflows can only be started from a workflow execution")?;
+
         // Determine input data: use provided input or pass through context input
         let input_data = if let Some(input) = params.input {
             Some(input)
@@ -116,56 +148,37 @@ impl Node for ExecuteWorkflowNode {
             anyhow::bail!("Cannot execute inactive workflow: {}", workflow.name);
         }
 
-        // Convert to workflow definition
-        let workflow_def = workflow.to_definition()?;
-
-        // Create execution engine with a full registry
-        let mut registry = NodeRegistry::new();
-        crate::nodes::register_builtin_nodes(&mut registry, &self.pool);
-        let engine = WorkflowEngine::new(self.pool.clone(), registry);
-
         if params.wait {
-            // Execute the workflow and wait for completion
+            // The engine runs the sub-workflow as a child of the execution
+            Ok(NodeOutput::success(serde_json::json!({
+                "workflow_id": workflow.id,
+                "workflow_name": workflow.name,
+                "input": input_data,
+                "wait": true
+            })))
+        } else {
+            // Fire and forget - queue the workflow for a worker
+            let mut execution = WorkflowExecution::pending(workflow.id, input_data).with_parent(
+                parent_execution_id,
+                context.node_id.clone(),
+                context.depth,
+            );
+            execution.ignore_pinned_data = context.ignore_pinned_data;
+            execution.dry_run = context.dry_run;
+            let execution = db::enqueue_execution(&self.pool, &execution).await?;
+
             tracing::info!(
-                "Executing sub-workflow '{}' (ID: {})",
+                "Queued sub-workflow '{}' (ID: {}) as execution {}",
                 workflow.name,
-                workflow.id
+                workflow.id,
+                execution.id
             );
 
-            let execution = engine
-                .execute_workflow(&workflow_def, workflow.id, input_data)
-                .await?;
-
-            // Check if execution was successful
-            if execution.status == crate::models::ExecutionStatus::Success {
-                Ok(NodeOutput::success(serde_json::json!({
-                    "execution_id": execution.id,
-                    "workflow_id": workflow.id,
-                    "workflow_name": workflow.name,
-                    "status": "success",
-                    "output": execution.output_data
-                })))
-            } else {
-                Ok(NodeOutput::error(format!(
-                    "Sub-workflow '{}' failed: {}",
-                    workflow.name,
-                    execution
-                        .error
-                        .unwrap_or_else(|| "Unknown error".to_string())
-                )))
-            }
-        } else {
-            // Fire and forget - start the workflow but don't wait
-            tokio::spawn(async move {
-                let _ = engine
-                    .execute_workflow(&workflow_def, workflow.id, input_data)
-                    .await;
-            });
-
             Ok(NodeOutput::success(serde_json::json!({
+                "execution_id": execution.id,
                 "workflow_id": workflow.id,
                 "workflow_name": workflow.name,
-                "status": "started",
+                "status": "queued",
                 "wait": false
             })))
         }
@@ -184,6 +197,10 @@ impl Node for ExecuteWorkflowNode {
             anyhow::bail!("Cannot specify both 'workflow_id' and 'workflow_name'");
         }
 
+        if params.max_depth < 1 {
+            anyhow::bail!("max_depth must be at least 1");
+        }
+
         // Validate workflow_id format if provided
         if let Some(ref workflow_id_str) = params.workflow_id {
             Uuid::parse_str(workflow_id_str)
@@ -250,4 +267,29 @@ mod tests {
         });
         assert!(node.validate_parameters(&params).is_err());
     }
+
+    #[tokio::test]
+    async fn test_validate_max_depth() {
+        let node = create_test_node();
+
+        let params = serde_json::json!({
+            "workflow_name": "My Workflow",
+            "max_depth": 0
+        });
+        assert!(node.validate_parameters(&params).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_depth_limit() {
+        let node = create_test_node();
+        let mut context = NodeContext::new(Uuid::new_v4().to_string(), "recurse".to_string());
+        context.depth = 3;
+
+        let params = serde_json::json!({
+            "workflow_name": "My Workflow",
+            "max_depth": 3
+        });
+        let error = node.execute(&context, &params).await.unwrap_err();
+        assert!(error.to_string().contains("depth limit of 3"));
+    }
 }
@@ -93,6 +93,55 @@ impl NodeType for FileOperationsNode {
     fn required_credential_type(&self) -> Option<&str> {
         None // No credentials needed for file operations
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "file_path": {
+                    "type": ["string", "null"],
+                    "description": "File the operation targeted"
+                },
+                "source_path": {
+                    "type": ["string", "null"],
+                    "description": "Source of a copy or move"
+                },
+                "destination_path": {
+                    "type": ["string", "null"],
+                    "description": "Destination of a copy or move"
+                },
+                "directory_path": {
+                    "type": ["string", "null"],
+                    "description": "Directory the operation targeted"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("read_file" | "list_directory")
+        )
+    }
 }
 
 #[async_trait]
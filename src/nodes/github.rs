@@ -260,6 +260,47 @@ impl NodeType for GitHubNode {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "owner": {
+                    "type": ["string", "null"],
+                    "description": "Owner of the repository"
+                },
+                "repo": {
+                    "type": ["string", "null"],
+                    "description": "Repository the operation targeted"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
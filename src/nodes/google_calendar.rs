@@ -140,6 +140,98 @@ impl NodeType for GoogleCalendarNode {
             "required": ["credentials_name", "operation"]
         })
     }
+
+    fn output_schema(&self) -> Value {
+        let result = json!({
+            "type": "object",
+            "description": "Result of the operation",
+            "properties": {
+                "event_id": {
+                    "type": "string",
+                    "description": "ID of the event, for `create_event`, `get_event`, `update_event` and `delete_event`"
+                },
+                "summary": {
+                    "type": "string",
+                    "description": "Title of the event"
+                },
+                "start": {
+                    "type": "string",
+                    "description": "Start time of the event"
+                },
+                "end": {
+                    "type": "string",
+                    "description": "End time of the event"
+                },
+                "status": {
+                    "type": "string",
+                    "description": "Status of the event"
+                },
+                "html_link": {
+                    "type": "string",
+                    "description": "Link to the event, for `create_event`"
+                },
+                "updated": {
+                    "type": "boolean",
+                    "description": "Whether the event was updated, for `update_event`"
+                },
+                "deleted": {
+                    "type": "boolean",
+                    "description": "Whether the event was deleted, for `delete_event`"
+                },
+                "events": {
+                    "type": "array",
+                    "description": "Events found, for `list_events` and `search_events`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "query": {
+                    "type": "string",
+                    "description": "Search query, for `search_events`"
+                },
+                "count": {
+                    "type": "integer",
+                    "description": "Number of events found, for `list_events` and `search_events`"
+                },
+                "calendars": {
+                    "type": "object",
+                    "description": "Busy periods per calendar, for `get_free_busy`"
+                }
+            }
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "result": result,
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("get_event" | "list_events" | "search_events" | "get_free_busy")
+        )
+    }
 }
 
 #[async_trait]
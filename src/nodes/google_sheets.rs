@@ -86,6 +86,108 @@ impl NodeType for GoogleSheetsNode {
             "required": ["credentials_name", "operation"]
         })
     }
+
+    fn output_schema(&self) -> Value {
+        let result = json!({
+            "type": "object",
+            "description": "Result of the operation",
+            "properties": {
+                "spreadsheet_id": {
+                    "type": "string",
+                    "description": "ID of the spreadsheet, for `create_spreadsheet`"
+                },
+                "title": {
+                    "type": "string",
+                    "description": "Title of the spreadsheet or sheet"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "Link to the spreadsheet, for `create_spreadsheet`"
+                },
+                "range": {
+                    "type": "string",
+                    "description": "Range read, for `read_values`"
+                },
+                "values": {
+                    "type": "array",
+                    "description": "Rows read, for `read_values`",
+                    "items": {
+                        "type": "array"
+                    }
+                },
+                "row_count": {
+                    "type": "integer",
+                    "description": "Number of rows read, for `read_values`"
+                },
+                "updated_range": {
+                    "type": "string",
+                    "description": "Range written, for `write_values` and `append_values`"
+                },
+                "updated_rows": {
+                    "type": "integer",
+                    "description": "Number of rows written, for `write_values` and `append_values`"
+                },
+                "updated_columns": {
+                    "type": "integer",
+                    "description": "Number of columns written, for `write_values`"
+                },
+                "updated_cells": {
+                    "type": "integer",
+                    "description": "Number of cells written, for `write_values`"
+                },
+                "appended": {
+                    "type": "boolean",
+                    "description": "Whether the rows were appended, for `append_values`"
+                },
+                "cleared_range": {
+                    "type": "string",
+                    "description": "Range cleared, for `clear_values`"
+                },
+                "cleared": {
+                    "type": "boolean",
+                    "description": "Whether the range was cleared, for `clear_values`"
+                },
+                "sheet_id": {
+                    "type": ["integer", "string"],
+                    "description": "Sheet created or deleted, for `create_sheet` and `delete_sheet`"
+                },
+                "deleted": {
+                    "type": "boolean",
+                    "description": "Whether the sheet was deleted, for `delete_sheet`"
+                },
+                "sheets": {
+                    "type": "array",
+                    "description": "Sheets of the spreadsheet, for `get_spreadsheet_metadata`",
+                    "items": {
+                        "type": "object"
+                    }
+                }
+            }
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "result": result,
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("read_values" | "get_spreadsheet_metadata")
+        )
+    }
 }
 
 #[async_trait]
@@ -63,6 +63,26 @@ impl NodeType for HttpRequestNode {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "status": {
+                    "type": "integer",
+                    "description": "HTTP status code of the response",
+                    "examples": [200]
+                },
+                "headers": {
+                    "type": "object",
+                    "description": "Response headers"
+                },
+                "body": {
+                    "description": "Response body, parsed as JSON when possible"
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -94,6 +94,47 @@ impl NodeType for HttpWebhookNode {
     fn required_credential_type(&self) -> Option<&str> {
         None // Credentials are optional
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "url": {
+                    "type": "string",
+                    "description": "URL the webhook was sent to"
+                },
+                "method": {
+                    "type": "string",
+                    "description": "HTTP method used"
+                },
+                "credentials_name": {
+                    "type": ["string", "null"],
+                    "description": "Name of the credentials used"
+                },
+                "timeout_seconds": {
+                    "type": "integer",
+                    "description": "Request timeout in seconds"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
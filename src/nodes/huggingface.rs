@@ -135,6 +135,234 @@ impl NodeType for HuggingFaceNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("huggingface_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        let outputs = serde_json::json!({
+            "type": "array",
+            "description": "Model outputs, for `inference`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "generated_text": {
+                        "type": "string",
+                        "description": "Generated text"
+                    }
+                }
+            }
+        });
+
+        let predictions = serde_json::json!({
+            "type": "array",
+            "description": "Predicted labels, for `text_classification` and `image_classification`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "label": {
+                        "type": "string",
+                        "description": "Predicted label"
+                    },
+                    "score": {
+                        "type": "number",
+                        "description": "Score of the label"
+                    }
+                }
+            }
+        });
+
+        let entities = serde_json::json!({
+            "type": "array",
+            "description": "Recognized entities, for `token_classification`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "entity": {
+                        "type": "string",
+                        "description": "Entity type"
+                    },
+                    "score": {
+                        "type": "number",
+                        "description": "Score of the entity"
+                    },
+                    "word": {
+                        "type": "string",
+                        "description": "Text of the entity"
+                    },
+                    "start": {
+                        "type": "integer",
+                        "description": "Start offset in the input"
+                    },
+                    "end": {
+                        "type": "integer",
+                        "description": "End offset in the input"
+                    }
+                }
+            }
+        });
+
+        let detections = serde_json::json!({
+            "type": "array",
+            "description": "Detected objects, for `object_detection`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "label": {
+                        "type": "string",
+                        "description": "Object label"
+                    },
+                    "score": {
+                        "type": "number",
+                        "description": "Score of the detection"
+                    },
+                    "box": {
+                        "type": "object",
+                        "properties": {
+                            "xmin": {
+                                "type": "integer",
+                                "description": "Left edge"
+                            },
+                            "ymin": {
+                                "type": "integer",
+                                "description": "Top edge"
+                            },
+                            "xmax": {
+                                "type": "integer",
+                                "description": "Right edge"
+                            },
+                            "ymax": {
+                                "type": "integer",
+                                "description": "Bottom edge"
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        let models = serde_json::json!({
+            "type": "array",
+            "description": "Available models, for `list_models`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "string",
+                        "description": "Model ID"
+                    },
+                    "task": {
+                        "type": "string",
+                        "description": "Task of the model"
+                    },
+                    "downloads": {
+                        "type": "integer",
+                        "description": "Number of downloads"
+                    },
+                    "library": {
+                        "type": "string",
+                        "description": "Library of the model"
+                    }
+                }
+            }
+        });
+
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Model used, for every operation but `download_model` and `list_models`"
+                },
+                "outputs": outputs,
+                "generated_text": {
+                    "type": "string",
+                    "description": "Generated text, for `text_generation`"
+                },
+                "metadata": {
+                    "type": "object",
+                    "description": "Model details, for `inference`, `text_generation`, `text_to_image` and `embeddings`"
+                },
+                "predictions": predictions,
+                "entities": entities,
+                "answer": {
+                    "type": "string",
+                    "description": "Answer, for `question_answering`"
+                },
+                "score": {
+                    "type": "number",
+                    "description": "Score of the answer, for `question_answering`"
+                },
+                "start": {
+                    "type": "integer",
+                    "description": "Start offset of the answer in the context, for `question_answering`"
+                },
+                "end": {
+                    "type": "integer",
+                    "description": "End offset of the answer in the context, for `question_answering`"
+                },
+                "summary_text": {
+                    "type": "string",
+                    "description": "Summary, for `summarization`"
+                },
+                "translation_text": {
+                    "type": "string",
+                    "description": "Translation, for `translation`"
+                },
+                "image": {
+                    "type": "string",
+                    "description": "Base64 encoded image, for `text_to_image`"
+                },
+                "detections": detections,
+                "text": {
+                    "type": "string",
+                    "description": "Transcript, for `speech_recognition`"
+                },
+                "embeddings": {
+                    "type": "array",
+                    "description": "Embedding vector, for `embeddings`",
+                    "items": {
+                        "type": "number"
+                    }
+                },
+                "repo_id": {
+                    "type": "string",
+                    "description": "Repository of the model, for `download_model`"
+                },
+                "filename": {
+                    "type": ["string", "null"],
+                    "description": "File downloaded, for `download_model`"
+                },
+                "revision": {
+                    "type": ["string", "null"],
+                    "description": "Revision downloaded, for `download_model`"
+                },
+                "download_url": {
+                    "type": "string",
+                    "description": "Download URL, for `download_model`"
+                },
+                "models": models,
+                "total": {
+                    "type": "integer",
+                    "description": "Number of models, for `list_models`"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -125,6 +125,52 @@ impl NodeType for ImageProcessorNode {
     fn required_credential_type(&self) -> Option<&str> {
         None // No credentials needed for image processing
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "input_path": {
+                    "type": ["string", "null"],
+                    "description": "Image processed"
+                },
+                "output_path": {
+                    "type": ["string", "null"],
+                    "description": "File the result was written to"
+                },
+                "width": {
+                    "type": ["integer", "null"],
+                    "description": "Width of the result"
+                },
+                "height": {
+                    "type": ["integer", "null"],
+                    "description": "Height of the result"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Only a result written to a file leaves a trace outside the workflow
+        parameters["output_path"].is_string()
+    }
 }
 
 #[async_trait]
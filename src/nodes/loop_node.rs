@@ -17,6 +17,8 @@ struct LoopParams {
     index_variable: Option<String>,
     /// Maximum iterations (safety limit, defaults to 1000)
     max_iterations: Option<usize>,
+    /// Number of iterations of the body that may run at the same time (defaults to 1)
+    concurrency: Option<usize>,
 }
 
 impl LoopNode {
@@ -68,6 +70,11 @@ impl NodeType for LoopNode {
                     "description": "Maximum number of iterations (default: 1000)",
                     "minimum": 1,
                     "maximum": 10000
+                },
+                "concurrency": {
+                    "type": "integer",
+                    "description": "Number of body iterations that may run at the same time (default: 1)",
+                    "minimum": 1
                 }
             },
             "required": ["items"]
@@ -115,18 +122,21 @@ impl Node for LoopNode {
             );
         }
 
-        // In a real implementation, this would trigger execution of child nodes for each item
-        // For now, we'll return metadata about the loop
+        let concurrency = params.concurrency.unwrap_or(1);
+
+        // The engine runs the node's nested body once per item and adds the
+        // per-iteration results to this output
         let result = json!({
             "items_count": items_array.len(),
             "item_variable": item_var,
             "index_variable": index_var,
+            "concurrency": concurrency,
             "loop_metadata": {
                 "total_iterations": items_array.len(),
                 "max_iterations": max_iterations
             },
-            // For demonstration, include the first few items
-            "preview_items": items_array.iter().take(3).collect::<Vec<_>>()
+            "preview_items": items_array.iter().take(3).collect::<Vec<_>>(),
+            "items": items_array
         });
 
         Ok(NodeOutput::success(result))
@@ -155,6 +165,10 @@ impl Node for LoopNode {
             anyhow::bail!("max_iterations must be between 1 and 10000");
         }
 
+        if params.concurrency == Some(0) {
+            anyhow::bail!("concurrency must be at least 1");
+        }
+
         Ok(())
     }
 }
@@ -180,6 +194,17 @@ mod tests {
         let output = result.unwrap();
         assert!(output.success);
         assert_eq!(output.data["items_count"], 3);
+        assert_eq!(output.data["items"][2]["id"], 3);
+        assert_eq!(output.data["concurrency"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_loop_node_respects_max_iterations() {
+        let node = LoopNode::new();
+        let context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        let params = json!({"items": [1, 2, 3], "max_iterations": 2});
+
+        assert!(node.execute(&context, &params).await.is_err());
     }
 
     #[tokio::test]
@@ -223,5 +248,9 @@ mod tests {
         // Invalid - variable reference without $
         let params = json!({"items": "my_items"});
         assert!(node.validate_parameters(&params).is_err());
+
+        // Invalid - zero concurrency
+        let params = json!({"items": [1], "concurrency": 0});
+        assert!(node.validate_parameters(&params).is_err());
     }
 }
@@ -1,3 +1,4 @@
+use crate::execution::expression::lookup_path;
 use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -135,17 +136,7 @@ impl Node for MapNode {
 
 /// Helper function to extract a field value from an object using dot notation
 fn get_field_value(item: &Value, field: &str) -> Option<Value> {
-    let parts: Vec<&str> = field.split('.').collect();
-    let mut current = item;
-
-    for part in parts {
-        if part.is_empty() {
-            continue;
-        }
-        current = current.get(part)?;
-    }
-
-    Some(current.clone())
+    lookup_path(item, field).cloned()
 }
 
 /// Transform a single item using the template
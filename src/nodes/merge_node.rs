@@ -9,14 +9,98 @@ pub struct MergeNode {}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct MergeParams {
-    /// Strategy for merging: "all" (wait for all), "any" (first to complete), "majority"
+    /// Strategy for merging: "all" (wait for all), "any" (first to complete), "majority".
+    /// Applied by the engine when it decides which branches reach the node.
     strategy: Option<String>,
-    /// Timeout in seconds (optional)
+    /// Seconds to wait for the remaining branches once the first one delivered (optional)
     timeout: Option<u64>,
-    /// How to combine outputs: "array", "object", "first", "last"
+    /// How to combine outputs: "object", "array", "deep_merge", "zip", "first", "last"
     combine_mode: Option<String>,
 }
 
+const COMBINE_MODES: [&str; 6] = ["object", "array", "deep_merge", "zip", "first", "last"];
+
+/// Combine the outputs of the merged branches, given in the order they arrived
+fn combine(mode: &str, inputs: Vec<(&String, &Value)>) -> anyhow::Result<Value> {
+    let result = match mode {
+        "array" => {
+            let mut items = Vec::new();
+            for (_, value) in inputs {
+                match value {
+                    Value::Array(values) => items.extend(values.iter().cloned()),
+                    value => items.push(value.clone()),
+                }
+            }
+            Value::Array(items)
+        }
+        "deep_merge" => {
+            let mut merged = json!({});
+            for (_, value) in inputs {
+                deep_merge(&mut merged, value);
+            }
+            merged
+        }
+        "zip" => {
+            let mut columns = Vec::new();
+            for (key, value) in inputs {
+                let Some(values) = value.as_array() else {
+                    anyhow::bail!("zip requires every input to be an array, '{}' is not", key);
+                };
+                columns.push((key, values));
+            }
+
+            let len = columns.iter().map(|(_, values)| values.len()).max();
+            let rows = (0..len.unwrap_or(0))
+                .map(|index| {
+                    let row = columns
+                        .iter()
+                        .map(|(key, values)| {
+                            (
+                                (*key).clone(),
+                                values.get(index).cloned().unwrap_or(Value::Null),
+                            )
+                        })
+                        .collect();
+                    Value::Object(row)
+                })
+                .collect();
+            Value::Array(rows)
+        }
+        "first" => inputs
+            .first()
+            .map_or(Value::Null, |(_, value)| (*value).clone()),
+        "last" => inputs
+            .last()
+            .map_or(Value::Null, |(_, value)| (*value).clone()),
+        _ => Value::Object(
+            inputs
+                .into_iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        ),
+    };
+
+    Ok(result)
+}
+
+/// Recursively merge `value` into `target`; objects are merged key by key,
+/// anything else replaces the existing value
+fn deep_merge(target: &mut Value, value: &Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(value)) => {
+            for (key, value) in value {
+                match target.get_mut(key) {
+                    Some(existing) => deep_merge(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, value) => *target = value.clone(),
+    }
+}
+
 impl MergeNode {
     pub fn new() -> Self {
         Self {}
@@ -53,11 +137,12 @@ impl NodeType for MergeNode {
                 },
                 "timeout": {
                     "type": "integer",
-                    "description": "Timeout in seconds"
+                    "minimum": 1,
+                    "description": "Seconds to wait for the remaining branches once the first one delivered"
                 },
                 "combine_mode": {
                     "type": "string",
-                    "enum": ["array", "object", "first", "last"],
+                    "enum": ["object", "array", "deep_merge", "zip", "first", "last"],
                     "description": "How to combine outputs (default: 'object')"
                 }
             }
@@ -74,19 +159,10 @@ impl Node for MergeNode {
     ) -> anyhow::Result<NodeOutput> {
         let params: MergeParams = serde_json::from_value(parameters.clone())?;
 
-        let strategy = params.strategy.unwrap_or_else(|| "all".to_string());
-        let combine_mode = params.combine_mode.unwrap_or_else(|| "object".to_string());
-
-        // In a real implementation, this would wait for multiple paths to complete
-        // and merge their outputs according to the strategy
+        let combine_mode = params.combine_mode.as_deref().unwrap_or("object");
 
-        let result = json!({
-            "strategy": strategy,
-            "combine_mode": combine_mode,
-            "timeout": params.timeout,
-            "merged": true,
-            "inputs_merged": context.inputs.len()
-        });
+        // The engine only passes the branches selected by the strategy
+        let result = combine(combine_mode, context.ordered_inputs())?;
 
         Ok(NodeOutput::success(result))
     }
@@ -101,9 +177,15 @@ impl Node for MergeNode {
         }
 
         if let Some(mode) = &params.combine_mode
-            && !["array", "object", "first", "last"].contains(&mode.as_str())
+            && !COMBINE_MODES.contains(&mode.as_str())
         {
-            anyhow::bail!("combine_mode must be 'array', 'object', 'first', or 'last'");
+            anyhow::bail!(
+                "combine_mode must be 'object', 'array', 'deep_merge', 'zip', 'first', or 'last'"
+            );
+        }
+
+        if params.timeout == Some(0) {
+            anyhow::bail!("timeout must be at least 1 second");
         }
 
         Ok(())
@@ -123,4 +205,94 @@ mod tests {
         let result = node.execute(&context, &params).await;
         assert!(result.is_ok());
     }
+
+    fn context_with_inputs() -> NodeContext {
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        context.add_input("users".to_string(), json!([{"id": 1}, {"id": 2}]));
+        context.add_input("scores".to_string(), json!([10]));
+        context
+    }
+
+    #[tokio::test]
+    async fn test_merge_combine_modes() {
+        let node = MergeNode::new();
+        let context = context_with_inputs();
+
+        let cases = [
+            (
+                "object",
+                json!({"users": [{"id": 1}, {"id": 2}], "scores": [10]}),
+            ),
+            ("array", json!([{"id": 1}, {"id": 2}, 10])),
+            (
+                "zip",
+                json!([{"users": {"id": 1}, "scores": 10}, {"users": {"id": 2}, "scores": null}]),
+            ),
+            ("first", json!([{"id": 1}, {"id": 2}])),
+            ("last", json!([10])),
+        ];
+
+        for (mode, expected) in cases {
+            let output = node
+                .execute(&context, &json!({"combine_mode": mode}))
+                .await
+                .unwrap();
+            assert_eq!(output.data, expected, "combine_mode {}", mode);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_merge_deep_merge() {
+        let node = MergeNode::new();
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        context.add_input(
+            "defaults".to_string(),
+            json!({"retry": {"count": 3, "delay": 1}, "tags": ["a"]}),
+        );
+        context.add_input(
+            "overrides".to_string(),
+            json!({"retry": {"delay": 5}, "tags": ["b"]}),
+        );
+
+        let output = node
+            .execute(&context, &json!({"combine_mode": "deep_merge"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            output.data,
+            json!({"retry": {"count": 3, "delay": 5}, "tags": ["b"]})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_zip_requires_arrays() {
+        let node = MergeNode::new();
+        let mut context = context_with_inputs();
+        context.add_input("total".to_string(), json!(2));
+
+        let result = node
+            .execute(&context, &json!({"combine_mode": "zip"}))
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_validation() {
+        let node = MergeNode::new();
+        assert!(
+            node.validate_parameters(
+                &json!({"strategy": "majority", "combine_mode": "zip", "timeout": 30})
+            )
+            .is_ok()
+        );
+        assert!(
+            node.validate_parameters(&json!({"strategy": "some"}))
+                .is_err()
+        );
+        assert!(
+            node.validate_parameters(&json!({"combine_mode": "sum"}))
+                .is_err()
+        );
+        assert!(node.validate_parameters(&json!({"timeout": 0})).is_err());
+    }
 }
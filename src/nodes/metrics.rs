@@ -100,6 +100,65 @@ impl NodeType for MetricsNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "metric_name": {
+                    "type": ["string", "null"],
+                    "description": "Metric emitted"
+                },
+                "value": {
+                    "type": ["number", "null"],
+                    "description": "Metric value"
+                },
+                "unit": {
+                    "type": ["string", "null"],
+                    "description": "Metric unit"
+                },
+                "tags": {
+                    "description": "Tags of the metric"
+                },
+                "namespace": {
+                    "type": ["string", "null"],
+                    "description": "Namespace of the metric"
+                },
+                "timestamp": {
+                    "type": ["integer", "null"],
+                    "description": "Time of the metric as a Unix timestamp"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "metric_status": {
+                    "type": "string",
+                    "description": "Status of the metric",
+                    "examples": [
+                        "emitted"
+                    ]
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
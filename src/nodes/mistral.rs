@@ -133,6 +133,133 @@ impl NodeType for MistralNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("mistral_api")
     }
+
+    fn output_schema(&self) -> Value {
+        let choices = json!({
+            "type": "array",
+            "description": "Completions, for `chat_completion` and `completion`",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "index": {
+                        "type": "integer",
+                        "description": "Index of the choice"
+                    },
+                    "message": {
+                        "type": "object",
+                        "properties": {
+                            "role": {
+                                "type": "string",
+                                "description": "Role of the author"
+                            },
+                            "content": {
+                                "type": "string",
+                                "description": "Message content"
+                            }
+                        }
+                    },
+                    "text": {
+                        "type": "string",
+                        "description": "Completion text, for `completion`"
+                    },
+                    "finish_reason": {
+                        "type": "string",
+                        "description": "Why the completion stopped"
+                    }
+                }
+            }
+        });
+
+        let usage = json!({
+            "type": "object",
+            "properties": {
+                "prompt_tokens": {
+                    "type": "integer",
+                    "description": "Tokens in the prompt"
+                },
+                "completion_tokens": {
+                    "type": "integer",
+                    "description": "Tokens in the completion"
+                },
+                "total_tokens": {
+                    "type": "integer",
+                    "description": "Tokens used in total"
+                }
+            }
+        });
+
+        let capabilities = json!({
+            "type": "object",
+            "properties": {
+                "completion_chat": {
+                    "type": "boolean",
+                    "description": "Supports chat completions"
+                },
+                "completion_fim": {
+                    "type": "boolean",
+                    "description": "Supports fill-in-the-middle completions"
+                },
+                "function_calling": {
+                    "type": "boolean",
+                    "description": "Supports function calling"
+                },
+                "fine_tuning": {
+                    "type": "boolean",
+                    "description": "Supports fine-tuning"
+                }
+            },
+            "description": "Capabilities of the model, for `get_model`"
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "model": {
+                    "type": "string",
+                    "description": "Model used, for `chat_completion`, `completion` and `embeddings`"
+                },
+                "choices": choices,
+                "usage": usage,
+                "created": {
+                    "type": "integer",
+                    "description": "Creation time as a Unix timestamp"
+                },
+                "id": {
+                    "type": "string",
+                    "description": "ID of the completion, or of the model for `get_model`"
+                },
+                "data": {
+                    "type": "array",
+                    "description": "Embeddings for `embeddings`, or models for `list_models`",
+                    "items": {
+                        "type": "object"
+                    }
+                },
+                "object": {
+                    "type": "string",
+                    "description": "Object type, for `get_model`"
+                },
+                "owned_by": {
+                    "type": "string",
+                    "description": "Owner of the model, for `get_model`"
+                },
+                "capabilities": capabilities,
+                "max_context_length": {
+                    "type": "integer",
+                    "description": "Context window of the model, for `get_model`"
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -176,6 +176,31 @@ impl NodeType for MySQLNode {
             }
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "rows_affected": {
+                    "type": "integer",
+                    "description": "Number of rows changed by the statement"
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
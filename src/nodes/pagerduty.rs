@@ -113,6 +113,51 @@ impl NodeType for PagerDutyNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("pagerduty_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "incident_id": {
+                    "type": ["string", "null"],
+                    "description": "Incident the operation targeted"
+                },
+                "title": {
+                    "type": ["string", "null"],
+                    "description": "Title of the incident"
+                },
+                "service_id": {
+                    "type": ["string", "null"],
+                    "description": "Service of the incident"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -102,6 +102,93 @@ impl NodeType for PerformanceDashboardNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "workflow_id": {
+                    "type": ["string", "null"],
+                    "description": "Workflow the operation targeted"
+                },
+                "execution_time_ms": {
+                    "type": ["integer", "null"],
+                    "description": "Recorded execution time in milliseconds"
+                },
+                "success_count": {
+                    "type": ["integer", "null"],
+                    "description": "Recorded successful executions"
+                },
+                "error_count": {
+                    "type": ["integer", "null"],
+                    "description": "Recorded failed executions"
+                },
+                "time_window": {
+                    "type": "string",
+                    "description": "Time window of the statistics"
+                },
+                "threshold": {
+                    "type": ["number", "null"],
+                    "description": "Alert threshold"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "performance_metrics": {
+                    "type": "object",
+                    "description": "Statistics over the time window",
+                    "properties": {
+                        "avg_execution_time_ms": {
+                            "type": "integer",
+                            "description": "Average execution time in milliseconds"
+                        },
+                        "p95_execution_time_ms": {
+                            "type": "integer",
+                            "description": "95th percentile execution time in milliseconds"
+                        },
+                        "p99_execution_time_ms": {
+                            "type": "integer",
+                            "description": "99th percentile execution time in milliseconds"
+                        },
+                        "total_executions": {
+                            "type": "integer",
+                            "description": "Number of executions"
+                        },
+                        "success_rate": {
+                            "type": "number",
+                            "description": "Share of successful executions"
+                        },
+                        "error_rate": {
+                            "type": "number",
+                            "description": "Share of failed executions"
+                        }
+                    }
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("get_statistics" | "generate_report")
+        )
+    }
 }
 
 #[async_trait]
@@ -105,10 +105,8 @@ impl Node for RetryNode {
 
         let max_attempts = params.max_attempts.unwrap_or(3);
 
-        // In a real implementation, this would wrap child node execution
-        // and retry on failures with exponential backoff
-
-        // Simulate retry logic
+        // Preview the backoff schedule; the engine retries nodes that declare
+        // a `retry` policy on their definition
         let delays: Vec<u64> = (0..max_attempts)
             .map(|i| self.calculate_delay(i, &params).as_millis() as u64)
             .collect();
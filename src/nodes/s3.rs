@@ -104,6 +104,47 @@ impl NodeType for S3Node {
             "additionalProperties": false
         })
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "bucket": {
+                    "type": "string",
+                    "description": "Bucket the operation targeted"
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Object key the operation targeted"
+                },
+                "content_type": {
+                    "type": ["string", "null"],
+                    "description": "Content type of the object"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -1,5 +1,6 @@
 use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
 use async_trait::async_trait;
+use chrono_tz::Tz;
 use cron::Schedule;
 use serde::Deserialize;
 use std::str::FromStr;
@@ -14,15 +15,46 @@ pub struct ScheduleTriggerParams {
     pub timezone: String,
     /// Optional description
     pub description: Option<String>,
+    /// What to do with fire times missed while no scheduler was running
+    #[serde(default)]
+    pub misfire: MisfirePolicy,
 }
 
 fn default_timezone() -> String {
     "UTC".to_string()
 }
 
+/// How the scheduler handles fire times it missed, e.g. after downtime
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MisfirePolicy {
+    /// Drop missed fire times; only fire on time
+    Skip,
+    /// Fire once for all missed fire times
+    #[default]
+    FireOnce,
+    /// Fire once for every missed fire time
+    CatchUp,
+}
+
+impl ScheduleTriggerParams {
+    /// Parse the cron expression and the timezone of the schedule
+    pub fn schedule(&self) -> anyhow::Result<(Schedule, Tz)> {
+        let schedule = Schedule::from_str(&self.cron)
+            .map_err(|e| anyhow::anyhow!("Invalid cron expression '{}': {}", self.cron, e))?;
+        let timezone = self
+            .timezone
+            .parse::<Tz>()
+            .map_err(|e| anyhow::anyhow!("Invalid timezone '{}': {}", self.timezone, e))?;
+
+        Ok((schedule, timezone))
+    }
+}
+
 /// Schedule Trigger node - starts workflow based on cron schedule
-/// Note: This node only validates and stores the schedule configuration.
-/// Actual scheduling is handled externally.
+///
+/// The scheduler queues an execution at every fire time and passes the
+/// schedule metadata as the execution input.
 pub struct ScheduleTriggerNode;
 
 impl NodeType for ScheduleTriggerNode {
@@ -55,6 +87,12 @@ impl NodeType for ScheduleTriggerNode {
                 "description": {
                     "type": "string",
                     "description": "Description of this schedule trigger"
+                },
+                "misfire": {
+                    "type": "string",
+                    "enum": ["skip", "fire_once", "catch_up"],
+                    "description": "What to do with fire times missed while no scheduler was running",
+                    "default": "fire_once"
                 }
             },
             "required": ["cron"],
@@ -70,14 +108,18 @@ impl Node for ScheduleTriggerNode {
         context: &NodeContext,
         _parameters: &serde_json::Value,
     ) -> anyhow::Result<NodeOutput> {
-        // Schedule trigger passes through input data
-        // In practice, this would include timestamp and other schedule metadata
-        let data = context.get_main_input().cloned().unwrap_or_else(|| {
-            serde_json::json!({
-                "trigger_type": "schedule",
-                "triggered_at": chrono::Utc::now().to_rfc3339()
-            })
-        });
+        // Schedule trigger passes through the schedule metadata the scheduler
+        // queued the execution with
+        let data = context
+            .get_main_input()
+            .or_else(|| context.variables.get("input"))
+            .cloned()
+            .unwrap_or_else(|| {
+                serde_json::json!({
+                    "trigger_type": "schedule",
+                    "triggered_at": chrono::Utc::now().to_rfc3339()
+                })
+            });
 
         Ok(NodeOutput::success(data))
     }
@@ -85,9 +127,8 @@ impl Node for ScheduleTriggerNode {
     fn validate_parameters(&self, parameters: &serde_json::Value) -> anyhow::Result<()> {
         let params: ScheduleTriggerParams = serde_json::from_value(parameters.clone())?;
 
-        // Validate cron expression
-        Schedule::from_str(&params.cron)
-            .map_err(|e| anyhow::anyhow!("Invalid cron expression '{}': {}", params.cron, e))?;
+        // Validate cron expression and timezone
+        params.schedule()?;
 
         Ok(())
     }
@@ -140,6 +181,51 @@ mod tests {
         assert!(node.validate_parameters(&invalid_params).is_err());
     }
 
+    #[tokio::test]
+    async fn test_schedule_trigger_passes_schedule_metadata() {
+        let node = ScheduleTriggerNode;
+        let mut context = NodeContext::new("exec-123".to_string(), "trigger-1".to_string());
+        context.variables.insert(
+            "input".to_string(),
+            serde_json::json!({
+                "trigger_type": "schedule",
+                "scheduled_for": "2024-01-01T00:00:00Z"
+            }),
+        );
+
+        let result = node
+            .execute(&context, &serde_json::json!({"cron": "0 0 0 * * *"}))
+            .await
+            .unwrap();
+        assert_eq!(result.data["scheduled_for"], "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_schedule_trigger_timezone_and_misfire() {
+        let node = ScheduleTriggerNode;
+
+        let params = serde_json::json!({
+            "cron": "0 30 9 * * MON-FRI",
+            "timezone": "Europe/Madrid",
+            "misfire": "catch_up"
+        });
+        assert!(node.validate_parameters(&params).is_ok());
+
+        let params: ScheduleTriggerParams = serde_json::from_value(params).unwrap();
+        assert_eq!(params.misfire, MisfirePolicy::CatchUp);
+        assert_eq!(params.schedule().unwrap().1, Tz::Europe__Madrid);
+
+        let invalid_timezone = serde_json::json!({
+            "cron": "0 0 0 * * *",
+            "timezone": "Mars/Olympus_Mons"
+        });
+        assert!(node.validate_parameters(&invalid_timezone).is_err());
+
+        let default_params: ScheduleTriggerParams =
+            serde_json::from_value(serde_json::json!({"cron": "0 0 0 * * *"})).unwrap();
+        assert_eq!(default_params.misfire, MisfirePolicy::FireOnce);
+    }
+
     #[test]
     fn test_various_cron_patterns() {
         let node = ScheduleTriggerNode;
@@ -117,6 +117,50 @@ impl NodeType for SendGridNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("sendgrid_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "to": {
+                    "type": ["array", "null"],
+                    "description": "Recipients of the email",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "from": {
+                    "type": ["string", "null"],
+                    "description": "Sender of the email"
+                },
+                "subject": {
+                    "type": ["string", "null"],
+                    "description": "Subject of the email"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
 }
 
 #[async_trait]
@@ -9,10 +9,61 @@ pub struct SplitNode {}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct SplitParams {
-    /// Number of parallel branches
+    /// Array to fan out, one branch per item (can be a reference like $items or direct array).
+    /// Defaults to the node's input when it is an array and `branches` is not set.
+    items: Option<Value>,
+    /// Field of each item used as its branch key (defaults to the item index)
+    key: Option<String>,
+    /// Number of parallel branches, each receiving the node's input
     branches: Option<usize>,
-    /// Whether to wait for all branches to complete
+    /// Whether to wait for all branches to complete before failing
     wait_for_all: Option<bool>,
+    /// Number of branches that may run at the same time (defaults to all)
+    concurrency: Option<usize>,
+}
+
+impl SplitParams {
+    /// Items the branches run for
+    fn items(&self, context: &NodeContext) -> anyhow::Result<Vec<Value>> {
+        let input = context.get_main_input().cloned().unwrap_or(Value::Null);
+
+        match (&self.items, self.branches) {
+            (Some(Value::Array(items)), _) => Ok(items.clone()),
+            (Some(Value::String(reference)), _) => {
+                let var_name = reference.trim_start_matches('$');
+                match context.get_variable(var_name) {
+                    Some(Value::Array(items)) => Ok(items.clone()),
+                    Some(_) => anyhow::bail!("Variable '{}' is not an array", var_name),
+                    None => anyhow::bail!("Variable '{}' not found in context", var_name),
+                }
+            }
+            (Some(_), _) => anyhow::bail!("items must be an array or a variable reference"),
+            (None, Some(branches)) => Ok(vec![input; branches]),
+            (None, None) => match input {
+                Value::Array(items) => Ok(items),
+                _ => anyhow::bail!("split needs items, an array input, or a number of branches"),
+            },
+        }
+    }
+
+    /// Key identifying the branch of an item
+    fn branch_key(&self, index: usize, item: &Value) -> anyhow::Result<String> {
+        let Some(field) = &self.key else {
+            return Ok(index.to_string());
+        };
+
+        match item.get(field) {
+            Some(Value::String(key)) => Ok(key.clone()),
+            Some(Value::Null) | None => {
+                anyhow::bail!(
+                    "Item {} has no '{}' field to use as branch key",
+                    index,
+                    field
+                )
+            }
+            Some(key) => Ok(key.to_string()),
+        }
+    }
 }
 
 impl SplitNode {
@@ -44,15 +95,31 @@ impl NodeType for SplitNode {
         json!({
             "type": "object",
             "properties": {
+                "items": {
+                    "description": "Array to fan out, one branch per item, or variable reference (default: the input array)",
+                    "oneOf": [
+                        {"type": "array"},
+                        {"type": "string", "pattern": "^\\$[a-zA-Z_][a-zA-Z0-9_]*$"}
+                    ]
+                },
+                "key": {
+                    "type": "string",
+                    "description": "Field of each item used as its branch key (default: the item index)"
+                },
                 "branches": {
                     "type": "integer",
-                    "description": "Number of parallel branches (default: 2)",
+                    "description": "Number of parallel branches, each receiving the input",
                     "minimum": 2,
                     "maximum": 10
                 },
                 "wait_for_all": {
                     "type": "boolean",
-                    "description": "Wait for all branches to complete (default: true)"
+                    "description": "Wait for all branches to complete before failing (default: true)"
+                },
+                "concurrency": {
+                    "type": "integer",
+                    "description": "Number of branches that may run at the same time (default: all)",
+                    "minimum": 1
                 }
             }
         })
@@ -63,20 +130,28 @@ impl NodeType for SplitNode {
 impl Node for SplitNode {
     async fn execute(
         &self,
-        _context: &NodeContext,
+        context: &NodeContext,
         parameters: &serde_json::Value,
     ) -> anyhow::Result<NodeOutput> {
         let params: SplitParams = serde_json::from_value(parameters.clone())?;
 
-        let branches = params.branches.unwrap_or(2);
-        let wait_for_all = params.wait_for_all.unwrap_or(true);
-
-        // In a real implementation, this would spawn multiple parallel execution paths
-
+        let branches = params
+            .items(context)?
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| {
+                let key = params.branch_key(index, &item)?;
+                Ok(json!({"key": key, "item": item}))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // The engine runs the node's nested body once per branch and adds the
+        // per-branch results to this output
         let result = json!({
-            "branches": branches,
-            "wait_for_all": wait_for_all,
-            "split_initiated": true
+            "branch_count": branches.len(),
+            "wait_for_all": params.wait_for_all.unwrap_or(true),
+            "concurrency": params.concurrency,
+            "branches": branches
         });
 
         Ok(NodeOutput::success(result))
@@ -91,6 +166,10 @@ impl Node for SplitNode {
             anyhow::bail!("branches must be between 2 and 10");
         }
 
+        if params.concurrency == Some(0) {
+            anyhow::bail!("concurrency must be at least 1");
+        }
+
         Ok(())
     }
 }
@@ -105,7 +184,56 @@ mod tests {
         let context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
         let params = json!({"branches": 3});
 
-        let result = node.execute(&context, &params).await;
-        assert!(result.is_ok());
+        let result = node.execute(&context, &params).await.unwrap();
+        assert_eq!(result.data["branch_count"], 3);
+        assert_eq!(result.data["branches"][2]["key"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_split_items_with_key() {
+        let node = SplitNode::new();
+        let context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        let params = json!({
+            "items": [{"order_id": "A-1"}, {"order_id": 7}],
+            "key": "order_id",
+            "wait_for_all": false
+        });
+
+        let result = node.execute(&context, &params).await.unwrap();
+        assert_eq!(result.data["branches"][0]["key"], "A-1");
+        assert_eq!(result.data["branches"][1]["key"], "7");
+        assert_eq!(result.data["branches"][1]["item"], json!({"order_id": 7}));
+        assert_eq!(result.data["wait_for_all"], false);
+
+        let params = json!({"items": [{"id": 1}], "key": "order_id"});
+        assert!(node.execute(&context, &params).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_split_input_array() {
+        let node = SplitNode::new();
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        context.add_input("tickets".to_string(), json!(["t1", "t2"]));
+
+        let result = node.execute(&context, &json!({})).await.unwrap();
+        assert_eq!(result.data["branch_count"], 2);
+        assert_eq!(result.data["branches"][1]["item"], "t2");
+
+        // Configured branches each receive the whole input
+        let result = node
+            .execute(&context, &json!({"branches": 3}))
+            .await
+            .unwrap();
+        assert_eq!(result.data["branch_count"], 3);
+        assert_eq!(result.data["branches"][0]["item"], json!(["t1", "t2"]));
+    }
+
+    #[tokio::test]
+    async fn test_split_requires_items() {
+        let node = SplitNode::new();
+        let mut context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        context.add_input("main".to_string(), json!({"not": "an array"}));
+
+        assert!(node.execute(&context, &json!({})).await.is_err());
     }
 }
@@ -162,6 +162,51 @@ impl NodeType for StripeNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("stripe_api")
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "credentials_name": {
+                    "type": "string",
+                    "description": "Name of the credentials used"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "amount": {
+                    "type": ["integer", "null"],
+                    "description": "Amount in the smallest currency unit"
+                },
+                "currency": {
+                    "type": ["string", "null"],
+                    "description": "Currency of the amount"
+                },
+                "customer_id": {
+                    "type": ["string", "null"],
+                    "description": "Customer the operation targeted"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, _parameters: &serde_json::Value) -> bool {
+        true
+    }
 }
 
 #[async_trait]
@@ -167,7 +167,7 @@ impl Node for SwitchNode {
             "has_default": params.default.is_some()
         });
 
-        Ok(NodeOutput::success(result))
+        Ok(NodeOutput::success(result).with_port(selected_path))
     }
 
     fn validate_parameters(&self, parameters: &Value) -> anyhow::Result<()> {
@@ -220,6 +220,7 @@ mod tests {
         assert!(result.is_ok());
         let output = result.unwrap();
         assert_eq!(output.data["selected_path"], "active_path");
+        assert_eq!(output.port, Some("active_path".to_string()));
     }
 
     #[tokio::test]
@@ -262,6 +263,7 @@ mod tests {
         assert!(result.is_ok());
         let output = result.unwrap();
         assert_eq!(output.data["selected_path"], "fallback_path");
+        assert_eq!(output.port, Some("fallback_path".to_string()));
     }
 
     #[test]
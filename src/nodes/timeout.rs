@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 /// Timeout node to enforce time limits on operations
+///
+/// The engine runs the nested body of the node under a shared deadline and
+/// applies `on_timeout` when it expires.
 #[derive(Clone)]
 pub struct TimeoutNode {}
 
@@ -95,13 +98,12 @@ impl Node for TimeoutNode {
 
         let on_timeout = params.on_timeout.unwrap_or_else(|| "error".to_string());
 
-        // In a real implementation, this would wrap child node execution
-        // with tokio::time::timeout and handle timeouts appropriately
-
+        // The engine enforces the deadline on the nested body using this configuration
         let result = json!({
             "timeout_ms": timeout_ms,
             "on_timeout": on_timeout,
             "has_default_value": params.default_value.is_some(),
+            "default_value": params.default_value.unwrap_or(Value::Null),
             "context_execution_id": context.execution_id
         });
 
@@ -156,6 +158,21 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_timeout_node_default_value() {
+        let node = TimeoutNode::new();
+        let context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        let params = json!({
+            "timeout_milliseconds": 500,
+            "on_timeout": "default",
+            "default_value": {"status": "pending"}
+        });
+
+        let output = node.execute(&context, &params).await.unwrap();
+        assert_eq!(output.data["timeout_ms"], 500);
+        assert_eq!(output.data["default_value"], json!({"status": "pending"}));
+    }
+
     #[test]
     fn test_timeout_validation() {
         let node = TimeoutNode::new();
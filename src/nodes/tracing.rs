@@ -102,6 +102,62 @@ impl NodeType for TracingNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "span_name": {
+                    "type": ["string", "null"],
+                    "description": "Name of the span"
+                },
+                "trace_id": {
+                    "type": "string",
+                    "description": "ID of the trace"
+                },
+                "span_id": {
+                    "type": "string",
+                    "description": "ID of the span"
+                },
+                "attributes": {
+                    "description": "Attributes of the span"
+                },
+                "parent_span_id": {
+                    "type": ["string", "null"],
+                    "description": "ID of the parent span"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "trace_status": {
+                    "type": "string",
+                    "description": "Status of the trace",
+                    "examples": [
+                        "active"
+                    ]
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(parameters["operation"].as_str(), Some("get_trace_context"))
+    }
 }
 
 #[async_trait]
@@ -1,3 +1,4 @@
+use crate::execution::expression::lookup_path;
 use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -82,33 +83,11 @@ impl Node for TransformNode {
     }
 }
 
-/// Extract a field from JSON using a simple path expression
+/// Extract a field from JSON using a path expression
 fn extract_field(data: &serde_json::Value, path: &str) -> anyhow::Result<serde_json::Value> {
-    let parts: Vec<&str> = path.split('.').collect();
-    let mut current = data;
-
-    for part in parts {
-        if part.is_empty() {
-            continue;
-        }
-
-        // Handle array indexing
-        if let Some(idx_str) = part.strip_prefix('[').and_then(|s| s.strip_suffix(']'))
-            && let Ok(idx) = idx_str.parse::<usize>()
-        {
-            current = current
-                .get(idx)
-                .ok_or_else(|| anyhow::anyhow!("Array index out of bounds: {}", idx))?;
-            continue;
-        }
-
-        // Regular field access
-        current = current
-            .get(part)
-            .ok_or_else(|| anyhow::anyhow!("Field not found: {}", part))?;
-    }
-
-    Ok(current.clone())
+    lookup_path(data, path)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Field not found: {}", path))
 }
 
 /// Substitute template values with data from input
@@ -172,6 +151,10 @@ mod tests {
 
         let result = extract_field(&data, "user.age").unwrap();
         assert_eq!(result, serde_json::json!(30));
+
+        let data = serde_json::json!({"items": [{"id": "a"}, {"id": "b"}]});
+        let result = extract_field(&data, "items[1].id").unwrap();
+        assert_eq!(result, serde_json::json!("b"));
     }
 
     #[test]
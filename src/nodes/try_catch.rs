@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 
 /// Try/Catch node for error handling with fallback paths
+///
+/// The nodes in its nested body run once; the engine applies `error_strategy`
+/// when the body fails.
 #[derive(Clone)]
 pub struct TryCatchNode {}
 
@@ -75,14 +78,13 @@ impl Node for TryCatchNode {
         let continue_on_error = params.continue_on_error.unwrap_or(true);
         let error_strategy = params.error_strategy.unwrap_or_else(|| "catch".to_string());
 
-        // In a real implementation, this would wrap child node execution
-        // and catch any errors, routing to appropriate paths
-
+        // The engine runs the nested body and handles its errors using this configuration
         let result = json!({
             "try_catch_active": true,
             "continue_on_error": continue_on_error,
             "error_strategy": error_strategy,
             "has_default_value": params.default_value.is_some(),
+            "default_value": params.default_value.unwrap_or(Value::Null),
             "context_execution_id": context.execution_id
         });
 
@@ -119,6 +121,18 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_try_catch_defaults() {
+        let node = TryCatchNode::new();
+        let context = NodeContext::new("exec-123".to_string(), "node-1".to_string());
+        let params = json!({"default_value": {"items": []}});
+
+        let output = node.execute(&context, &params).await.unwrap();
+        assert_eq!(output.data["continue_on_error"], true);
+        assert_eq!(output.data["error_strategy"], "catch");
+        assert_eq!(output.data["default_value"], json!({"items": []}));
+    }
+
     #[test]
     fn test_try_catch_validation() {
         let node = TryCatchNode::new();
@@ -121,6 +121,137 @@ impl NodeType for VaultNode {
     fn required_credential_type(&self) -> Option<&str> {
         Some("vault")
     }
+
+    fn output_schema(&self) -> Value {
+        let auth = json!({
+            "type": "object",
+            "description": "Token issued, for `create_token`, `renew_token` and `approle_login`",
+            "properties": {
+                "client_token": {
+                    "type": "string",
+                    "description": "Client token"
+                },
+                "accessor": {
+                    "type": "string",
+                    "description": "Accessor of the token"
+                },
+                "policies": {
+                    "type": "array",
+                    "description": "Policies of the token",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "lease_duration": {
+                    "type": "integer",
+                    "description": "Lease duration in seconds"
+                },
+                "renewable": {
+                    "type": "boolean",
+                    "description": "Whether the token can be renewed"
+                },
+                "metadata": {
+                    "type": "object",
+                    "description": "Metadata of the login, for `approle_login`"
+                }
+            }
+        });
+
+        json!({
+            "type": "object",
+            "properties": {
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Path the operation targeted"
+                },
+                "data": {
+                    "type": "object",
+                    "description": "Secret data, for `read_secret`"
+                },
+                "version": {
+                    "type": ["integer", "string"],
+                    "description": "Version of the secret, or of Vault for `seal_status`"
+                },
+                "created_time": {
+                    "type": "string",
+                    "description": "Creation time of the secret version, for `read_secret` and `write_secret`"
+                },
+                "metadata": {
+                    "type": "object",
+                    "description": "Secret metadata, for `read_secret` and `read_kv_metadata`"
+                },
+                "deleted": {
+                    "type": "boolean",
+                    "description": "Whether the secret was deleted, for `delete_secret`"
+                },
+                "keys": {
+                    "type": "array",
+                    "description": "Secrets under the path, for `list_secrets`",
+                    "items": {
+                        "type": "string"
+                    }
+                },
+                "auth": auth,
+                "revoked": {
+                    "type": "boolean",
+                    "description": "Whether the token was revoked, for `revoke_token`"
+                },
+                "enabled": {
+                    "type": "boolean",
+                    "description": "Whether the audit device was enabled, for `enable_audit`"
+                },
+                "type": {
+                    "type": "string",
+                    "description": "Type of the audit device or seal, for `enable_audit` and `seal_status`"
+                },
+                "sealed": {
+                    "type": "boolean",
+                    "description": "Whether Vault is sealed, for `seal_status`"
+                },
+                "initialized": {
+                    "type": "boolean",
+                    "description": "Whether Vault is initialized, for `seal_status`"
+                },
+                "cluster_name": {
+                    "type": "string",
+                    "description": "Name of the cluster, for `seal_status`"
+                },
+                "cluster_id": {
+                    "type": "string",
+                    "description": "ID of the cluster, for `seal_status`"
+                },
+                "progress": {
+                    "type": "integer",
+                    "description": "Unseal progress, for `seal_status`"
+                },
+                "n": {
+                    "type": "integer",
+                    "description": "Number of key shares, for `seal_status`"
+                },
+                "t": {
+                    "type": "integer",
+                    "description": "Key shares needed to unseal, for `seal_status`"
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Reads run for real during dry runs
+        !matches!(
+            parameters["operation"].as_str(),
+            Some("read_secret" | "list_secrets" | "read_kv_metadata" | "seal_status")
+        )
+    }
 }
 
 #[async_trait]
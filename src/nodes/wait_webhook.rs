@@ -5,6 +5,9 @@ use serde_json::{Value, json};
 use uuid::Uuid;
 
 /// Wait for webhook node that pauses execution until an external event occurs
+///
+/// The engine suspends the execution after this node and resumes it when the
+/// wait is posted to `/api/v1/resume/{wait_id}`.
 #[derive(Clone)]
 pub struct WaitWebhookNode {}
 
@@ -16,7 +19,7 @@ struct WaitWebhookParams {
     timeout_seconds: Option<u64>,
     /// Expected payload schema (for validation)
     expected_schema: Option<Value>,
-    /// Return URL path (defaults to /webhook/resume/{wait_id})
+    /// Return URL path (defaults to /api/v1/resume/{wait_id})
     webhook_path: Option<String>,
 }
 
@@ -65,7 +68,7 @@ impl NodeType for WaitWebhookNode {
                 },
                 "webhook_path": {
                     "type": "string",
-                    "description": "Custom webhook path (defaults to /webhook/resume/{wait_id})"
+                    "description": "Custom webhook path (defaults to /api/v1/resume/{wait_id})"
                 }
             }
         })
@@ -87,18 +90,11 @@ impl Node for WaitWebhookNode {
         // Generate webhook URL
         let webhook_path = params
             .webhook_path
-            .unwrap_or_else(|| format!("/webhook/resume/{}", wait_id));
+            .unwrap_or_else(|| format!("/api/v1/resume/{}", wait_id));
 
         let timeout_seconds = params.timeout_seconds.unwrap_or(3600); // Default 1 hour
 
-        // In a real implementation, this would:
-        // 1. Store the execution state in the database
-        // 2. Register the webhook endpoint
-        // 3. Pause execution
-        // 4. Wait for the webhook to be called
-        // 5. Resume execution with the webhook payload
-
-        // For now, we'll simulate the setup
+        // The engine stores the execution state and suspends it after this node
         let result = json!({
             "wait_id": wait_id,
             "webhook_url": webhook_path,
@@ -151,7 +147,13 @@ mod tests {
         let output = result.unwrap();
         assert!(output.success);
         assert!(output.data["wait_id"].is_string());
-        assert!(output.data["webhook_url"].is_string());
+        assert_eq!(
+            output.data["webhook_url"],
+            format!(
+                "/api/v1/resume/{}",
+                output.data["wait_id"].as_str().unwrap()
+            )
+        );
     }
 
     #[tokio::test]
@@ -1,6 +1,8 @@
+use crate::execution::expression::{self, ExpressionScope};
 use crate::models::{Node, NodeCategory, NodeContext, NodeOutput, NodeSubcategory, NodeType};
 use async_trait::async_trait;
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Debug, Deserialize)]
 pub struct WebhookTriggerParams {
@@ -9,6 +11,36 @@ pub struct WebhookTriggerParams {
     pub method: String,
     /// Optional description
     pub description: Option<String>,
+    /// Expression over the request payload (`$input`) computing the
+    /// idempotency key, used when the request has no `Idempotency-Key` header
+    pub idempotency_key: Option<String>,
+}
+
+impl WebhookTriggerParams {
+    /// Evaluate the idempotency key expression against a request payload.
+    ///
+    /// Returns `None` when no expression is configured or it yields null.
+    pub fn idempotency_key(&self, payload: &serde_json::Value) -> anyhow::Result<Option<String>> {
+        let Some(template) = &self.idempotency_key else {
+            return Ok(None);
+        };
+
+        let empty = HashMap::new();
+        let scope = ExpressionScope {
+            input: payload.clone(),
+            nodes: &empty,
+            variables: &empty,
+            execution: serde_json::Value::Null,
+        };
+        let key = match expression::resolve_parameters(&template.clone().into(), &scope)? {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(key) if key.is_empty() => None,
+            serde_json::Value::String(key) => Some(key),
+            other => Some(other.to_string()),
+        };
+
+        Ok(key)
+    }
 }
 
 fn default_method() -> String {
@@ -44,6 +76,10 @@ impl NodeType for WebhookTriggerNode {
                 "description": {
                     "type": "string",
                     "description": "Description of this webhook trigger"
+                },
+                "idempotency_key": {
+                    "type": "string",
+                    "description": "Expression over the request payload computing the idempotency key, e.g. {{ $input.event.id }}"
                 }
             },
             "additionalProperties": false
@@ -124,6 +160,37 @@ mod tests {
         assert!(node.validate_parameters(&invalid_params).is_err());
     }
 
+    #[test]
+    fn test_webhook_trigger_idempotency_key() {
+        let payload = serde_json::json!({"event": {"id": "evt_42", "sequence": 7}});
+
+        let params: WebhookTriggerParams =
+            serde_json::from_value(serde_json::json!({"idempotency_key": "{{ $input.event.id }}"}))
+                .unwrap();
+        assert_eq!(
+            params.idempotency_key(&payload).unwrap(),
+            Some("evt_42".to_string())
+        );
+
+        let params: WebhookTriggerParams = serde_json::from_value(serde_json::json!({
+            "idempotency_key": "order-{{ $input.event.sequence }}"
+        }))
+        .unwrap();
+        assert_eq!(
+            params.idempotency_key(&payload).unwrap(),
+            Some("order-7".to_string())
+        );
+
+        // Missing fields and unset expressions give no key
+        let params: WebhookTriggerParams =
+            serde_json::from_value(serde_json::json!({"idempotency_key": "{{ $input.missing }}"}))
+                .unwrap();
+        assert_eq!(params.idempotency_key(&payload).unwrap(), None);
+
+        let params: WebhookTriggerParams = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(params.idempotency_key(&payload).unwrap(), None);
+    }
+
     #[test]
     fn test_webhook_trigger_category() {
         use crate::models::NodeCategory;
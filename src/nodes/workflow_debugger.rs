@@ -168,10 +168,8 @@ impl Node for WorkflowDebuggerNode {
 
         // Validate required parameters based on operation
         match params.operation.as_str() {
-            "start_debug_session" => {
-                if params.workflow_id.is_none() {
-                    anyhow::bail!("start_debug_session operation requires 'workflow_id' parameter");
-                }
+            "start_debug_session" if params.workflow_id.is_none() => {
+                anyhow::bail!("start_debug_session operation requires 'workflow_id' parameter");
             }
             "set_breakpoint" | "remove_breakpoint" => {
                 if params.session_id.is_none() {
@@ -187,18 +185,16 @@ impl Node for WorkflowDebuggerNode {
                     );
                 }
             }
-            "step_over" | "step_into" | "continue_execution" | "get_call_stack" => {
-                if params.session_id.is_none() {
-                    anyhow::bail!(
-                        "{} operation requires 'session_id' parameter",
-                        params.operation
-                    );
-                }
+            "step_over" | "step_into" | "continue_execution" | "get_call_stack"
+                if params.session_id.is_none() =>
+            {
+                anyhow::bail!(
+                    "{} operation requires 'session_id' parameter",
+                    params.operation
+                );
             }
-            "inspect_variables" => {
-                if params.session_id.is_none() {
-                    anyhow::bail!("inspect_variables operation requires 'session_id' parameter");
-                }
+            "inspect_variables" if params.session_id.is_none() => {
+                anyhow::bail!("inspect_variables operation requires 'session_id' parameter");
             }
             _ => {}
         }
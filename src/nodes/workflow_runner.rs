@@ -180,32 +180,29 @@ impl Node for WorkflowRunnerNode {
 
         // Validate that appropriate workflow source is provided
         match params.operation.as_str() {
-            "run_workflow" | "dry_run" | "validate_and_run" => {
-                if params.workflow_yaml.is_none() && params.workflow_id.is_none() {
-                    anyhow::bail!(
-                        "{} operation requires either 'workflow_yaml' or 'workflow_id' parameter",
-                        params.operation
-                    );
-                }
+            "run_workflow" | "dry_run" | "validate_and_run"
+                if params.workflow_yaml.is_none() && params.workflow_id.is_none() =>
+            {
+                anyhow::bail!(
+                    "{} operation requires either 'workflow_yaml' or 'workflow_id' parameter",
+                    params.operation
+                );
             }
-            "run_workflow_file" => {
-                if params.workflow_path.is_none() {
-                    anyhow::bail!(
-                        "{} operation requires 'workflow_path' parameter",
-                        params.operation
-                    );
-                }
+            "run_workflow_file" if params.workflow_path.is_none() => {
+                anyhow::bail!(
+                    "{} operation requires 'workflow_path' parameter",
+                    params.operation
+                );
             }
-            "run_with_inputs" => {
+            "run_with_inputs"
                 if params.workflow_yaml.is_none()
                     && params.workflow_path.is_none()
-                    && params.workflow_id.is_none()
-                {
-                    anyhow::bail!(
-                        "{} operation requires 'workflow_yaml', 'workflow_path', or 'workflow_id' parameter",
-                        params.operation
-                    );
-                }
+                    && params.workflow_id.is_none() =>
+            {
+                anyhow::bail!(
+                    "{} operation requires 'workflow_yaml', 'workflow_path', or 'workflow_id' parameter",
+                    params.operation
+                );
             }
             _ => {}
         }
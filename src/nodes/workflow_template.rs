@@ -105,6 +105,63 @@ impl NodeType for WorkflowTemplateNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "template_name": {
+                    "type": ["string", "null"],
+                    "description": "Template the operation targeted"
+                },
+                "template_path": {
+                    "type": ["string", "null"],
+                    "description": "File of the template"
+                },
+                "output_path": {
+                    "type": ["string", "null"],
+                    "description": "File the result was written to"
+                },
+                "template_variables": {
+                    "description": "Variables substituted into the template"
+                },
+                "description": {
+                    "type": ["string", "null"],
+                    "description": "Description of the template"
+                },
+                "category": {
+                    "type": ["string", "null"],
+                    "description": "Category of the template"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Only a result written to a file leaves a trace outside the workflow
+        parameters["output_path"].is_string()
+            || matches!(
+                parameters["operation"].as_str(),
+                Some("create_template" | "save_template")
+            )
+    }
 }
 
 #[async_trait]
@@ -171,13 +228,13 @@ impl Node for WorkflowTemplateNode {
 
         // Validate required parameters based on operation
         match params.operation.as_str() {
-            "generate_from_template" | "render_template" => {
-                if params.template_name.is_none() && params.template_path.is_none() {
-                    anyhow::bail!(
-                        "{} operation requires either 'template_name' or 'template_path' parameter",
-                        params.operation
-                    );
-                }
+            "generate_from_template" | "render_template"
+                if params.template_name.is_none() && params.template_path.is_none() =>
+            {
+                anyhow::bail!(
+                    "{} operation requires either 'template_name' or 'template_path' parameter",
+                    params.operation
+                );
             }
             "create_template" | "save_template" => {
                 if params.workflow_yaml.is_none() {
@@ -193,12 +250,12 @@ impl Node for WorkflowTemplateNode {
                     );
                 }
             }
-            "validate_template" => {
-                if params.template_name.is_none() && params.template_path.is_none() {
-                    anyhow::bail!(
-                        "validate_template operation requires either 'template_name' or 'template_path' parameter"
-                    );
-                }
+            "validate_template"
+                if params.template_name.is_none() && params.template_path.is_none() =>
+            {
+                anyhow::bail!(
+                    "validate_template operation requires either 'template_name' or 'template_path' parameter"
+                );
             }
             _ => {}
         }
@@ -109,6 +109,65 @@ impl NodeType for WorkflowVisualizerNode {
     fn required_credential_type(&self) -> Option<&str> {
         None
     }
+
+    fn output_schema(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "message": {
+                    "type": "string",
+                    "description": "Summary of the operation"
+                },
+                "operation": {
+                    "type": "string",
+                    "description": "Operation that was performed"
+                },
+                "workflow_yaml_provided": {
+                    "type": "boolean",
+                    "description": "Whether the workflow was passed inline"
+                },
+                "workflow_path": {
+                    "type": ["string", "null"],
+                    "description": "File of the workflow"
+                },
+                "output_path": {
+                    "type": ["string", "null"],
+                    "description": "File the result was written to"
+                },
+                "format": {
+                    "type": ["string", "null"],
+                    "description": "Format of the diagram"
+                },
+                "include_details": {
+                    "type": "boolean",
+                    "description": "Whether node details are included"
+                },
+                "orientation": {
+                    "type": "string",
+                    "description": "Direction of the diagram"
+                },
+                "theme": {
+                    "type": "string",
+                    "description": "Theme of the diagram"
+                },
+                "context_execution_id": {
+                    "type": "string",
+                    "description": "ID of the execution that ran the node"
+                },
+                "success": {
+                    "type": "boolean",
+                    "description": "Whether the operation succeeded",
+                    "const": true
+                }
+            }
+        })
+    }
+
+    fn has_side_effects(&self, parameters: &serde_json::Value) -> bool {
+        // Only a result written to a file leaves a trace outside the workflow
+        parameters["output_path"].is_string()
+            || matches!(parameters["operation"].as_str(), Some("export_diagram"))
+    }
 }
 
 #[async_trait]
@@ -1,14 +1,21 @@
-use crate::{WorkflowExecutor, create_node_registry, db};
+use crate::nodes::webhook_trigger::WebhookTriggerParams;
+use crate::{
+    CircuitStore, ExecutionEvent, ExecutionObserver, ExecutionStatus, ResumedWait,
+    WorkflowExecutor, create_node_registry, db, recover_interrupted_executions, run_scheduler,
+    run_workers,
+};
 use axum::{
     Router,
     extract::{Path, State},
-    http::StatusCode,
+    http::{HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
 };
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Semaphore, broadcast};
 use tower_http::{
     cors::CorsLayer,
     trace::{DefaultMakeSpan, TraceLayer},
@@ -19,8 +26,88 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct AppState {
     pub pool: PgPool,
+    /// Executor running the executions of the server; clones share its
+    /// event channel
+    executor: WorkflowExecutor,
+    /// Number of workers running queued executions in the server process
+    pub workers: usize,
+    /// Whether the server process runs the scheduler
+    pub scheduler: bool,
+    /// Whether executions started by the server ignore pinned node output
+    pub ignore_pinned_data: bool,
+}
+
+impl AppState {
+    pub fn new(pool: PgPool) -> Self {
+        let registry = create_node_registry(&pool);
+        let executor = WorkflowExecutor::new(pool.clone(), registry)
+            .with_circuit_store(CircuitStore::Postgres(pool.clone()));
+        Self {
+            pool,
+            executor,
+            workers: 0,
+            scheduler: false,
+            ignore_pinned_data: false,
+        }
+    }
+
+    /// Run every node of the executions started by webhooks and schedules
+    /// for real, ignoring pinned output
+    pub fn without_pinned_data(mut self) -> Self {
+        self.ignore_pinned_data = true;
+        self.executor = self.executor.without_pinned_data();
+        self
+    }
+
+    /// Run the scheduler for `schedule_trigger` nodes in the server process
+    pub fn with_scheduler(mut self, scheduler: bool) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// Run queued executions in the server process with the given number of workers
+    pub fn with_workers(mut self, workers: usize) -> Self {
+        self.workers = workers;
+        self
+    }
+
+    /// Limit the number of nodes running at the same time across all executions
+    pub fn with_max_concurrency(mut self, limit: usize) -> Self {
+        let slots = Arc::new(Semaphore::new(limit.max(1)));
+        self.executor = self.executor.with_node_slots(slots);
+        self
+    }
+
+    /// Keep circuit breaker state in the given store instead of Postgres
+    pub fn with_circuit_store(mut self, circuits: CircuitStore) -> Self {
+        self.executor = self.executor.with_circuit_store(circuits);
+        self
+    }
+
+    /// Call `observer` for every event of every execution the server runs
+    pub fn with_observer(mut self, observer: Arc<dyn ExecutionObserver>) -> Self {
+        self.executor = self.executor.with_observer(observer);
+        self
+    }
+
+    /// Receive the events of every execution the server runs from now on,
+    /// whether started by a webhook, a resume, a rerun or a worker
+    pub fn subscribe(&self) -> broadcast::Receiver<ExecutionEvent> {
+        self.executor.subscribe()
+    }
+
+    /// The executor shared by the handlers, workers and recovery
+    fn executor(&self) -> WorkflowExecutor {
+        self.executor.clone()
+    }
 }
 
+/// How often expired waits are checked
+const WAIT_EXPIRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Request header carrying the idempotency key of a webhook trigger
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
 /// Request body for webhook trigger
 #[derive(Debug, Deserialize)]
 pub struct WebhookTriggerRequest {
@@ -36,6 +123,56 @@ pub struct WebhookTriggerResponse {
     pub execution_id: Uuid,
     pub workflow_id: Uuid,
     pub message: String,
+    /// Whether the idempotency key matched an earlier execution, which is
+    /// returned instead of a new one
+    pub duplicate: bool,
+}
+
+/// Request body for resuming a waiting execution
+#[derive(Debug, Deserialize)]
+pub struct ResumeRequest {
+    /// Payload passed on as the output of the wait node
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+/// Response for resuming a waiting execution
+#[derive(Debug, Serialize)]
+pub struct ResumeResponse {
+    pub success: bool,
+    pub execution_id: Uuid,
+    pub status: String,
+    pub message: String,
+}
+
+/// Request body for re-running an execution
+#[derive(Debug, Default, Deserialize)]
+pub struct RerunRequest {
+    /// Node to start from (defaults to the node where the execution failed)
+    #[serde(default)]
+    pub from_node: Option<String>,
+    /// Input data replacing the input of the original execution
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+/// Response for re-running an execution
+#[derive(Debug, Serialize)]
+pub struct RerunResponse {
+    pub success: bool,
+    pub execution_id: Uuid,
+    pub rerun_of: Uuid,
+    pub from_node: Option<String>,
+    pub message: String,
+}
+
+/// Response for cancelling an execution
+#[derive(Debug, Serialize)]
+pub struct CancelResponse {
+    pub success: bool,
+    pub execution_id: Uuid,
+    pub status: String,
+    pub message: String,
 }
 
 /// Error response
@@ -86,6 +223,7 @@ async fn health_check() -> impl IntoResponse {
 async fn trigger_webhook(
     State(state): State<Arc<AppState>>,
     Path((workflow_id, trigger_node_id)): Path<(Uuid, String)>,
+    headers: HeaderMap,
     Json(payload): Json<WebhookTriggerRequest>,
 ) -> Result<Json<WebhookTriggerResponse>, ApiError> {
     tracing::info!(
@@ -128,9 +266,29 @@ async fn trigger_webhook(
         )));
     }
 
-    // Execute the workflow
-    let registry = create_node_registry(&state.pool);
-    let executor = WorkflowExecutor::new(state.pool.clone(), registry);
+    // The header takes precedence over the key computed from the payload
+    let idempotency_key = match headers.get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => Some(
+            value
+                .to_str()
+                .map_err(|_| {
+                    ApiError::BadRequest("Idempotency-Key header is not valid text".to_string())
+                })?
+                .to_string(),
+        ),
+        None => {
+            let params: WebhookTriggerParams =
+                serde_json::from_value(trigger_node.parameters.clone()).map_err(|e| {
+                    ApiError::InternalError(format!("Invalid trigger parameters: {}", e))
+                })?;
+            params.idempotency_key(&payload.data).map_err(|e| {
+                ApiError::BadRequest(format!("Failed to compute idempotency key: {:#}", e))
+            })?
+        }
+    };
+
+    // Queue the execution for a worker
+    let executor = state.executor();
 
     let input_data = if payload.data.is_null() {
         None
@@ -138,35 +296,173 @@ async fn trigger_webhook(
         Some(payload.data)
     };
 
-    let execution = executor
-        .execute_by_id(workflow_id, input_data)
+    let queued = executor
+        .enqueue_by_id(workflow_id, input_data, idempotency_key)
         .await
-        .map_err(|e| ApiError::InternalError(format!("Workflow execution failed: {}", e)))?;
+        .map_err(|e| {
+            ApiError::InternalError(format!("Failed to queue workflow execution: {}", e))
+        })?;
 
-    tracing::info!(
-        "Workflow {} execution started with ID {}",
+    let message = if queued.duplicate {
+        "Workflow execution already exists for this idempotency key"
+    } else {
+        tracing::info!(
+            "Workflow {} execution queued with ID {}",
+            workflow_id,
+            queued.execution.id
+        );
+        "Workflow execution queued successfully"
+    };
+
+    Ok(Json(WebhookTriggerResponse {
+        success: true,
+        execution_id: queued.execution.id,
         workflow_id,
-        execution.id
+        message: message.to_string(),
+        duplicate: queued.duplicate,
+    }))
+}
+
+/// Resume endpoint for executions waiting at a `wait_webhook` node.
+///
+/// The execution is queued for a worker to continue, so the request returns
+/// `202 Accepted` without waiting for the rest of the workflow.
+async fn resume_execution(
+    State(state): State<Arc<AppState>>,
+    Path(wait_id): Path<String>,
+    Json(payload): Json<ResumeRequest>,
+) -> Result<(StatusCode, Json<ResumeResponse>), ApiError> {
+    tracing::info!("Resume received for wait {}", wait_id);
+
+    let resumed = state
+        .executor()
+        .enqueue_resume(&wait_id, payload.data)
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Failed to resume execution: {}", e)))?
+        .ok_or_else(|| ApiError::NotFound(format!("No execution is waiting on '{}'", wait_id)))?;
+
+    let execution = match resumed {
+        ResumedWait::Queued(execution) => execution,
+        ResumedWait::Expired(execution) => {
+            return Err(ApiError::BadRequest(format!(
+                "Wait '{}' expired: {}",
+                wait_id,
+                execution.error.unwrap_or_default()
+            )));
+        }
+    };
+
+    tracing::info!(
+        "Execution {} queued to resume from wait {}",
+        execution.id,
+        wait_id
     );
 
-    Ok(Json(WebhookTriggerResponse {
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ResumeResponse {
+            success: true,
+            execution_id: execution.id,
+            status: execution.status.to_string(),
+            message: "Workflow execution queued to resume".to_string(),
+        }),
+    ))
+}
+
+/// Cancel endpoint for running or waiting executions
+async fn cancel_execution(
+    State(state): State<Arc<AppState>>,
+    Path(execution_id): Path<Uuid>,
+) -> Result<Json<CancelResponse>, ApiError> {
+    tracing::info!("Cancellation requested for execution {}", execution_id);
+
+    // Make sure the execution exists before trying to cancel it
+    db::get_workflow_execution(&state.pool, execution_id)
+        .await
+        .map_err(|e| ApiError::NotFound(format!("Execution not found: {}", e)))?;
+
+    let execution = state
+        .executor()
+        .cancel(execution_id)
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to cancel execution: {}", e)))?;
+
+    let message = if execution.status == ExecutionStatus::Cancelled {
+        "Workflow execution cancelled"
+    } else {
+        "Workflow execution cancellation requested"
+    };
+
+    Ok(Json(CancelResponse {
+        success: true,
+        execution_id,
+        status: execution.status.to_string(),
+        message: message.to_string(),
+    }))
+}
+
+/// Re-run endpoint for finished executions
+async fn rerun_execution(
+    State(state): State<Arc<AppState>>,
+    Path(execution_id): Path<Uuid>,
+    Json(payload): Json<RerunRequest>,
+) -> Result<Json<RerunResponse>, ApiError> {
+    tracing::info!("Re-run requested for execution {}", execution_id);
+
+    // Make sure the execution exists before trying to re-run it
+    db::get_workflow_execution(&state.pool, execution_id)
+        .await
+        .map_err(|e| ApiError::NotFound(format!("Execution not found: {}", e)))?;
+
+    let execution = state
+        .executor()
+        .enqueue_rerun(execution_id, payload.from_node, payload.data)
+        .await
+        .map_err(|e| ApiError::BadRequest(format!("Failed to re-run execution: {:#}", e)))?;
+
+    Ok(Json(RerunResponse {
         success: true,
         execution_id: execution.id,
-        workflow_id,
-        message: "Workflow execution started successfully".to_string(),
+        rerun_of: execution_id,
+        from_node: execution.rerun_from_node,
+        message: "Workflow execution re-run queued successfully".to_string(),
     }))
 }
 
+/// Periodically fail executions whose waits expired
+async fn expire_waits(state: Arc<AppState>) {
+    let mut interval = tokio::time::interval(WAIT_EXPIRY_INTERVAL);
+    loop {
+        interval.tick().await;
+        match state.executor().expire_waits().await {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Expired {} waiting executions", count),
+            Err(e) => tracing::error!("Failed to expire waiting executions: {}", e),
+        }
+    }
+}
+
 /// Create the webhook server router
-pub fn create_router(pool: PgPool) -> Router {
-    let state = Arc::new(AppState { pool });
+pub fn create_router(state: AppState) -> Router {
+    router(Arc::new(state))
+}
 
+fn router(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/health", get(health_check))
         .route(
             "/api/v1/webhook/:workflow_id/trigger/:trigger_node_id",
             post(trigger_webhook),
         )
+        .route("/api/v1/resume/:wait_id", post(resume_execution))
+        .route(
+            "/api/v1/executions/:execution_id/cancel",
+            post(cancel_execution),
+        )
+        .route(
+            "/api/v1/executions/:execution_id/rerun",
+            post(rerun_execution),
+        )
         .layer(
             TraceLayer::new_for_http()
                 .make_span_with(DefaultMakeSpan::default().include_headers(true)),
@@ -176,8 +472,17 @@ pub fn create_router(pool: PgPool) -> Router {
 }
 
 /// Start the webhook server
-pub async fn start_server(pool: PgPool, host: &str, port: u16) -> anyhow::Result<()> {
-    let app = create_router(pool);
+pub async fn start_server(state: AppState, host: &str, port: u16) -> anyhow::Result<()> {
+    let state = Arc::new(state);
+    tokio::spawn(expire_waits(Arc::clone(&state)));
+    tokio::spawn(recover_interrupted_executions(state.executor()));
+    if state.workers > 0 {
+        tokio::spawn(run_workers(state.executor(), state.workers));
+    }
+    if state.scheduler {
+        tokio::spawn(run_scheduler(state.pool.clone(), state.ignore_pinned_data));
+    }
+    let app = router(state);
 
     let addr = format!("{}:{}", host, port);
     let listener = tokio::net::TcpListener::bind(&addr).await?;
@@ -187,6 +492,10 @@ pub async fn start_server(pool: PgPool, host: &str, port: u16) -> anyhow::Result
         "Webhook endpoint: POST http://{}/api/v1/webhook/{{workflow-id}}/trigger/{{trigger-node-id}}",
         addr
     );
+    tracing::info!(
+        "Resume endpoint: POST http://{}/api/v1/resume/{{wait-id}}",
+        addr
+    );
 
     axum::serve(listener, app).await?;
 
@@ -215,7 +524,7 @@ mod tests {
             .await
             .expect("Failed to connect to database");
 
-        let app = create_router(pool);
+        let app = create_router(AppState::new(pool));
 
         let response = app
             .oneshot(
@@ -229,4 +538,68 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    #[ignore] // Requires a database connection
+    async fn test_subscribers_receive_webhook_execution_events() {
+        let database_url =
+            std::env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(2)
+            .connect(&database_url)
+            .await
+            .expect("Failed to connect to database");
+
+        let yaml = format!(
+            r#"
+name: Webhook Events {}
+nodes:
+  - id: webhook
+    node_type: webhook_trigger
+    name: Webhook
+    parameters: {{}}
+edges: []
+"#,
+            Uuid::new_v4()
+        );
+        let definition = crate::config::load_workflow_from_str(&yaml).unwrap();
+        let workflow = db::create_workflow(&pool, &definition.to_workflow().unwrap())
+            .await
+            .unwrap();
+
+        let state = AppState::new(pool);
+        let mut events = state.subscribe();
+
+        let response = create_router(state.clone())
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri(format!("/api/v1/webhook/{}/trigger/webhook", workflow.id))
+                    .header("content-type", "application/json")
+                    .body(Body::from(r#"{"data": {"id": 1}}"#))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Run the queued execution the way a server worker does
+        while state.executor().run_next().await.unwrap().is_some() {}
+
+        let mut started = false;
+        let mut finished = false;
+        while let Ok(event) = events.try_recv() {
+            match event {
+                ExecutionEvent::ExecutionStarted { workflow_id, .. } => {
+                    started |= workflow_id == workflow.id;
+                }
+                ExecutionEvent::ExecutionFinished { workflow_id, .. } => {
+                    finished |= workflow_id == workflow.id;
+                }
+                _ => {}
+            }
+        }
+        assert!(started && finished);
+    }
 }